@@ -0,0 +1,297 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.dtos;
+
+import java.io.IOException;
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.annotation.Annotation;
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Field;
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Comparator;
+import java.util.Enumeration;
+import java.util.List;
+import java.util.jar.JarEntry;
+import java.util.jar.JarFile;
+import java.util.stream.Collectors;
+
+/**
+ * Describes the constructors, methods and fields that a class declares, whatever their visibility, along with its
+ * superclass, interfaces, annotations and modifiers, in a form that can be transferred to the Rust world.
+ */
+public class ClassDescription {
+    private final String name;
+    private final boolean isInterface;
+    private final String superclass;
+    private final List<String> interfaces;
+    private final List<String> annotations;
+    private final List<String> modifiers;
+    private final List<MethodDescription> constructors;
+    private final List<MethodDescription> methods;
+    private final List<FieldDescription> fields;
+
+    public ClassDescription(Class<?> clazz) {
+        this.name = clazz.getName();
+        this.isInterface = clazz.isInterface();
+        this.superclass = clazz.getSuperclass() == null ? null : clazz.getSuperclass().getName();
+        this.interfaces = Arrays.stream(clazz.getInterfaces()).map(Class::getName).collect(Collectors.toList());
+        this.annotations = annotationNames(clazz.getAnnotations());
+        this.modifiers = modifierNames(clazz.getModifiers() & Modifier.classModifiers());
+        // The reflection does not return the members in any particular order
+        this.constructors = Arrays.stream(clazz.getDeclaredConstructors())
+                .filter(constructor -> !constructor.isSynthetic())
+                .map(MethodDescription::new)
+                .sorted(MethodDescription.ORDER)
+                .collect(Collectors.toList());
+        this.methods = Arrays.stream(clazz.getDeclaredMethods())
+                .filter(method -> !method.isSynthetic() && !method.isBridge())
+                .map(MethodDescription::new)
+                .sorted(MethodDescription.ORDER)
+                .collect(Collectors.toList());
+        this.fields = Arrays.stream(clazz.getDeclaredFields())
+                .filter(field -> !field.isSynthetic())
+                .map(FieldDescription::new)
+                .sorted(Comparator.comparing(FieldDescription::getName))
+                .collect(Collectors.toList());
+    }
+
+    /**
+     * Describes a class, or a primitive type (eg. {@code int}).
+     *
+     * @param className The name of the class
+     * @return The description
+     * @throws ClassNotFoundException If the class cannot be found
+     */
+    public static ClassDescription describe(String className) throws ClassNotFoundException {
+        return new ClassDescription(Utils.forNameEnhanced(className));
+    }
+
+    /**
+     * Describes the public classes that are contained in a jar. Anonymous and local classes are omitted.
+     * The jar should be in the classpath. The classes that cannot be linked, because they depend on classes that are
+     * not in the classpath, are omitted as well.
+     *
+     * @param jarPath The path of the jar
+     * @return The descriptions, ordered by class name
+     * @throws IOException If the jar cannot be read
+     * @throws ClassNotFoundException If a class of the jar cannot be found in the classpath
+     */
+    public static List<ClassDescription> describeJar(String jarPath) throws IOException, ClassNotFoundException {
+        return describeJar(jarPath, ClassDescription.class.getClassLoader());
+    }
+
+    static List<ClassDescription> describeJar(String jarPath, ClassLoader classLoader) throws IOException, ClassNotFoundException {
+        List<ClassDescription> descriptions = new ArrayList<>();
+        try (JarFile jar = new JarFile(jarPath)) {
+            Enumeration<JarEntry> entries = jar.entries();
+            while (entries.hasMoreElements()) {
+                String entryName = entries.nextElement().getName();
+                if (!entryName.endsWith(".class") || entryName.endsWith("module-info.class") || entryName.endsWith("package-info.class")) {
+                    continue;
+                }
+                String className = entryName.substring(0, entryName.length() - ".class".length()).replace('/', '.');
+                try {
+                    Class<?> clazz = Class.forName(className, false, classLoader);
+                    if (Modifier.isPublic(clazz.getModifiers()) && !clazz.isAnonymousClass() && !clazz.isLocalClass() && !clazz.isSynthetic()) {
+                        descriptions.add(new ClassDescription(clazz));
+                    }
+                } catch (LinkageError error) {
+                    // A class that references classes which are missing from the classpath is skipped
+                }
+            }
+        }
+        descriptions.sort(Comparator.comparing(ClassDescription::getName));
+        return descriptions;
+    }
+
+    public String getName() {
+        return name;
+    }
+
+    public boolean isInterface() {
+        return isInterface;
+    }
+
+    /**
+     * @return The name of the superclass, or null for interfaces, primitives and {@code java.lang.Object}
+     */
+    public String getSuperclass() {
+        return superclass;
+    }
+
+    /**
+     * @return The names of the interfaces that the class directly implements
+     */
+    public List<String> getInterfaces() {
+        return interfaces;
+    }
+
+    /**
+     * @return The class names of the runtime annotations, including the inherited ones
+     */
+    public List<String> getAnnotations() {
+        return annotations;
+    }
+
+    /**
+     * @return The modifiers, as named by {@link Modifier#toString(int)} (eg. {@code public}, {@code abstract})
+     */
+    public List<String> getModifiers() {
+        return modifiers;
+    }
+
+    public List<MethodDescription> getConstructors() {
+        return constructors;
+    }
+
+    public List<MethodDescription> getMethods() {
+        return methods;
+    }
+
+    public List<FieldDescription> getFields() {
+        return fields;
+    }
+
+    /**
+     * Describes a constructor or a method.
+     */
+    public static class MethodDescription {
+        static final Comparator<MethodDescription> ORDER = Comparator.comparing(MethodDescription::getName)
+                .thenComparing(method -> method.getParameterTypes().size())
+                .thenComparing(method -> String.join(",", method.getParameterTypes()));
+
+        private final String name;
+        private final List<String> parameterTypes;
+        private final String returnType;
+        private final boolean isStatic;
+        private final boolean isVarArgs;
+        private final List<String> annotations;
+        private final List<String> modifiers;
+
+        MethodDescription(Constructor<?> constructor) {
+            this.name = constructor.getDeclaringClass().getName();
+            this.parameterTypes = typeNames(constructor.getParameterTypes());
+            this.returnType = constructor.getDeclaringClass().getTypeName();
+            this.isStatic = false;
+            this.isVarArgs = constructor.isVarArgs();
+            this.annotations = annotationNames(constructor.getDeclaredAnnotations());
+            this.modifiers = modifierNames(constructor.getModifiers() & Modifier.constructorModifiers());
+        }
+
+        MethodDescription(Method method) {
+            this.name = method.getName();
+            this.parameterTypes = typeNames(method.getParameterTypes());
+            this.returnType = method.getReturnType().getTypeName();
+            this.isStatic = Modifier.isStatic(method.getModifiers());
+            this.isVarArgs = method.isVarArgs();
+            this.annotations = annotationNames(method.getDeclaredAnnotations());
+            this.modifiers = modifierNames(method.getModifiers() & Modifier.methodModifiers());
+        }
+
+        public String getName() {
+            return name;
+        }
+
+        /**
+         * @return The names of the parameter types, as returned by {@link Class#getTypeName()} (eg. {@code int[]})
+         */
+        public List<String> getParameterTypes() {
+            return parameterTypes;
+        }
+
+        public String getReturnType() {
+            return returnType;
+        }
+
+        public boolean isStatic() {
+            return isStatic;
+        }
+
+        public boolean isVarArgs() {
+            return isVarArgs;
+        }
+
+        public List<String> getAnnotations() {
+            return annotations;
+        }
+
+        public List<String> getModifiers() {
+            return modifiers;
+        }
+
+        private static List<String> typeNames(Class<?>[] types) {
+            return Arrays.stream(types).map(Class::getTypeName).collect(Collectors.toList());
+        }
+    }
+
+    /**
+     * Describes a field.
+     */
+    public static class FieldDescription {
+        private final String name;
+        private final String type;
+        private final boolean isStatic;
+        private final boolean isFinal;
+        private final List<String> annotations;
+        private final List<String> modifiers;
+
+        FieldDescription(Field field) {
+            this.name = field.getName();
+            this.type = field.getType().getTypeName();
+            this.isStatic = Modifier.isStatic(field.getModifiers());
+            this.isFinal = Modifier.isFinal(field.getModifiers());
+            this.annotations = annotationNames(field.getDeclaredAnnotations());
+            this.modifiers = modifierNames(field.getModifiers() & Modifier.fieldModifiers());
+        }
+
+        public String getName() {
+            return name;
+        }
+
+        public String getType() {
+            return type;
+        }
+
+        public boolean isStatic() {
+            return isStatic;
+        }
+
+        public boolean isFinal() {
+            return isFinal;
+        }
+
+        public List<String> getAnnotations() {
+            return annotations;
+        }
+
+        public List<String> getModifiers() {
+            return modifiers;
+        }
+    }
+
+    private static List<String> annotationNames(Annotation[] annotations) {
+        return Arrays.stream(annotations).map(annotation -> annotation.annotationType().getName()).collect(Collectors.toList());
+    }
+
+    // The modifiers should be masked, so that eg. the varargs bit of a method is not named as transient
+    private static List<String> modifierNames(int modifiers) {
+        String names = Modifier.toString(modifiers);
+        return names.isEmpty() ? new ArrayList<>() : Arrays.asList(names.split(" "));
+    }
+}
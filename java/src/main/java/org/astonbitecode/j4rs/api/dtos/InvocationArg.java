@@ -17,6 +17,7 @@ package org.astonbitecode.j4rs.api.dtos;
 import org.astonbitecode.j4rs.api.Instance;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
 import org.astonbitecode.j4rs.errors.InvalidArgumentException;
+import org.astonbitecode.j4rs.json.Codec;
 import org.astonbitecode.j4rs.utils.Utils;
 
 public class InvocationArg implements Instance {
@@ -26,6 +27,14 @@ public class InvocationArg implements Instance {
     public static final String CONTENTS_ARRAY = "org.astonbitecode.j4rs.api.dtos.Array";
     private final Instance instance;
     private final String json;
+    /**
+     * The serialized argument, if it is not a json document.
+     */
+    private final byte[] payload;
+    /**
+     * The format of the serialized argument.
+     */
+    private final Codec.Format format;
     /**
      * If not serialized, the argument is taken straight by the Java code as Object.
      * Otherwise, the argument is a json document that needs to be deserialized to an
@@ -39,6 +48,8 @@ public class InvocationArg implements Instance {
 
     public InvocationArg(String className, Instance instance) {
         this.json = null;
+        this.payload = null;
+        this.format = Codec.Format.JSON;
         this.className = className;
         this.instance = instance;
         this.serialized = false;
@@ -46,6 +57,8 @@ public class InvocationArg implements Instance {
 
     public InvocationArg(Instance instance) {
         this.json = null;
+        this.payload = null;
+        this.format = Codec.Format.JSON;
         this.className = instance.getClass().getName();
         this.instance = instance;
         this.serialized = false;
@@ -55,6 +68,17 @@ public class InvocationArg implements Instance {
         this.instance = null;
         this.className = className;
         this.json = json;
+        this.payload = null;
+        this.format = Codec.Format.JSON;
+        this.serialized = true;
+    }
+
+    public InvocationArg(String className, byte[] payload, String format) {
+        this.instance = null;
+        this.className = className;
+        this.json = null;
+        this.payload = payload;
+        this.format = Codec.Format.valueOf(format);
         this.serialized = true;
     }
 
@@ -62,6 +86,8 @@ public class InvocationArg implements Instance {
         this.instance = new JsonInvocationImpl(object, Utils.forNameEnhanced(className));
         this.className = className;
         this.json = null;
+        this.payload = null;
+        this.format = Codec.Format.JSON;
         this.serialized = false;
     }
 
@@ -96,12 +122,39 @@ public class InvocationArg implements Instance {
         if (!isSerialized()) {
             throw new InvalidArgumentException("This InvocationArg of class " + className + " is created by Java code.");
         }
+        if (format != Codec.Format.JSON) {
+            throw new InvalidArgumentException("This InvocationArg of class " + className + " is serialized as " + format + ".");
+        }
         return json;
     }
 
+    /**
+     * The format that the argument is serialized to. Arguments that are not serialized are considered JSON.
+     *
+     * @return The format
+     */
+    public Codec.Format getFormat() {
+        return format;
+    }
+
+    /**
+     * The serialized argument, for the formats other than JSON.
+     *
+     * @return The payload
+     */
+    public byte[] getPayload() {
+        if (!isSerialized()) {
+            throw new InvalidArgumentException("This InvocationArg of class " + className + " is created by Java code.");
+        }
+        if (format == Codec.Format.JSON) {
+            throw new InvalidArgumentException("This InvocationArg of class " + className + " is serialized as JSON.");
+        }
+        return payload;
+    }
+
     @Override
     public String toString() {
-        return "classname:" + this.className + ", serialized:" + this.serialized + ", json:" + this.json + ", instance:" + this.instance;
+        return "classname:" + this.className + ", serialized:" + this.serialized + ", json:" + this.json + ", format:" + this.format + ", instance:" + this.instance;
     }
 
     @Override
@@ -27,7 +27,7 @@ public class InvocationArgGenerator {
         GeneratedArg[] generatedArgArr = Arrays.stream(args).map(invArg -> {
             GeneratedArg generatedArg;
             if (invArg.isSerialized()) {
-                ObjectValue objValue = JsonValueFactory.create(invArg.getJson(), invArg.getClassName());
+                ObjectValue objValue = JsonValueFactory.createFromSerialized(invArg);
                 try {
                     // If the invArg is an array, use its type class. In other cases, use the forNameEnhanced to retrieve its class.
                     generatedArg = invArg.getClassName().equals(InvocationArg.CONTENTS_ARRAY) ?
@@ -0,0 +1,81 @@
+/*
+ * Copyright 2018 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.dtos;
+
+import com.fasterxml.jackson.core.JsonProcessingException;
+import org.astonbitecode.j4rs.json.Codec;
+
+import java.util.Arrays;
+import java.util.Collections;
+import java.util.IdentityHashMap;
+import java.util.List;
+import java.util.Set;
+import java.util.stream.Collectors;
+
+/**
+ * Describes a {@link Throwable} (class name, message, stack trace and the chain of causes)
+ * in a form that can be transferred to the Rust world.
+ */
+public class ThrowableDescription {
+    private static final Codec CODEC = new Codec();
+
+    private final String className;
+    private final String message;
+    private final List<String> stackTrace;
+    private final ThrowableDescription cause;
+
+    public ThrowableDescription(Throwable throwable) {
+        this(throwable, Collections.newSetFromMap(new IdentityHashMap<>()));
+    }
+
+    private ThrowableDescription(Throwable throwable, Set<Throwable> visited) {
+        visited.add(throwable);
+        this.className = throwable.getClass().getName();
+        this.message = throwable.getMessage();
+        this.stackTrace = Arrays.stream(throwable.getStackTrace())
+                .map(StackTraceElement::toString)
+                .collect(Collectors.toList());
+        Throwable c = throwable.getCause();
+        // Guard against circular cause chains
+        this.cause = c == null || visited.contains(c) ? null : new ThrowableDescription(c, visited);
+    }
+
+    /**
+     * Creates the JSON representation of the description of the passed {@link Throwable}.
+     *
+     * @param throwable The Throwable to describe
+     * @return The JSON String
+     * @throws JsonProcessingException In case the description cannot be encoded
+     */
+    public static String describe(Throwable throwable) throws JsonProcessingException {
+        return CODEC.encode(new ThrowableDescription(throwable));
+    }
+
+    public String getClassName() {
+        return className;
+    }
+
+    public String getMessage() {
+        return message;
+    }
+
+    public List<String> getStackTrace() {
+        return stackTrace;
+    }
+
+    public ThrowableDescription getCause() {
+        return cause;
+    }
+}
@@ -20,13 +20,20 @@ import org.astonbitecode.j4rs.api.dtos.InvocationArg;
 import org.astonbitecode.j4rs.api.dtos.InvocationArgGenerator;
 import org.astonbitecode.j4rs.api.invocation.InstanceGenerator;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
+import org.astonbitecode.j4rs.api.value.ObjectGraphs;
 import org.astonbitecode.j4rs.errors.InstantiationException;
+import org.astonbitecode.j4rs.errors.InvocationException;
 import org.astonbitecode.j4rs.utils.Utils;
 
 import java.lang.reflect.*;
 import java.util.ArrayList;
 import java.util.Arrays;
+import java.util.LinkedHashMap;
+import java.util.LinkedHashSet;
 import java.util.List;
+import java.util.Map;
+import java.util.Optional;
+import java.util.Set;
 import java.util.stream.Collectors;
 
 public class NativeInstantiationImpl {
@@ -68,6 +75,154 @@ public class NativeInstantiationImpl {
         }
     }
 
+    public static Instance createJavaSet(InvocationArg... args) {
+        try {
+            Set<Object> set = new LinkedHashSet<>();
+            for (GeneratedArg arg : generateArgObjects(args)) {
+                set.add(arg.getObject());
+            }
+            return new JsonInvocationImpl(set, set.getClass());
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot create Java Set", error);
+        }
+    }
+
+    /**
+     * Creates a Map from keys and values that alternate in the args (key1, value1, key2, value2...).
+     */
+    public static Instance createJavaMap(InvocationArg... keysAndValues) {
+        try {
+            if (keysAndValues.length % 2 != 0) {
+                throw new IllegalArgumentException("Each key of a Java Map should be followed by its value");
+            }
+            GeneratedArg[] params = generateArgObjects(keysAndValues);
+            Map<Object, Object> map = new LinkedHashMap<>();
+            for (int i = 0; i < params.length; i += 2) {
+                map.put(params[i].getObject(), params[i + 1].getObject());
+            }
+            return new JsonInvocationImpl(map, map.getClass());
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot create Java Map", error);
+        }
+    }
+
+    /**
+     * Creates an Optional that contains the value of the arg, or an empty Optional if no arg is passed.
+     */
+    public static Instance createJavaOptional(InvocationArg... args) {
+        try {
+            GeneratedArg[] params = generateArgObjects(args);
+            Optional<Object> optional = params.length == 0 ? Optional.empty() : Optional.ofNullable(params[0].getObject());
+            return new JsonInvocationImpl(optional, Optional.class);
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot create Java Optional", error);
+        }
+    }
+
+    /**
+     * Retrieves the constant with the provided name, of the enum with the provided class name.
+     */
+    @SuppressWarnings({"unchecked", "rawtypes"})
+    public static Instance createJavaEnum(String className, String name) {
+        try {
+            Class<?> clazz = Utils.forNameEnhanced(className);
+            if (!clazz.isEnum()) {
+                throw new IllegalArgumentException(className + " is not an enum");
+            }
+            Object constant = Enum.valueOf((Class<Enum>) clazz, name);
+            return new JsonInvocationImpl(constant, clazz);
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot retrieve the constant " + name + " of the Java enum " + className, error);
+        }
+    }
+
+    /**
+     * Retrieves the field with the provided name, of the object that the instance contains.
+     * If forceAccessible is true, the field may be private or declared by any superclass.
+     */
+    public static Instance getField(Instance instance, String fieldName, boolean forceAccessible) {
+        try {
+            Field field = findField(classOf(instance), fieldName, forceAccessible);
+            return new JsonInvocationImpl(field.get(instance.getObject()), field.getType());
+        } catch (Exception error) {
+            throw new InvocationException("Error while accessing field " + fieldName + " of Class " + instance.getObjectClass().getName(), error);
+        }
+    }
+
+    /**
+     * Sets the field with the provided name, of the object that the instance contains.
+     * If forceAccessible is true, the field may be private, final or declared by any superclass.
+     */
+    public static void setField(Instance instance, String fieldName, InvocationArg arg, boolean forceAccessible) {
+        try {
+            Field field = findField(classOf(instance), fieldName, forceAccessible);
+            field.set(instance.getObject(), fieldValue(field, arg));
+        } catch (Exception error) {
+            throw new InvocationException("Error while setting field " + fieldName + " of Class " + instance.getObjectClass().getName(), error);
+        }
+    }
+
+    /**
+     * Retrieves the static field with the provided name, of the class with the provided name.
+     * If forceAccessible is true, the field may be private or declared by any superclass.
+     */
+    public static Instance getStaticField(String className, String fieldName, boolean forceAccessible) {
+        try {
+            Field field = findStaticField(className, fieldName, forceAccessible);
+            return new JsonInvocationImpl(field.get(null), field.getType());
+        } catch (Exception error) {
+            throw new InvocationException("Error while accessing static field " + fieldName + " of Class " + className, error);
+        }
+    }
+
+    /**
+     * Sets the static field with the provided name, of the class with the provided name.
+     * If forceAccessible is true, the field may be private or declared by any superclass.
+     */
+    public static void setStaticField(String className, String fieldName, InvocationArg arg, boolean forceAccessible) {
+        try {
+            Field field = findStaticField(className, fieldName, forceAccessible);
+            field.set(null, fieldValue(field, arg));
+        } catch (Exception error) {
+            throw new InvocationException("Error while setting static field " + fieldName + " of Class " + className, error);
+        }
+    }
+
+    private static Class<?> classOf(Instance instance) {
+        Object object = instance.getObject();
+        return object != null ? object.getClass() : instance.getObjectClass();
+    }
+
+    private static Field findStaticField(String className, String fieldName, boolean forceAccessible) throws Exception {
+        Field field = findField(Utils.forNameEnhanced(className), fieldName, forceAccessible);
+        if (!Modifier.isStatic(field.getModifiers())) {
+            throw new NoSuchFieldException("The field " + fieldName + " is not static");
+        }
+        return field;
+    }
+
+    static Field findField(Class<?> clazz, String fieldName, boolean forceAccessible) throws NoSuchFieldException {
+        if (forceAccessible) {
+            for (Class<?> current = clazz; current != null; current = current.getSuperclass()) {
+                try {
+                    Field field = current.getDeclaredField(fieldName);
+                    field.setAccessible(true);
+                    return field;
+                } catch (NoSuchFieldException error) {
+                    // Search in the superclass
+                }
+            }
+        }
+        // The public fields, including the constants of the interfaces
+        return clazz.getField(fieldName);
+    }
+
+    // The value of the arg, converted to the type of the field (eg. a Long to an int)
+    private static Object fieldValue(Field field, InvocationArg arg) throws Exception {
+        Object value = generateArgObjects(new InvocationArg[]{arg})[0].getObject();
+        return ObjectGraphs.convert(value, field.getType().getName());
+    }
+
     static GeneratedArg[] generateArgObjects(InvocationArg[] args) throws Exception {
         return gen.generateArgObjects(args);
     }
@@ -211,7 +211,8 @@ public class JsonInvocationImpl<T> implements Instance<T> {
                             matchedParams.add(true);
                         } else if (typ instanceof GenericArrayType) {
                             // TODO: Improve by checking the actual types of the arrays?
-                            matchedParams.add(argTypes[i].isArray());
+                            // A generic array cannot hold primitives (eg. a T[] parameter does not accept an int[])
+                            matchedParams.add(argTypes[i].isArray() && !argTypes[i].getComponentType().isPrimitive());
                         } else if (typ instanceof Class) {
                             // In case of TypeVariable, the arg matches via the equals method
                             matchedParams.add(((Class<?>) typ).isAssignableFrom(argTypes[i]));
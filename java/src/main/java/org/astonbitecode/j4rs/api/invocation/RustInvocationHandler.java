@@ -0,0 +1,147 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.api.value.JsonValueFactory;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.ref.PhantomReference;
+import java.lang.ref.ReferenceQueue;
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+import java.util.Set;
+import java.util.concurrent.ConcurrentHashMap;
+
+/**
+ * An {@link InvocationHandler} that implements the methods of a {@link Proxy} by calling a Rust closure.
+ * <p>
+ * The closure is released when the handler (and therefore the proxy) is garbage collected.
+ */
+public class RustInvocationHandler implements InvocationHandler {
+    private static final ReferenceQueue<RustInvocationHandler> collectedHandlers = new ReferenceQueue<>();
+    // Keeps the references reachable until their closures are released
+    private static final Set<ClosureReference> closureReferences = ConcurrentHashMap.newKeySet();
+    private static Thread releaser;
+
+    private static native Instance invokeRust(long invokeFnAddress, long closureAddress, String methodName, Instance[] args);
+
+    private static native void releaseRust(long releaseFnAddress, long closureAddress);
+
+    private final String interfaceName;
+    private final long invokeFnAddress;
+    private final long closureAddress;
+
+    private RustInvocationHandler(String interfaceName, long invokeFnAddress, long closureAddress) {
+        this.interfaceName = interfaceName;
+        this.invokeFnAddress = invokeFnAddress;
+        this.closureAddress = closureAddress;
+    }
+
+    /**
+     * Creates a proxy that implements an interface by calling a Rust closure.
+     *
+     * @param interfaceName The fully qualified name of the interface.
+     * @param invokeFnAddress The address of the Rust function that invokes the closure.
+     * @param releaseFnAddress The address of the Rust function that releases the closure.
+     * @param closureAddress The address of the closure.
+     * @return The proxy. From now on, the closure is owned by the proxy.
+     * @throws ClassNotFoundException If the interface is not found.
+     */
+    public static Object newProxy(String interfaceName, Long invokeFnAddress, Long releaseFnAddress, Long closureAddress) throws ClassNotFoundException {
+        Class<?> iface = Utils.forNameEnhanced(interfaceName);
+        if (!iface.isInterface()) {
+            throw new InvocationException("Cannot create a proxy for " + interfaceName + " because it is not an interface");
+        }
+        RustInvocationHandler handler = new RustInvocationHandler(interfaceName, invokeFnAddress, closureAddress);
+        Object proxy = Proxy.newProxyInstance(iface.getClassLoader(), new Class<?>[]{iface}, handler);
+        closureReferences.add(new ClosureReference(handler, releaseFnAddress, closureAddress));
+        startReleaser();
+        return proxy;
+    }
+
+    @Override
+    public Object invoke(Object proxy, Method method, Object[] args) {
+        if (method.getDeclaringClass() == Object.class) {
+            return invokeObjectMethod(proxy, method, args);
+        }
+        Class<?>[] parameterTypes = method.getParameterTypes();
+        Instance[] instances = new Instance[args == null ? 0 : args.length];
+        for (int i = 0; i < instances.length; i++) {
+            Class<?> clazz = args[i] == null ? parameterTypes[i] : args[i].getClass();
+            instances[i] = InstanceGenerator.create(args[i], clazz);
+        }
+        Instance result = invokeRust(invokeFnAddress, closureAddress, method.getName(), instances);
+        return result == null || method.getReturnType() == void.class ? null : objectOf(result);
+    }
+
+    // The Instances that are created by Rust may contain serialized values
+    private static Object objectOf(Instance instance) {
+        if (instance instanceof InvocationArg && ((InvocationArg) instance).isSerialized()) {
+            return JsonValueFactory.createFromSerialized((InvocationArg) instance).getObject();
+        }
+        return instance.getObject();
+    }
+
+    private Object invokeObjectMethod(Object proxy, Method method, Object[] args) {
+        switch (method.getName()) {
+            case "equals":
+                return proxy == args[0];
+            case "hashCode":
+                return System.identityHashCode(proxy);
+            case "toString":
+                return "RustProxy(" + interfaceName + ")@" + Integer.toHexString(System.identityHashCode(proxy));
+            default:
+                throw new InvocationException("Method " + method.getName() + " is not supported by Rust proxies");
+        }
+    }
+
+    private static synchronized void startReleaser() {
+        if (releaser == null) {
+            releaser = new Thread(RustInvocationHandler::releaseCollected, "j4rs-proxy-releaser");
+            releaser.setDaemon(true);
+            releaser.start();
+        }
+    }
+
+    private static void releaseCollected() {
+        while (true) {
+            try {
+                ClosureReference reference = (ClosureReference) collectedHandlers.remove();
+                closureReferences.remove(reference);
+                releaseRust(reference.releaseFnAddress, reference.closureAddress);
+            } catch (InterruptedException error) {
+                return;
+            } catch (Throwable error) {
+                System.err.println("Could not release the Rust closure of a proxy");
+                error.printStackTrace();
+            }
+        }
+    }
+
+    private static class ClosureReference extends PhantomReference<RustInvocationHandler> {
+        private final long releaseFnAddress;
+        private final long closureAddress;
+
+        ClosureReference(RustInvocationHandler handler, long releaseFnAddress, long closureAddress) {
+            super(handler, collectedHandlers);
+            this.releaseFnAddress = releaseFnAddress;
+            this.closureAddress = closureAddress;
+        }
+    }
+}
@@ -17,6 +17,9 @@ package org.astonbitecode.j4rs.api.java2rust;
 import org.astonbitecode.j4rs.api.Instance;
 import org.astonbitecode.j4rs.api.ObjectValue;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
+import org.astonbitecode.j4rs.json.Codec;
+
+import java.io.IOException;
 
 public class Java2RustUtils {
     @SuppressWarnings("unchecked")
@@ -28,4 +31,16 @@ public class Java2RustUtils {
     public static <T> T getObjectCasted(Instance instance) {
         return (T) instance.getObject();
     }
+
+    /**
+     * Encodes the object of an instance, in order to be deserialized by Rust.
+     *
+     * @param instance The instance.
+     * @param format The name of the {@link Codec.Format} to use.
+     * @return The encoded object.
+     * @throws IOException If the object cannot be encoded.
+     */
+    public static byte[] encode(Instance instance, String format) throws IOException {
+        return Codec.of(Codec.Format.valueOf(format)).encodeToBytes(instance.getObject());
+    }
 }
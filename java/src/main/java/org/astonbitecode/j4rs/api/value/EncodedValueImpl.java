@@ -0,0 +1,52 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.astonbitecode.j4rs.api.ObjectValue;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.errors.JsonCodecException;
+import org.astonbitecode.j4rs.json.Codec;
+
+import java.io.IOException;
+
+/**
+ * A value that is decoded from a payload in a binary {@link Codec.Format}.
+ */
+public class EncodedValueImpl implements ObjectValue {
+    private Object obj;
+
+    EncodedValueImpl(byte[] payload, Codec.Format format, String className) {
+        Codec codec = Codec.of(format);
+        try {
+            if (className.equals(InvocationArg.CONTENTS_ARRAY)) {
+                this.obj = codec.decodeArrayContents(payload);
+            } else {
+                this.obj = codec.decode(payload, className);
+            }
+        } catch (ClassNotFoundException | IOException error) {
+            throw new JsonCodecException("While creating EncodedValueImpl: Could not decode a " + format + " payload of " + payload.length + " bytes", error);
+        }
+    }
+
+    @Override
+    public Object getObject() {
+        return this.obj;
+    }
+
+    @Override
+    public Class<?> getObjectClass() {
+        return this.obj.getClass();
+    }
+}
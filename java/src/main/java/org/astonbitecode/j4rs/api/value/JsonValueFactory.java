@@ -15,6 +15,9 @@
 package org.astonbitecode.j4rs.api.value;
 
 import org.astonbitecode.j4rs.api.JsonValue;
+import org.astonbitecode.j4rs.api.ObjectValue;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.json.Codec;
 
 public class JsonValueFactory {
     public static JsonValue create(Object obj) {
@@ -24,4 +27,17 @@ public class JsonValueFactory {
     public static JsonValue create(String json, String className) {
         return new JsonValueImpl(json, className);
     }
+
+    public static ObjectValue create(byte[] payload, Codec.Format format, String className) {
+        return new EncodedValueImpl(payload, format, className);
+    }
+
+    /**
+     * Decodes an {@link InvocationArg} that is serialized by Rust, using the format of the argument.
+     */
+    public static ObjectValue createFromSerialized(InvocationArg serializedArg) {
+        return serializedArg.getFormat() == Codec.Format.JSON ?
+                create(serializedArg.getJson(), serializedArg.getClassName()) :
+                create(serializedArg.getPayload(), serializedArg.getFormat(), serializedArg.getClassName());
+    }
 }
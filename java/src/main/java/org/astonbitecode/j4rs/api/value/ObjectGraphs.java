@@ -0,0 +1,407 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.astonbitecode.j4rs.errors.InvalidArgumentException;
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.reflect.Array;
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Field;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.math.BigDecimal;
+import java.math.BigInteger;
+import java.util.ArrayList;
+import java.util.Collection;
+import java.util.LinkedHashMap;
+import java.util.LinkedHashSet;
+import java.util.List;
+import java.util.Map;
+import java.util.Optional;
+import java.util.Set;
+import java.util.concurrent.ConcurrentHashMap;
+
+/**
+ * Helpers for the serde Serializer and Deserializer of Rust, which build and read Java object graphs directly,
+ * without encoding them.
+ * <p>
+ * The properties of an object are accessed using their setter or getter, or else their field.
+ * A property named <code>first_name</code> in Rust matches both <code>first_name</code> and <code>firstName</code> in Java.
+ */
+public class ObjectGraphs {
+    private static final Map<String, Property> PROPERTIES = new ConcurrentHashMap<>();
+
+    /**
+     * Creates the object that a Rust struct is serialized to.
+     *
+     * @param className The class that is expected, or null if it is not known.
+     * @return A new instance of the class, or a {@link LinkedHashMap} if the class is not known or is an interface.
+     * @throws ClassNotFoundException If the class cannot be found.
+     */
+    public static Object newStruct(String className) throws ClassNotFoundException {
+        Class<?> clazz = className == null ? Object.class : Utils.forNameEnhanced(className);
+        if (clazz.isAssignableFrom(LinkedHashMap.class)) {
+            return new LinkedHashMap<String, Object>();
+        }
+        try {
+            Constructor<?> constructor = clazz.getDeclaredConstructor();
+            constructor.setAccessible(true);
+            return constructor.newInstance();
+        } catch (ReflectiveOperationException error) {
+            throw new InvalidArgumentException("Cannot create an instance of " + className + " for a Rust struct", error);
+        }
+    }
+
+    /**
+     * The name of the class of a property, to be used while serializing its value.
+     *
+     * @param struct The object that was created by {@link #newStruct(String)}.
+     * @param name The name of the property.
+     * @return The class name, or null if the value may be of any class.
+     */
+    public static String propertyClass(Object struct, String name) {
+        if (struct instanceof Map) {
+            return null;
+        }
+        Class<?> type = property(struct.getClass(), name).type;
+        return type == Object.class ? null : type.getName();
+    }
+
+    @SuppressWarnings("unchecked")
+    public static void setProperty(Object struct, String name, Object value) throws IllegalAccessException, InvocationTargetException {
+        if (struct instanceof Map) {
+            ((Map<String, Object>) struct).put(name, value);
+        } else {
+            property(struct.getClass(), name).set(struct, value);
+        }
+    }
+
+    /**
+     * Converts a value that was created from Rust to the class that is expected by Java.
+     * <p>
+     * Numbers are converted to the expected primitive, boxed or big number type, Lists to arrays or Sets,
+     * Strings to enum constants, chars or big numbers and nullable values to Optionals. Any other value is returned as is.
+     *
+     * @param value The value.
+     * @param className The name of the expected class.
+     * @return The converted value.
+     * @throws ClassNotFoundException If the class cannot be found.
+     */
+    public static Object convert(Object value, String className) throws ClassNotFoundException {
+        return convert(value, Utils.forNameEnhanced(className));
+    }
+
+    /**
+     * The properties of an object, to be deserialized in Rust.
+     *
+     * @param object The object.
+     * @param names The names of the properties to retrieve, or null to retrieve all of them.
+     * @return Pairs of names and values, flattened. The names that do not match any property are omitted.
+     * @throws IllegalAccessException If a property cannot be accessed.
+     * @throws InvocationTargetException If a getter throws.
+     */
+    public static Object[] properties(Object object, String[] names) throws IllegalAccessException, InvocationTargetException {
+        List<Object> pairs = new ArrayList<>();
+        Class<?> clazz = object.getClass();
+        if (names == null) {
+            Set<String> found = new LinkedHashSet<>();
+            for (Method method : clazz.getMethods()) {
+                String name = getterProperty(method);
+                if (name != null && found.add(name)) {
+                    pairs.add(name);
+                    pairs.add(method.invoke(object));
+                }
+            }
+            for (Field field : clazz.getFields()) {
+                if (!Modifier.isStatic(field.getModifiers()) && found.add(field.getName())) {
+                    pairs.add(field.getName());
+                    pairs.add(field.get(object));
+                }
+            }
+        } else {
+            for (String name : names) {
+                Property property = findProperty(clazz, name);
+                if (property != null && property.readable()) {
+                    pairs.add(name);
+                    pairs.add(property.get(object));
+                }
+            }
+        }
+        return pairs.toArray();
+    }
+
+    /**
+     * The entries of a Map, to be deserialized in Rust.
+     *
+     * @param map The map.
+     * @return Pairs of keys and values, flattened.
+     */
+    public static Object[] entries(Map<?, ?> map) {
+        Object[] pairs = new Object[map.size() * 2];
+        int i = 0;
+        for (Map.Entry<?, ?> entry : map.entrySet()) {
+            pairs[i++] = entry.getKey();
+            pairs[i++] = entry.getValue();
+        }
+        return pairs;
+    }
+
+    @SuppressWarnings({"unchecked", "rawtypes"})
+    private static Object convert(Object value, Class<?> type) {
+        if (type == Optional.class && !(value instanceof Optional)) {
+            return Optional.ofNullable(value);
+        } else if (value == null || type.isInstance(value)) {
+            return value;
+        }
+        Class<?> boxed = boxed(type);
+        if (value instanceof Number && Number.class.isAssignableFrom(boxed)) {
+            Number number = (Number) value;
+            if (boxed == Byte.class || boxed == Short.class || boxed == Integer.class || boxed == Long.class) {
+                return convertIntegral(longValueExact(number), boxed);
+            } else if (boxed == Float.class) {
+                float f = number.floatValue();
+                if (Float.isInfinite(f) && !Double.isInfinite(number.doubleValue())) {
+                    throw new ArithmeticException(number + " is out of the range of float");
+                }
+                return f;
+            } else if (boxed == Double.class) {
+                double d = number.doubleValue();
+                if (isIntegral(number) && (Double.isInfinite(d) || new BigDecimal(d).compareTo(bigDecimalValue(number)) != 0)) {
+                    throw new ArithmeticException(number + " cannot be represented exactly as a double");
+                }
+                return d;
+            } else if (boxed == BigInteger.class) {
+                return bigDecimalValue(number).toBigIntegerExact();
+            } else if (boxed == BigDecimal.class) {
+                return bigDecimalValue(number);
+            }
+        } else if (value instanceof String && (boxed == BigInteger.class || boxed == BigDecimal.class)) {
+            // The big numbers of Rust are serialized to their exact decimal strings
+            return boxed == BigInteger.class ? new BigInteger((String) value) : new BigDecimal((String) value);
+        } else if (boxed == Character.class && value instanceof String && ((String) value).length() == 1) {
+            return ((String) value).charAt(0);
+        } else if (type.isEnum() && value instanceof String) {
+            return Enum.valueOf((Class<? extends Enum>) type, (String) value);
+        } else if (type.isArray() && value instanceof List) {
+            List<?> list = (List<?>) value;
+            Object array = Array.newInstance(type.getComponentType(), list.size());
+            for (int i = 0; i < list.size(); i++) {
+                Array.set(array, i, convert(list.get(i), type.getComponentType()));
+            }
+            return array;
+        } else if (type.isAssignableFrom(LinkedHashSet.class) && value instanceof Collection) {
+            return new LinkedHashSet<>((Collection<?>) value);
+        }
+        return value;
+    }
+
+    private static boolean isIntegral(Number number) {
+        return number instanceof Byte || number instanceof Short || number instanceof Integer || number instanceof Long
+                || number instanceof BigInteger;
+    }
+
+    // Throws an ArithmeticException if the number has a fractional part or does not fit in a long
+    private static long longValueExact(Number number) {
+        if (number instanceof Byte || number instanceof Short || number instanceof Integer || number instanceof Long) {
+            return number.longValue();
+        } else if (number instanceof BigInteger) {
+            return ((BigInteger) number).longValueExact();
+        } else {
+            return bigDecimalValue(number).longValueExact();
+        }
+    }
+
+    private static BigDecimal bigDecimalValue(Number number) {
+        if ((number instanceof Double || number instanceof Float) && !Double.isFinite(number.doubleValue())) {
+            throw new ArithmeticException(number + " is not a finite number");
+        }
+        return new BigDecimal(number.toString());
+    }
+
+    // Throws an ArithmeticException if the value does not fit in the type
+    private static Object convertIntegral(long value, Class<?> boxed) {
+        if (boxed == Byte.class) {
+            if (value < Byte.MIN_VALUE || value > Byte.MAX_VALUE) {
+                throw new ArithmeticException(value + " is out of the range of byte");
+            }
+            return (byte) value;
+        } else if (boxed == Short.class) {
+            if (value < Short.MIN_VALUE || value > Short.MAX_VALUE) {
+                throw new ArithmeticException(value + " is out of the range of short");
+            }
+            return (short) value;
+        } else if (boxed == Integer.class) {
+            return Math.toIntExact(value);
+        } else {
+            return value;
+        }
+    }
+
+    private static Class<?> boxed(Class<?> type) {
+        if (!type.isPrimitive()) {
+            return type;
+        } else if (type == boolean.class) {
+            return Boolean.class;
+        } else if (type == byte.class) {
+            return Byte.class;
+        } else if (type == short.class) {
+            return Short.class;
+        } else if (type == int.class) {
+            return Integer.class;
+        } else if (type == long.class) {
+            return Long.class;
+        } else if (type == float.class) {
+            return Float.class;
+        } else if (type == double.class) {
+            return Double.class;
+        } else if (type == char.class) {
+            return Character.class;
+        } else {
+            return Void.class;
+        }
+    }
+
+    private static String getterProperty(Method method) {
+        if (Modifier.isStatic(method.getModifiers()) || method.getParameterCount() != 0 || method.getDeclaringClass() == Object.class) {
+            return null;
+        }
+        String name = method.getName();
+        if (name.startsWith("get") && name.length() > 3) {
+            return decapitalize(name.substring(3));
+        } else if (name.startsWith("is") && name.length() > 2 && boxed(method.getReturnType()) == Boolean.class) {
+            return decapitalize(name.substring(2));
+        } else {
+            return null;
+        }
+    }
+
+    private static Property property(Class<?> clazz, String name) {
+        Property property = findProperty(clazz, name);
+        if (property == null) {
+            throw new InvalidArgumentException("No property " + name + " was found in " + clazz.getName());
+        }
+        return property;
+    }
+
+    private static Property findProperty(Class<?> clazz, String name) {
+        String key = clazz.getName() + "#" + name;
+        Property property = PROPERTIES.get(key);
+        if (property == null) {
+            property = Property.find(clazz, name);
+            if (property == null) {
+                property = Property.find(clazz, camelCase(name));
+            }
+            if (property != null) {
+                PROPERTIES.put(key, property);
+            }
+        }
+        return property;
+    }
+
+    static String camelCase(String name) {
+        StringBuilder camel = new StringBuilder(name.length());
+        boolean upper = false;
+        for (char c : name.toCharArray()) {
+            if (c == '_') {
+                upper = camel.length() > 0;
+            } else {
+                camel.append(upper ? Character.toUpperCase(c) : c);
+                upper = false;
+            }
+        }
+        return camel.toString();
+    }
+
+    private static String capitalize(String name) {
+        return name.isEmpty() ? name : Character.toUpperCase(name.charAt(0)) + name.substring(1);
+    }
+
+    private static String decapitalize(String name) {
+        return Character.toLowerCase(name.charAt(0)) + name.substring(1);
+    }
+
+    // A property of a class, accessed using its setter and getter if they exist, or its field
+    private static class Property {
+        final Class<?> type;
+        final Method setter;
+        final Method getter;
+        final Field field;
+
+        private Property(Class<?> type, Method setter, Method getter, Field field) {
+            this.type = type;
+            this.setter = setter;
+            this.getter = getter;
+            this.field = field;
+        }
+
+        static Property find(Class<?> clazz, String name) {
+            Method setter = null;
+            Method getter = null;
+            for (Method method : clazz.getMethods()) {
+                if (method.getName().equals("set" + capitalize(name)) && method.getParameterCount() == 1) {
+                    setter = method;
+                } else if (name.equals(getterProperty(method))) {
+                    getter = method;
+                }
+            }
+            Field field = findField(clazz, name);
+            if (setter != null) {
+                return new Property(setter.getParameterTypes()[0], setter, getter, field);
+            } else if (field != null) {
+                return new Property(field.getType(), null, getter, field);
+            } else if (getter != null) {
+                return new Property(getter.getReturnType(), null, getter, null);
+            } else {
+                return null;
+            }
+        }
+
+        private static Field findField(Class<?> clazz, String name) {
+            for (Class<?> c = clazz; c != null && c != Object.class; c = c.getSuperclass()) {
+                try {
+                    Field field = c.getDeclaredField(name);
+                    if (!Modifier.isStatic(field.getModifiers())) {
+                        field.setAccessible(true);
+                        return field;
+                    }
+                } catch (NoSuchFieldException error) {
+                    // Search the superclass
+                }
+            }
+            return null;
+        }
+
+        boolean readable() {
+            return getter != null || field != null;
+        }
+
+        Object get(Object object) throws IllegalAccessException, InvocationTargetException {
+            return getter != null ? getter.invoke(object) : field.get(object);
+        }
+
+        void set(Object object, Object value) throws IllegalAccessException, InvocationTargetException {
+            Object converted = convert(value, type);
+            if (setter != null) {
+                setter.invoke(object, converted);
+            } else if (field != null) {
+                field.set(object, converted);
+            } else {
+                throw new InvalidArgumentException("The property " + getter.getName() + " of " + object.getClass().getName() + " is read only");
+            }
+        }
+    }
+}
@@ -14,25 +14,81 @@
  */
 package org.astonbitecode.j4rs.json;
 
+import com.fasterxml.jackson.core.JsonFactory;
 import com.fasterxml.jackson.core.JsonProcessingException;
 import com.fasterxml.jackson.core.type.TypeReference;
 import com.fasterxml.jackson.databind.ObjectMapper;
+import com.fasterxml.jackson.dataformat.cbor.CBORFactory;
 import org.astonbitecode.j4rs.errors.JsonCodecException;
 import org.astonbitecode.j4rs.utils.Utils;
+import org.msgpack.jackson.dataformat.MessagePackFactory;
 
 import java.io.IOException;
 import java.util.Arrays;
+import java.util.EnumMap;
 import java.util.Map;
 
 public class Codec {
     private static final String RUST_FIELD = "Rust";
     private static final String JSON_FIELD = "json";
     private static final String CLASS_NAME_FIELD = "class_name";
-    private ObjectMapper mapper = new ObjectMapper();
+    private static final Map<Format, Codec> sharedCodecs = new EnumMap<>(Format.class);
+    // The JSON documents that are passed as Strings
+    private final ObjectMapper mapper = new ObjectMapper();
+    // The payloads that are serialized in the format of this Codec
+    private final ObjectMapper formatMapper;
+    private final Format format;
     TypeReference<Map<String, Object>[]> typeRef
             = new TypeReference<Map<String, Object>[]>() {
     };
 
+    /**
+     * The formats that the values may be serialized to, while being transferred between Java and Rust.
+     */
+    public enum Format {
+        JSON,
+        CBOR,
+        MESSAGE_PACK;
+
+        private JsonFactory newFactory() {
+            switch (this) {
+                case CBOR:
+                    return new CBORFactory();
+                case MESSAGE_PACK:
+                    return new MessagePackFactory();
+                default:
+                    return new JsonFactory();
+            }
+        }
+    }
+
+    static {
+        Arrays.stream(Format.values()).forEach(format -> sharedCodecs.put(format, new Codec(format)));
+    }
+
+    public Codec() {
+        this(Format.JSON);
+    }
+
+    public Codec(Format format) {
+        this.format = format;
+        this.formatMapper = format == Format.JSON ? mapper : new ObjectMapper(format.newFactory());
+    }
+
+    /**
+     * Returns a Codec for the given format. The returned Codecs are shared and may be used by any thread.
+     *
+     * @param format The format of the Codec.
+     * @return The Codec.
+     */
+    public static Codec of(Format format) {
+        return sharedCodecs.get(format);
+    }
+
+    public Format getFormat() {
+        return format;
+    }
+
     @SuppressWarnings("unchecked")
     public <T> T decode(String json, String className) throws ClassNotFoundException, IOException {
         Class<T> clazz = (Class<T>) Utils.forNameEnhanced(className);
@@ -44,9 +100,34 @@ public class Codec {
         return mapper.writeValueAsString(obj);
     }
 
+    /**
+     * Decodes a payload that is serialized in the format of this Codec.
+     */
+    @SuppressWarnings("unchecked")
+    public <T> T decode(byte[] payload, String className) throws ClassNotFoundException, IOException {
+        Class<T> clazz = (Class<T>) Utils.forNameEnhanced(className);
+        return formatMapper.readValue(payload, clazz);
+    }
+
+    /**
+     * Encodes an object to the format of this Codec.
+     */
+    public byte[] encodeToBytes(Object obj) throws IOException {
+        return formatMapper.writeValueAsBytes(obj);
+    }
+
     public Object[] decodeArrayContents(String json) throws IOException {
-        Map<String, Object>[] array = mapper.readValue(json, typeRef);
+        return retrieveFromMaps(mapper.readValue(json, typeRef));
+    }
+
+    /**
+     * Like {@link #decodeArrayContents(String)}, for a payload that is serialized in the format of this Codec.
+     */
+    public Object[] decodeArrayContents(byte[] payload) throws IOException {
+        return retrieveFromMaps(formatMapper.readValue(payload, typeRef));
+    }
 
+    private Object[] retrieveFromMaps(Map<String, Object>[] array) {
         return Arrays.stream(array)
                 .map(elem -> {
                     try {
@@ -16,6 +16,7 @@ package org.astonbitecode.j4rs.tests;
 
 import org.astonbitecode.j4rs.api.invocation.NativeCallbackSupport;
 
+import java.io.FileNotFoundException;
 import java.util.ArrayList;
 import java.util.Arrays;
 import java.util.List;
@@ -128,4 +129,24 @@ public class MyTest extends NativeCallbackSupport {
         return null;
     }
 
+    public void throwFileNotFound(String path) throws FileNotFoundException {
+        throw new FileNotFoundException(path);
+    }
+
+    public void throwMyTestException(Integer errorCode) throws MyTestException {
+        throw new MyTestException("Failed with error code " + errorCode, errorCode);
+    }
+
+    public static native int nativeAdd(int a, int b);
+
+    public static native String nativeAdd(String a, String b);
+
+    public static Integer addNatively(Integer a, Integer b) {
+        return nativeAdd(a, b);
+    }
+
+    public static String addNatively(String a, String b) {
+        return nativeAdd(a, b);
+    }
+
 }
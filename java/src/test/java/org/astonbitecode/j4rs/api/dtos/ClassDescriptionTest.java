@@ -0,0 +1,133 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.dtos;
+
+import org.astonbitecode.j4rs.utils.Dummy;
+import org.astonbitecode.j4rs.utils.DummyWithFields;
+import org.astonbitecode.j4rs.utils.GrandchildDummy;
+import org.junit.Test;
+
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.InputStream;
+import java.net.URL;
+import java.net.URLClassLoader;
+import java.util.Arrays;
+import java.util.Collections;
+import java.util.List;
+import java.util.jar.JarEntry;
+import java.util.jar.JarOutputStream;
+import java.util.stream.Collectors;
+
+public class ClassDescriptionTest {
+
+    @Test
+    public void describeConstructorsAndMethods() throws Exception {
+        ClassDescription description = ClassDescription.describe(Dummy.class.getName());
+        assert description.getName().equals(Dummy.class.getName());
+        assert !description.isInterface();
+        assert description.getConstructors().stream()
+                .map(constructor -> constructor.getParameterTypes())
+                .collect(Collectors.toList())
+                .equals(Arrays.asList(Collections.emptyList(), Collections.singletonList("int"), Collections.singletonList("java.lang.Integer")));
+        assert description.getMethods().stream()
+                .map(ClassDescription.MethodDescription::getName)
+                .collect(Collectors.toList())
+                .equals(Arrays.asList("getI", "setI"));
+        ClassDescription.MethodDescription getter = description.getMethods().get(0);
+        assert getter.getReturnType().equals("java.lang.Integer");
+        assert !getter.isStatic() && !getter.isVarArgs();
+    }
+
+    @Test
+    public void describeStaticAndVarArgsMethods() throws Exception {
+        ClassDescription.MethodDescription asList = ClassDescription.describe("java.util.Arrays").getMethods().stream()
+                .filter(method -> method.getName().equals("asList"))
+                .findFirst()
+                .get();
+        assert asList.isStatic() && asList.isVarArgs();
+        assert asList.getParameterTypes().equals(Collections.singletonList("java.lang.Object[]"));
+        // The varargs bit is not named as transient
+        assert asList.getModifiers().equals(Arrays.asList("public", "static"));
+        assert ClassDescription.describe("java.util.List").isInterface();
+    }
+
+    @Test
+    public void describeAllDeclaredFields() throws Exception {
+        ClassDescription description = ClassDescription.describe(DummyWithFields.class.getName());
+        assert description.getFields().stream()
+                .map(ClassDescription.FieldDescription::getName)
+                .collect(Collectors.toList())
+                .equals(Arrays.asList("packageInt", "privateInt", "protectedInt", "pubInt"));
+        ClassDescription.FieldDescription field = description.getFields().get(3);
+        assert field.getType().equals("java.lang.Integer") && field.getModifiers().equals(Collections.singletonList("public"));
+        assert !field.isStatic() && !field.isFinal();
+        assert description.getFields().get(0).getModifiers().isEmpty();
+        assert description.getFields().get(1).getModifiers().equals(Collections.singletonList("private"));
+    }
+
+    @Test
+    public void describePrimitives() throws Exception {
+        ClassDescription description = ClassDescription.describe("int");
+        assert description.getName().equals("int");
+        assert description.getSuperclass() == null;
+        assert description.getConstructors().isEmpty() && description.getMethods().isEmpty() && description.getFields().isEmpty();
+    }
+
+    @Test
+    public void describeHierarchyAnnotationsAndModifiers() throws Exception {
+        ClassDescription arrayList = ClassDescription.describe("java.util.ArrayList");
+        assert arrayList.getSuperclass().equals("java.util.AbstractList");
+        assert arrayList.getInterfaces().contains("java.util.List");
+        assert arrayList.getModifiers().equals(Collections.singletonList("public"));
+
+        ClassDescription runnable = ClassDescription.describe("java.lang.Runnable");
+        assert runnable.getSuperclass() == null;
+        assert runnable.getAnnotations().equals(Collections.singletonList("java.lang.FunctionalInterface"));
+        assert runnable.getModifiers().equals(Arrays.asList("public", "abstract"));
+
+        ClassDescription.MethodDescription getYear = ClassDescription.describe("java.util.Date").getMethods().stream()
+                .filter(method -> method.getName().equals("getYear"))
+                .findFirst()
+                .get();
+        assert getYear.getAnnotations().equals(Collections.singletonList("java.lang.Deprecated"));
+    }
+
+    @Test
+    public void describeJarSkipsClassesThatCannotBeLinked() throws Exception {
+        // The jar does not contain the ChildDummy, which is the superclass of the GrandchildDummy
+        File jar = File.createTempFile("j4rs-describe-jar", ".jar");
+        jar.deleteOnExit();
+        try (JarOutputStream out = new JarOutputStream(new FileOutputStream(jar))) {
+            for (Class<?> clazz : Arrays.asList(Dummy.class, GrandchildDummy.class)) {
+                String entryName = clazz.getName().replace('.', '/') + ".class";
+                out.putNextEntry(new JarEntry(entryName));
+                try (InputStream in = clazz.getClassLoader().getResourceAsStream(entryName)) {
+                    byte[] buffer = new byte[4096];
+                    int read;
+                    while ((read = in.read(buffer)) != -1) {
+                        out.write(buffer, 0, read);
+                    }
+                }
+                out.closeEntry();
+            }
+        }
+        try (URLClassLoader classLoader = new URLClassLoader(new URL[]{jar.toURI().toURL()}, null)) {
+            List<ClassDescription> descriptions = ClassDescription.describeJar(jar.getPath(), classLoader);
+            assert descriptions.stream().map(ClassDescription::getName).collect(Collectors.toList())
+                    .equals(Collections.singletonList(Dummy.class.getName()));
+        }
+    }
+}
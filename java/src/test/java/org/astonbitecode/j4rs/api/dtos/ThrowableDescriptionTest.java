@@ -0,0 +1,50 @@
+/*
+ * Copyright 2018 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.dtos;
+
+import org.junit.Test;
+
+import java.io.FileNotFoundException;
+
+public class ThrowableDescriptionTest {
+
+    @Test
+    public void describeWithCauses() {
+        FileNotFoundException root = new FileNotFoundException("not there");
+        IllegalStateException top = new IllegalStateException("failed", root);
+
+        ThrowableDescription description = new ThrowableDescription(top);
+        assert description.getClassName().equals(IllegalStateException.class.getName());
+        assert description.getMessage().equals("failed");
+        assert !description.getStackTrace().isEmpty();
+        assert description.getCause().getClassName().equals(FileNotFoundException.class.getName());
+        assert description.getCause().getMessage().equals("not there");
+        assert description.getCause().getCause() == null;
+    }
+
+    @Test
+    public void describeCircularCauses() throws Exception {
+        RuntimeException first = new RuntimeException("first");
+        RuntimeException second = new RuntimeException("second", first);
+        first.initCause(second);
+
+        ThrowableDescription description = new ThrowableDescription(first);
+        assert description.getCause().getMessage().equals("second");
+        assert description.getCause().getCause() == null;
+
+        String json = ThrowableDescription.describe(first);
+        assert json.contains("\"className\":\"java.lang.RuntimeException\"");
+    }
+}
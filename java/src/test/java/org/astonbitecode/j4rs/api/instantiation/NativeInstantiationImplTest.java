@@ -16,15 +16,25 @@ package org.astonbitecode.j4rs.api.instantiation;
 
 import javafx.scene.Scene;
 import javafx.scene.layout.StackPane;
+import org.astonbitecode.j4rs.api.Instance;
 import org.astonbitecode.j4rs.api.dtos.GeneratedArg;
 import org.astonbitecode.j4rs.api.dtos.InvocationArg;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
+import org.astonbitecode.j4rs.errors.InstantiationException;
+import org.astonbitecode.j4rs.errors.InvocationException;
 import org.astonbitecode.j4rs.utils.ChildDummy;
 import org.astonbitecode.j4rs.utils.ClassWithDummyAtConstructor;
+import org.astonbitecode.j4rs.utils.ChildOfDummyWithFields;
 import org.astonbitecode.j4rs.utils.Dummy;
+import org.astonbitecode.j4rs.utils.DummyWithFields;
+import org.astonbitecode.j4rs.utils.DummyWithStatic;
 import org.junit.Test;
 
 import java.util.List;
+import java.util.Map;
+import java.util.Optional;
+import java.util.Set;
+import java.util.concurrent.TimeUnit;
 
 public class NativeInstantiationImplTest {
 
@@ -111,6 +121,101 @@ public class NativeInstantiationImplTest {
         NativeInstantiationImpl.createCollection(className, generatedArgs, NativeInstantiationImpl.J4rsCollectionType.Array);
     }
 
+    @Test
+    public void createJavaMapSuccess() throws Exception {
+        InvocationArg[] keysAndValues = {new InvocationArg("java.lang.String", "\"a\""), new InvocationArg("java.lang.Integer", "1")};
+        Object map = NativeInstantiationImpl.createJavaMap(keysAndValues).getObject();
+        assert (map instanceof Map);
+        assert (((Map<?, ?>) map).get("a").equals(1));
+    }
+
+    @Test(expected = InstantiationException.class)
+    public void createJavaMapFailureWithoutValue() throws Exception {
+        NativeInstantiationImpl.createJavaMap(new InvocationArg("java.lang.String", "\"a\""));
+    }
+
+    @Test
+    public void createJavaSetAndOptionalSuccess() throws Exception {
+        InvocationArg[] args = {new InvocationArg("java.lang.Integer", "1"), new InvocationArg("java.lang.Integer", "1")};
+        assert (((Set<?>) NativeInstantiationImpl.createJavaSet(args).getObject()).size() == 1);
+        assert (NativeInstantiationImpl.createJavaOptional(args[0]).getObject().equals(Optional.of(1)));
+        assert (!((Optional<?>) NativeInstantiationImpl.createJavaOptional().getObject()).isPresent());
+    }
+
+    @Test
+    public void createJavaEnumSuccess() throws Exception {
+        assert (NativeInstantiationImpl.createJavaEnum("java.util.concurrent.TimeUnit", "SECONDS").getObject() == TimeUnit.SECONDS);
+    }
+
+    @Test(expected = InstantiationException.class)
+    public void createJavaEnumFailureWithUnknownConstant() throws Exception {
+        NativeInstantiationImpl.createJavaEnum("java.util.concurrent.TimeUnit", "FORTNIGHTS");
+    }
+
+    @Test(expected = InstantiationException.class)
+    public void createJavaEnumFailureWithClassThatIsNotEnum() throws Exception {
+        NativeInstantiationImpl.createJavaEnum("java.lang.String", "SECONDS");
+    }
+
+    @Test
+    public void getAndSetFields() throws Exception {
+        DummyWithFields dummy = new DummyWithFields();
+        Instance instance = new JsonInvocationImpl(dummy, DummyWithFields.class);
+        // A Long is converted to the Integer of the field
+        NativeInstantiationImpl.setField(instance, "pubInt", new InvocationArg("java.lang.Long", "3"), false);
+        assert dummy.pubInt == 3;
+        assert NativeInstantiationImpl.getField(instance, "pubInt", false).getObject().equals(3);
+        // The retrieved Instance has the declared type of the field
+        assert NativeInstantiationImpl.getField(instance, "pubInt", false).getObjectClass().equals(Integer.class);
+    }
+
+    @Test
+    public void setFieldsWithoutLosingPrecision() throws Exception {
+        DummyWithFields dummy = new DummyWithFields();
+        Instance instance = new JsonInvocationImpl(dummy, DummyWithFields.class);
+        NativeInstantiationImpl.setField(instance, "pubInt", new InvocationArg("java.lang.Double", "4.0"), false);
+        assert dummy.pubInt == 4;
+        try {
+            NativeInstantiationImpl.setField(instance, "pubInt", new InvocationArg("java.lang.Long", "3000000000"), false);
+            assert false;
+        } catch (InvocationException error) {
+            assert error.getCause() instanceof ArithmeticException;
+        }
+        try {
+            NativeInstantiationImpl.setField(instance, "pubInt", new InvocationArg("java.lang.Double", "4.5"), false);
+            assert false;
+        } catch (InvocationException error) {
+            assert error.getCause() instanceof ArithmeticException;
+        }
+        assert dummy.pubInt == 4;
+    }
+
+    @Test
+    public void getAndSetInheritedPrivateFields() throws Exception {
+        Instance instance = new JsonInvocationImpl(new ChildOfDummyWithFields(), DummyWithFields.class);
+        NativeInstantiationImpl.setField(instance, "privateInt", new InvocationArg("java.lang.Integer", "5"), true);
+        assert NativeInstantiationImpl.getField(instance, "privateInt", true).getObject().equals(5);
+    }
+
+    @Test(expected = InvocationException.class)
+    public void privateFieldsAreNotAccessibleByDefault() throws Exception {
+        NativeInstantiationImpl.getField(new JsonInvocationImpl(new DummyWithFields(), DummyWithFields.class), "privateInt", false);
+    }
+
+    @Test
+    public void getAndSetStaticFields() throws Exception {
+        assert NativeInstantiationImpl.getStaticField("java.lang.Integer", "MAX_VALUE", false).getObject().equals(Integer.MAX_VALUE);
+        NativeInstantiationImpl.setStaticField(DummyWithStatic.class.getName(), "counter", new InvocationArg("java.lang.Integer", "7"), false);
+        assert DummyWithStatic.counter == 7;
+        NativeInstantiationImpl.setStaticField(DummyWithStatic.class.getName(), "secret", new InvocationArg("java.lang.String", "\"changed\""), true);
+        assert NativeInstantiationImpl.getStaticField(DummyWithStatic.class.getName(), "secret", true).getObject().equals("changed");
+    }
+
+    @Test(expected = InvocationException.class)
+    public void staticFieldsMustBeStatic() throws Exception {
+        NativeInstantiationImpl.getStaticField(DummyWithFields.class.getName(), "pubInt", false);
+    }
+
     @Test
     public void createClassWithNeededArgsInHierarchy() throws Exception {
         GeneratedArg[] generatedArgs = {
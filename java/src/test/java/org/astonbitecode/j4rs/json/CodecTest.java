@@ -22,6 +22,8 @@ import org.astonbitecode.j4rs.utils.Dummy;
 import org.astonbitecode.j4rs.utils.OtherDummy;
 import org.junit.Test;
 
+import java.io.IOException;
+import java.math.BigDecimal;
 import java.util.Arrays;
 
 public class CodecTest {
@@ -59,6 +61,14 @@ public class CodecTest {
         assert (codec.encode(person).equals(json));
     }
 
+    @Test
+    public void bigDecimals() throws Exception {
+        // Encoded as JSON numbers, while the decimal strings of Rust are decoded exactly
+        assert (codec.encode(new BigDecimal("0.10")).equals("0.10"));
+        BigDecimal decoded = codec.decode("\"12345678901234567890.123\"", "java.math.BigDecimal");
+        assert (decoded.equals(new BigDecimal("12345678901234567890.123")));
+    }
+
     @Test
     public void encodeJ4rsArray() throws Exception {
         String json = "[\n" +
@@ -74,5 +84,38 @@ public class CodecTest {
         String json = "[{\"i\":3,\"j\":33}, {\"i\":333,\"j\":3333}]";
         codec.decodeArrayContents(json);
     }
-}
 
+    @Test
+    public void decodeCbor() throws Exception {
+        // {"i": 3}
+        byte[] cbor = new byte[]{(byte) 0xa1, 0x61, 'i', 0x03};
+        Dummy dummy = Codec.of(Codec.Format.CBOR).decode(cbor, "org.astonbitecode.j4rs.utils.Dummy");
+        assert (dummy.getI() == 3);
+    }
+
+    @Test
+    public void decodeMessagePack() throws Exception {
+        // {"i": 3}
+        byte[] messagePack = new byte[]{(byte) 0x81, (byte) 0xa1, 'i', 0x03};
+        Dummy dummy = Codec.of(Codec.Format.MESSAGE_PACK).decode(messagePack, "org.astonbitecode.j4rs.utils.Dummy");
+        assert (dummy.getI() == 3);
+    }
+
+    @Test
+    public void binaryFormatsRoundTrip() throws Exception {
+        for (Codec.Format format : Codec.Format.values()) {
+            Codec binaryCodec = Codec.of(format);
+            for (int i : new int[]{0, -1, -300, 70000, Integer.MIN_VALUE}) {
+                Dummy decoded = binaryCodec.decode(binaryCodec.encodeToBytes(new Dummy(i)), "org.astonbitecode.j4rs.utils.Dummy");
+                assert (decoded.getI() == i);
+            }
+            String[] strings = binaryCodec.decode(binaryCodec.encodeToBytes(new String[]{"a", null, "\u00fc"}), "[Ljava.lang.String;");
+            assert (Arrays.equals(strings, new String[]{"a", null, "\u00fc"}));
+        }
+    }
+
+    @Test(expected = IOException.class)
+    public void decodeCborFailureTruncated() throws Exception {
+        Codec.of(Codec.Format.CBOR).decode(new byte[]{(byte) 0xa1, 0x61}, "org.astonbitecode.j4rs.utils.Dummy");
+    }
+}
@@ -15,6 +15,9 @@
 package org.astonbitecode.j4rs.utils;
 
 public class DummyWithStatic {
+    public static Integer counter = 0;
+    private static String secret = "secret";
+
     public static String method() {
         return "method product";
     }
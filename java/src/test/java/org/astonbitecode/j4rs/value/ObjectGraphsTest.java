@@ -0,0 +1,125 @@
+/*
+ * Copyright 2020 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.value;
+
+import org.astonbitecode.j4rs.api.value.ObjectGraphs;
+import org.astonbitecode.j4rs.errors.InvalidArgumentException;
+import org.astonbitecode.j4rs.utils.Dummy;
+import org.astonbitecode.j4rs.utils.DummyWithFields;
+import org.junit.Test;
+
+import java.math.BigDecimal;
+import java.math.BigInteger;
+import java.util.Arrays;
+import java.util.LinkedHashMap;
+import java.util.Map;
+import java.util.Optional;
+import java.util.Set;
+import java.util.concurrent.TimeUnit;
+
+public class ObjectGraphsTest {
+
+    @Test
+    public void structsOfUnknownClassesAreMaps() throws Exception {
+        assert ObjectGraphs.newStruct(null) instanceof LinkedHashMap;
+        assert ObjectGraphs.newStruct("java.util.Map") instanceof LinkedHashMap;
+        Object struct = ObjectGraphs.newStruct("java.lang.Object");
+        assert ObjectGraphs.propertyClass(struct, "any") == null;
+        ObjectGraphs.setProperty(struct, "any", 1);
+        assert ((Map<?, ?>) struct).get("any").equals(1);
+    }
+
+    @Test
+    public void setAndGetProperties() throws Exception {
+        Object dummy = ObjectGraphs.newStruct("org.astonbitecode.j4rs.utils.Dummy");
+        assert ObjectGraphs.propertyClass(dummy, "i").equals("java.lang.Integer");
+        // A Long is converted to the Integer that the setter expects
+        ObjectGraphs.setProperty(dummy, "i", 3L);
+        assert ((Dummy) dummy).getI() == 3;
+
+        Object[] properties = ObjectGraphs.properties(dummy, new String[]{"i", "missing"});
+        assert Arrays.equals(properties, new Object[]{"i", 3});
+        assert Arrays.equals(ObjectGraphs.properties(dummy, null), new Object[]{"i", 3});
+    }
+
+    @Test
+    public void setPrivateFields() throws Exception {
+        Object dummy = ObjectGraphs.newStruct("org.astonbitecode.j4rs.utils.DummyWithFields");
+        ObjectGraphs.setProperty(dummy, "private_int", 5);
+        ObjectGraphs.setProperty(dummy, "pubInt", 7);
+        assert ((DummyWithFields) dummy).pubInt == 7;
+        assert Arrays.equals(ObjectGraphs.properties(dummy, new String[]{"private_int"}), new Object[]{"private_int", 5});
+    }
+
+    @Test(expected = InvalidArgumentException.class)
+    public void unknownProperty() throws Exception {
+        ObjectGraphs.setProperty(new Dummy(), "unknown", 1);
+    }
+
+    @Test
+    public void convert() throws Exception {
+        assert ObjectGraphs.convert(1, "long").equals(1L);
+        assert ObjectGraphs.convert("a", "char").equals('a');
+        assert ObjectGraphs.convert("SECONDS", "java.util.concurrent.TimeUnit") == TimeUnit.SECONDS;
+        assert Arrays.equals((int[]) ObjectGraphs.convert(Arrays.asList(1, 2L), "[I"), new int[]{1, 2});
+        assert ObjectGraphs.convert(Arrays.asList(1, 1), "java.util.Set") instanceof Set;
+        assert ObjectGraphs.convert("1.50", "java.math.BigDecimal").equals(new BigDecimal("1.50"));
+        assert ObjectGraphs.convert(3L, "java.math.BigInteger").equals(BigInteger.valueOf(3));
+        assert ObjectGraphs.convert(null, "java.util.Optional").equals(Optional.empty());
+        assert ObjectGraphs.convert("a", "java.util.Optional").equals(Optional.of("a"));
+        assert ObjectGraphs.convert("unchanged", "java.lang.Object").equals("unchanged");
+    }
+
+    @Test
+    public void convertNumbersExactly() throws Exception {
+        assert ObjectGraphs.convert(300L, "short").equals((short) 300);
+        assert ObjectGraphs.convert(-128, "java.lang.Byte").equals((byte) -128);
+        assert ObjectGraphs.convert(2.0, "int").equals(2);
+        assert ObjectGraphs.convert(new BigInteger("9223372036854775807"), "long").equals(Long.MAX_VALUE);
+        assert ObjectGraphs.convert(1L << 53, "double").equals(9007199254740992.0);
+        assert ObjectGraphs.convert(0.5, "float").equals(0.5f);
+        assert ObjectGraphs.convert(1e20, "java.math.BigInteger").equals(new BigInteger("100000000000000000000"));
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertOutOfRangeOfInt() throws Exception {
+        ObjectGraphs.convert(1L << 40, "int");
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertOutOfRangeOfByte() throws Exception {
+        ObjectGraphs.convert(128, "byte");
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertFractionToLong() throws Exception {
+        ObjectGraphs.convert(2.5, "long");
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertNanToInt() throws Exception {
+        ObjectGraphs.convert(Double.NaN, "int");
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertLongToInexactDouble() throws Exception {
+        ObjectGraphs.convert((1L << 53) + 1, "double");
+    }
+
+    @Test(expected = ArithmeticException.class)
+    public void convertOutOfRangeOfFloat() throws Exception {
+        ObjectGraphs.convert(1e300, "float");
+    }
+}
@@ -6,7 +6,7 @@ use std::convert::TryFrom;
 use criterion::{black_box, BenchmarkId};
 use criterion::Criterion;
 
-use j4rs::{self, Instance, InvocationArg, Jvm};
+use j4rs::{self, Codec, Instance, InvocationArg, Jvm, JvmBuilder};
 
 fn do_instance_creation(jvm: &Jvm) -> Instance {
     jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap()
@@ -34,6 +34,12 @@ fn use_to_rust_boxed(jvm: &Jvm, instance: &Instance) {
     let _: Box<i32> = jvm.to_rust_boxed(i_instance).unwrap();
 }
 
+fn do_codec_round_trip(jvm: &Jvm, values: &Vec<f64>) -> Vec<f64> {
+    let arg = jvm.create_invocation_arg(values, "java.util.List").unwrap();
+    let list = jvm.create_instance("java.util.ArrayList", &[arg]).unwrap();
+    jvm.to_rust(list).unwrap()
+}
+
 fn j4rs_benchmark(c: &mut Criterion) {
     let jvm: Jvm = j4rs::new_jvm(Vec::new(), Vec::new()).unwrap();
     c.bench_function(
@@ -93,5 +99,22 @@ fn bench_to_rust(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, j4rs_benchmark, bench_to_rust);
+fn bench_codecs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("codecs");
+
+    let mut jvm: Jvm = JvmBuilder::new().build().unwrap();
+    for size in &[10, 10_000] {
+        let values: Vec<f64> = (0..*size).map(|i| i as f64 / 3.0).collect();
+        for codec in &[Codec::Json, Codec::Cbor, Codec::MessagePack, Codec::Jni] {
+            jvm.set_codec(*codec);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{:?}", codec), size),
+                &values,
+                |b, values| b.iter(|| do_codec_round_trip(black_box(&jvm), black_box(values))));
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, j4rs_benchmark, bench_to_rust, bench_codecs);
 criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,69 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use quote::quote;
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// The `key = "value"` pairs and the flags of the `#[java(...)]` attributes of an item,
+/// for example `#[java(class = "java.util.concurrent.TimeUnit")]` or `#[java(boxed)]`.
+pub(crate) struct JavaAttributes {
+    values: Vec<(String, String)>,
+}
+
+impl JavaAttributes {
+    /// Parses the `#[java(...)]` attributes. Keys that are not contained in the `allowed_keys` result in a panic.
+    pub(crate) fn parse(attrs: &[Attribute], allowed_keys: &[&str]) -> JavaAttributes {
+        let mut values = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("java")) {
+            let nested = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested,
+                _ => panic!("Invalid attribute {}. Usage: #[java(key = \"value\")]", quote!(#attr)),
+            };
+            for meta in nested {
+                match meta {
+                    NestedMeta::Meta(Meta::NameValue(ref name_value)) => {
+                        let key = name_value.path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                        if !allowed_keys.contains(&key.as_str()) {
+                            panic!("Unsupported key {} in the java attribute. The supported keys are: {}", quote!(#meta), allowed_keys.join(", "));
+                        }
+                        match &name_value.lit {
+                            Lit::Str(litstr) => values.push((key, litstr.value())),
+                            other => panic!("The value of {} in the java attribute should be a string. Found: {}", key, quote!(#other)),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::Path(ref path)) => {
+                        let key = path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                        if !allowed_keys.contains(&key.as_str()) {
+                            panic!("Unsupported flag {} in the java attribute. The supported keys are: {}", key, allowed_keys.join(", "));
+                        }
+                        values.push((key, String::new()));
+                    }
+                    other => panic!("Invalid argument {} in the java attribute. Usage: #[java(key = \"value\")]", quote!(#other)),
+                }
+            }
+        }
+        JavaAttributes { values }
+    }
+
+    /// The value of the provided key, if it is defined
+    pub(crate) fn value(&self, key: &str) -> Option<String> {
+        self.values.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    }
+
+    /// Whether the provided key is defined, either as a flag or with a value
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.values.iter().any(|(k, _)| k == key)
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, braced, FnArg, Ident, LitStr, Pat, ReturnType, Signature, Token, Type, Visibility};
+use syn::parse::{Parse, ParseStream, Result};
+
+use crate::java_attributes::JavaAttributes;
+
+// The Rust types that are passed to Java as primitives
+const PRIMITIVES: [&str; 8] = ["bool", "i8", "i16", "i32", "i64", "f32", "f64", "char"];
+
+/// The classes that are declared in a `java_class!` invocation
+pub(crate) struct JavaClasses {
+    classes: Vec<JavaClass>,
+}
+
+impl Parse for JavaClasses {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut classes = Vec::new();
+        while !input.is_empty() {
+            classes.push(input.parse()?);
+        }
+        Ok(JavaClasses { classes })
+    }
+}
+
+/// `#[attrs] pub Name = "java.class.Name" { methods }`
+struct JavaClass {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    class_name: LitStr,
+    methods: Vec<JavaMethod>,
+}
+
+impl Parse for JavaClass {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let class_name = input.parse()?;
+        let content;
+        braced!(content in input);
+        let mut methods = Vec::new();
+        while !content.is_empty() {
+            methods.push(content.parse()?);
+        }
+        Ok(JavaClass { attrs, vis, name, class_name, methods })
+    }
+}
+
+/// `#[attrs] pub static fn name(args) -> Type;`
+struct JavaMethod {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    is_static: bool,
+    sig: Signature,
+}
+
+impl Parse for JavaMethod {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let is_static = input.parse::<Option<Token![static]>>()?.is_some();
+        let sig = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(JavaMethod { attrs, vis, is_static, sig })
+    }
+}
+
+/// Generates a newtype over `Instance` for each one of the declared classes, with the declared methods.
+pub(crate) fn impl_java_classes(java_classes: &JavaClasses) -> TokenStream2 {
+    let class_idents: Vec<&Ident> = java_classes.classes.iter().map(|class| &class.name).collect();
+    let generated = java_classes.classes.iter().map(|class| impl_java_class(class, &class_idents));
+    quote! {
+        #(#generated)*
+    }
+}
+
+fn impl_java_class(class: &JavaClass, class_idents: &[&Ident]) -> TokenStream2 {
+    let JavaClass { attrs, vis, name, class_name, methods } = class;
+    let methods = methods.iter().map(|method| impl_java_method(name, method, class_idents));
+
+    quote! {
+        #(#attrs)*
+        #vis struct #name(Instance);
+
+        impl #name {
+            /// The name of the Java class
+            pub const CLASS_NAME: &'static str = #class_name;
+
+            /// Wraps an `Instance`. Its class is not checked.
+            pub fn from_instance(instance: Instance) -> #name {
+                #name(instance)
+            }
+
+            /// The wrapped `Instance`
+            pub fn instance(&self) -> &Instance {
+                &self.0
+            }
+
+            /// Returns the wrapped `Instance`
+            pub fn into_instance(self) -> Instance {
+                self.0
+            }
+
+            #(#methods)*
+        }
+
+        impl std::convert::From<Instance> for #name {
+            fn from(instance: Instance) -> #name {
+                #name(instance)
+            }
+        }
+
+        impl std::convert::From<#name> for Instance {
+            fn from(wrapper: #name) -> Instance {
+                wrapper.0
+            }
+        }
+
+        impl std::convert::From<#name> for InvocationArg {
+            fn from(wrapper: #name) -> InvocationArg {
+                InvocationArg::from(wrapper.0)
+            }
+        }
+    }
+}
+
+fn impl_java_method(class_ident: &Ident, method: &JavaMethod, class_idents: &[&Ident]) -> TokenStream2 {
+    let JavaMethod { attrs, vis, is_static, sig } = method;
+    let rust_name = &sig.ident;
+    let java_attributes = JavaAttributes::parse(attrs, &["name"]);
+    let attrs: Vec<&Attribute> = attrs.iter().filter(|attr| !attr.path.is_ident("java")).collect();
+    let java_name = java_attributes.value("name").unwrap_or_else(|| lower_camel_case(&rust_name.to_string()));
+
+    let mut has_receiver = false;
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                if receiver.reference.is_none() || receiver.mutability.is_some() {
+                    panic!("The method {} of {} should take &self", rust_name, class_ident);
+                }
+                has_receiver = true;
+            }
+            FnArg::Typed(pat_type) => {
+                let param_name = match pat_type.pat.as_ref() {
+                    Pat::Ident(pat_ident) => &pat_ident.ident,
+                    other => panic!("Could not locate the argument name for: {}", quote!(#other)),
+                };
+                let boxed = JavaAttributes::parse(&pat_type.attrs, &["boxed"]).contains("boxed");
+                let ty = &pat_type.ty;
+                params.push(quote!(#param_name: #ty));
+                args.push(invocation_arg(param_name, ty, boxed, class_idents));
+            }
+        }
+    }
+    let is_constructor = !has_receiver && !is_static;
+    if *is_static && has_receiver {
+        panic!("The static method {} of {} cannot take &self", rust_name, class_ident);
+    }
+    if is_constructor && !returns_self(&sig.output, class_ident) {
+        panic!("The method {} of {} should either take &self, be static, or return Self in order to be a constructor", rust_name, class_ident);
+    }
+
+    let invocation = if is_constructor {
+        quote!(jvm.create_instance(Self::CLASS_NAME, &[#(#args),*])?)
+    } else if *is_static {
+        quote!(jvm.invoke_static(Self::CLASS_NAME, #java_name, &[#(#args),*])?)
+    } else {
+        quote!(jvm.invoke(&self.0, #java_name, &[#(#args),*])?)
+    };
+    let (output, body) = match &sig.output {
+        ReturnType::Type(_, ty) if type_name(ty) != "()" => (quote!(#ty), rust_return(ty, &invocation, class_idents)),
+        _ => (quote!(()), quote! {
+            #invocation;
+            Ok(())
+        }),
+    };
+    let receiver = if has_receiver { quote!(&self,) } else { quote!() };
+
+    quote! {
+        #(#attrs)*
+        #[allow(clippy::too_many_arguments)]
+        #vis fn #rust_name(#receiver jvm: &Jvm, #(#params),*) -> std::result::Result<#output, J4RsError> {
+            #body
+        }
+    }
+}
+
+// The expression that creates the InvocationArg of a parameter
+fn invocation_arg(param_name: &Ident, ty: &Type, boxed: bool, class_idents: &[&Ident]) -> TokenStream2 {
+    let name = type_name(ty);
+    match ty {
+        Type::Reference(reference) => {
+            let referenced_name = type_name(&reference.elem);
+            if referenced_name == "Instance" {
+                return quote!(InvocationArg::from(jvm.clone_instance(#param_name)?));
+            } else if class_idents.iter().any(|ident| *ident == &referenced_name) {
+                return quote!(InvocationArg::from(jvm.clone_instance(#param_name.instance())?));
+            }
+        }
+        _ if name == "InvocationArg" => return quote!(#param_name),
+        _ if !boxed && PRIMITIVES.contains(&name.as_str()) => {
+            return quote!(<InvocationArg as std::convert::TryFrom<_>>::try_from(#param_name)?.into_primitive()?);
+        }
+        _ => {}
+    }
+    quote!(<InvocationArg as std::convert::TryFrom<_>>::try_from(#param_name)?)
+}
+
+// The statements that return the result of the invocation as the provided type
+fn rust_return(ty: &Type, invocation: &TokenStream2, class_idents: &[&Ident]) -> TokenStream2 {
+    let type_name = type_name(ty);
+    if type_name == "Instance" {
+        quote!(Ok(#invocation))
+    } else if type_name == "Self" || class_idents.iter().any(|ident| *ident == &type_name) {
+        quote!(Ok(#ty::from_instance(#invocation)))
+    } else {
+        quote! {
+            let instance = #invocation;
+            jvm.to_rust(instance)
+        }
+    }
+}
+
+fn returns_self(output: &ReturnType, class_ident: &Ident) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => {
+            let type_name = type_name(ty);
+            type_name == "Self" || class_ident == &type_name
+        }
+        ReturnType::Default => false,
+    }
+}
+
+// The name of a type, without whitespace
+fn type_name(ty: &Type) -> String {
+    quote!(#ty).to_string().replace(' ', "")
+}
+
+// Converts a snake case Rust name to a Java name. For example, `getFirstName` for `get_first_name`
+fn lower_camel_case(name: &str) -> String {
+    let mut camel = String::new();
+    let mut capitalize = false;
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' && !camel.is_empty() {
+            capitalize = true;
+        } else if capitalize {
+            camel.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
@@ -0,0 +1,105 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::java_attributes::JavaAttributes;
+
+/// Implements `JavaEnum`, `TryFrom<E> for InvocationArg`, `Serialize` and `Deserialize` for a fieldless enum.
+pub(crate) fn impl_java_enum(input: &DeriveInput) -> TokenStream2 {
+    let enum_name = &input.ident;
+    if !input.generics.params.is_empty() {
+        panic!("JavaEnum cannot be derived for the generic enum {}", enum_name);
+    }
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => panic!("JavaEnum can be derived only for enums. {} is not an enum", enum_name),
+    };
+    let class_name = JavaAttributes::parse(&input.attrs, &["class"])
+        .value("class")
+        .unwrap_or_else(|| panic!("Missing the Java class of {}. Usage: #[java(class = \"full.class.Name\")]", enum_name));
+
+    let mut variant_idents = Vec::new();
+    let mut java_names = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            panic!("JavaEnum can be derived only for fieldless enums. The variant {}::{} has fields", enum_name, variant.ident);
+        }
+        let java_name = JavaAttributes::parse(&variant.attrs, &["rename"])
+            .value("rename")
+            .unwrap_or_else(|| java_constant_name(&variant.ident.to_string()));
+        variant_idents.push(&variant.ident);
+        java_names.push(java_name);
+    }
+
+    quote! {
+        impl JavaEnum for #enum_name {
+            const JAVA_CLASS: &'static str = #class_name;
+
+            fn java_name(&self) -> &'static str {
+                match self {
+                    #(#enum_name::#variant_idents => #java_names,)*
+                }
+            }
+
+            fn from_java_name(name: &str) -> Option<Self> {
+                match name {
+                    #(#java_names => Some(#enum_name::#variant_idents),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::convert::TryFrom<#enum_name> for InvocationArg {
+            type Error = J4RsError;
+            fn try_from(value: #enum_name) -> std::result::Result<InvocationArg, J4RsError> {
+                InvocationArg::from_java_enum(&value)
+            }
+        }
+
+        impl<'a> std::convert::TryFrom<&'a #enum_name> for InvocationArg {
+            type Error = J4RsError;
+            fn try_from(value: &'a #enum_name) -> std::result::Result<InvocationArg, J4RsError> {
+                InvocationArg::from_java_enum(value)
+            }
+        }
+
+        impl j4rs_serde::Serialize for #enum_name {
+            fn serialize<S: j4rs_serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(JavaEnum::java_name(self))
+            }
+        }
+
+        impl<'de> j4rs_serde::Deserialize<'de> for #enum_name {
+            fn deserialize<D: j4rs_serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                deserialize_java_enum(deserializer)
+            }
+        }
+    }
+}
+
+// The name of the Java constant for a Rust variant, following the Java conventions. For example, `TIME_UNIT` for `TimeUnit`
+fn java_constant_name(variant_name: &str) -> String {
+    let mut name = String::new();
+    let mut previous_is_lower_or_digit = false;
+    for c in variant_name.chars() {
+        if c.is_uppercase() && previous_is_lower_or_digit {
+            name.push('_');
+        }
+        previous_is_lower_or_digit = c.is_lowercase() || c.is_ascii_digit();
+        name.extend(c.to_uppercase());
+    }
+    name
+}
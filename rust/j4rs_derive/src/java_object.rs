@@ -0,0 +1,87 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::java_attributes::JavaAttributes;
+
+/// Implements `JavaObject`, `TryFrom<&S> for InvocationArg` and `TryFrom<&Instance> for S` for a struct with named fields.
+pub(crate) fn impl_java_object(input: &DeriveInput) -> TokenStream2 {
+    let struct_name = &input.ident;
+    if !input.generics.params.is_empty() {
+        panic!("JavaObject cannot be derived for the generic struct {}", struct_name);
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("JavaObject can be derived only for structs with named fields. {} has unnamed fields", struct_name),
+        },
+        _ => panic!("JavaObject can be derived only for structs. {} is not a struct", struct_name),
+    };
+    let class_name = JavaAttributes::parse(&input.attrs, &["class"])
+        .value("class")
+        .unwrap_or_else(|| panic!("Missing the Java class of {}. Usage: #[java(class = \"full.class.Name\")]", struct_name));
+
+    let mut field_idents = Vec::new();
+    let mut java_names = Vec::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().expect("Named fields should have an ident");
+        // ObjectGraphs matches the snake case names with the camel case Java properties as well
+        let java_name = JavaAttributes::parse(&field.attrs, &["rename"])
+            .value("rename")
+            .unwrap_or_else(|| field_ident.to_string());
+        field_idents.push(field_ident);
+        java_names.push(java_name);
+    }
+
+    quote! {
+        impl JavaObject for #struct_name {
+            const JAVA_CLASS: &'static str = #class_name;
+            const JAVA_FIELDS: &'static [&'static str] = &[#(#java_names),*];
+
+            fn write_fields(&self, writer: &mut JavaObjectWriter) -> std::result::Result<(), J4RsError> {
+                #(writer.set_field(#java_names, &self.#field_idents)?;)*
+                Ok(())
+            }
+
+            fn read_fields(reader: &mut JavaObjectReader) -> std::result::Result<Self, J4RsError> {
+                Ok(#struct_name {
+                    #(#field_idents: reader.field(#java_names)?,)*
+                })
+            }
+        }
+
+        impl<'a> std::convert::TryFrom<&'a #struct_name> for InvocationArg {
+            type Error = J4RsError;
+            fn try_from(value: &'a #struct_name) -> std::result::Result<InvocationArg, J4RsError> {
+                InvocationArg::from_java_object(value)
+            }
+        }
+
+        impl std::convert::TryFrom<#struct_name> for InvocationArg {
+            type Error = J4RsError;
+            fn try_from(value: #struct_name) -> std::result::Result<InvocationArg, J4RsError> {
+                InvocationArg::from_java_object(&value)
+            }
+        }
+
+        impl<'a> std::convert::TryFrom<&'a Instance> for #struct_name {
+            type Error = J4RsError;
+            fn try_from(instance: &'a Instance) -> std::result::Result<#struct_name, J4RsError> {
+                <#struct_name as JavaObject>::from_instance(instance)
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable that defines the directory where the Java sources of the native declarations are generated.
+/// Relative paths are resolved against the directory of the crate that is being compiled.
+pub(crate) const JAVA_STUBS_DIR_ENV_VAR: &str = "J4RS_JAVA_STUBS_DIR";
+// The directory (under the OUT_DIR or the target directory) that keeps the native declarations of each class,
+// in order to regenerate a class each time one of its declarations changes.
+const NATIVE_DECLARATIONS_DIR: &str = "j4rs-natives";
+
+/// A Java `native` method that is implemented by a function annotated with `call_from_java`.
+pub(crate) struct NativeMethod {
+    /// The fully qualified name of the Java class
+    pub(crate) class_name: String,
+    pub(crate) method_name: String,
+    /// The Java types and names of the arguments
+    pub(crate) args: Vec<(String, String)>,
+    pub(crate) return_type: String,
+}
+
+impl NativeMethod {
+    fn declaration(&self) -> String {
+        let args: Vec<String> = self.args.iter()
+            .map(|(java_type, name)| format!("{} {}", java_type, name))
+            .collect();
+        format!("    public static native {} {}({});", self.return_type, self.method_name, args.join(", "))
+    }
+
+    // Overloaded methods are declared in separate files, because Java identifies methods by their name and argument types.
+    fn declaration_file_name(&self) -> String {
+        let arg_types: Vec<&str> = self.args.iter()
+            .map(|(java_type, _)| java_type.as_str())
+            .collect();
+        format!("{}({})", self.method_name, arg_types.join(","))
+    }
+}
+
+/// Returns the directory where the Java stubs should be generated, if the `J4RS_JAVA_STUBS_DIR` environment variable is defined
+/// and the crate that is being compiled is selected by the build (eg. it is not a dependency).
+pub(crate) fn java_stubs_dir() -> Option<PathBuf> {
+    resolve_java_stubs_dir(
+        env::var(JAVA_STUBS_DIR_ENV_VAR).ok(),
+        env::var("CARGO_MANIFEST_DIR").ok(),
+        env::var_os("CARGO_PRIMARY_PACKAGE").is_some())
+}
+
+// The stubs are generated only for the packages that are selected by the build, and never for their dependencies,
+// which see the same environment variable.
+fn resolve_java_stubs_dir(java_stubs_dir: Option<String>, manifest_dir: Option<String>, primary_package: bool) -> Option<PathBuf> {
+    if !primary_package {
+        return None;
+    }
+    let dir = PathBuf::from(java_stubs_dir.filter(|dir| !dir.is_empty())?);
+    match manifest_dir {
+        Some(manifest_dir) if dir.is_relative() => Some(Path::new(&manifest_dir).join(dir)),
+        _ => Some(dir),
+    }
+}
+
+/// The directory that keeps the native declarations: the `OUT_DIR` of the crate that is being compiled, if it has a build script,
+/// or else the target directory. The declarations of each crate are kept separately.
+pub(crate) fn native_declarations_dir() -> PathBuf {
+    let dir = env::var_os("OUT_DIR")
+        .or_else(|| env::var_os("CARGO_TARGET_DIR"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join("target"));
+    dir.join(NATIVE_DECLARATIONS_DIR).join(library_name())
+}
+
+/// Identifies the current compilation. All the macros of a crate are expanded by the same compiler process,
+/// every time that the crate is compiled.
+pub(crate) fn compilation_id() -> &'static str {
+    static COMPILATION_ID: OnceLock<String> = OnceLock::new();
+    COMPILATION_ID.get_or_init(|| {
+        let started = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+        format!("{}-{}", std::process::id(), started)
+    })
+}
+
+/// Adds the declaration of the `native_method` to the Java source of its class.
+///
+/// The Java source contains the native declarations of the class that have been generated by the compilation
+/// with the provided `compilation_id`, along with a static initializer that loads the library of the crate that is being compiled.
+/// The declarations of previous compilations are removed, so that functions that are renamed, changed or removed are not declared anymore.
+pub(crate) fn generate_java_stub(java_stubs_dir: &Path, declarations_dir: &Path, compilation_id: &str, native_method: &NativeMethod) -> io::Result<PathBuf> {
+    let class_declarations_dir = declarations_dir.join(&native_method.class_name);
+    let compilation_declarations_dir = class_declarations_dir.join(compilation_id);
+    fs::create_dir_all(&compilation_declarations_dir)?;
+    for entry in fs::read_dir(&class_declarations_dir)? {
+        let path = entry?.path();
+        if path != compilation_declarations_dir {
+            fs::remove_dir_all(path)?;
+        }
+    }
+    write_if_changed(&compilation_declarations_dir.join(native_method.declaration_file_name()), &native_method.declaration())?;
+
+    let mut declaration_files = fs::read_dir(&compilation_declarations_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    declaration_files.sort();
+    let declarations = declaration_files.iter()
+        .map(fs::read_to_string)
+        .collect::<io::Result<Vec<String>>>()?;
+
+    let (package, simple_class_name) = match native_method.class_name.rfind('.') {
+        Some(index) => (Some(&native_method.class_name[..index]), &native_method.class_name[index + 1..]),
+        None => (None, native_method.class_name.as_str()),
+    };
+    let mut java_source_path = java_stubs_dir.to_path_buf();
+    if let Some(package) = package {
+        java_source_path.extend(package.split('.'));
+    }
+    fs::create_dir_all(&java_source_path)?;
+    java_source_path.push(format!("{}.java", simple_class_name));
+
+    write_if_changed(&java_source_path, &java_source(package, simple_class_name, &declarations))?;
+    Ok(java_source_path)
+}
+
+// Files that do not change are not written again, so that the Java sources are not recompiled needlessly
+fn write_if_changed(path: &Path, contents: &str) -> io::Result<()> {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+fn java_source(package: Option<&str>, simple_class_name: &str, declarations: &[String]) -> String {
+    let package_declaration = package
+        .map(|p| format!("package {};\n\n", p))
+        .unwrap_or_default();
+    format!(r#"// Generated by j4rs. Any changes will be overwritten.
+{}import org.astonbitecode.j4rs.api.Instance;
+
+public class {} {{
+    static {{
+        System.loadLibrary("{}");
+    }}
+
+{}
+}}
+"#, package_declaration, simple_class_name, library_name(), declarations.join("\n"))
+}
+
+// The name of the library that is produced by the crate that is being compiled
+fn library_name() -> String {
+    env::var("CARGO_CRATE_NAME")
+        .or_else(|_| env::var("CARGO_PKG_NAME").map(|name| name.replace('-', "_")))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod java_stubs_unit_tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    fn native_method(method_name: &str, args: &[(&str, &str)]) -> NativeMethod {
+        NativeMethod {
+            class_name: "org.astonbitecode.j4rs.tests.Natives".to_string(),
+            method_name: method_name.to_string(),
+            args: args.iter().map(|(java_type, name)| (java_type.to_string(), name.to_string())).collect(),
+            return_type: "int".to_string(),
+        }
+    }
+
+    #[test]
+    fn java_stubs_dir_is_resolved_only_for_primary_packages() {
+        let manifest_dir = Some("/a/crate".to_string());
+        assert_eq!(resolve_java_stubs_dir(None, manifest_dir.clone(), true), None);
+        assert_eq!(resolve_java_stubs_dir(Some("".to_string()), manifest_dir.clone(), true), None);
+        assert_eq!(resolve_java_stubs_dir(Some("src/main/java".to_string()), manifest_dir.clone(), false), None);
+        assert_eq!(
+            resolve_java_stubs_dir(Some("src/main/java".to_string()), manifest_dir.clone(), true),
+            Some(Path::new("/a/crate").join("src/main/java")));
+        assert_eq!(
+            resolve_java_stubs_dir(Some("/java".to_string()), manifest_dir, true),
+            Some(PathBuf::from("/java")));
+    }
+
+    #[test]
+    fn generate_java_stubs() {
+        let java_stubs_dir = env::temp_dir().join(format!("j4rs_java_stubs_{}", std::process::id()));
+        let declarations_dir = java_stubs_dir.join("target");
+        let _ = fs::remove_dir_all(&java_stubs_dir);
+
+        generate_java_stub(&java_stubs_dir, &declarations_dir, "1", &native_method("add", &[("int", "a"), ("int", "b")])).unwrap();
+        let java_source_path = generate_java_stub(&java_stubs_dir, &declarations_dir, "1", &native_method("add", &[("long", "a")])).unwrap();
+        assert_eq!(java_source_path, java_stubs_dir.join("org/astonbitecode/j4rs/tests/Natives.java"));
+        let java_source = fs::read_to_string(&java_source_path).unwrap();
+        assert!(java_source.starts_with("// Generated by j4rs"));
+        assert!(java_source.contains("package org.astonbitecode.j4rs.tests;"));
+        assert!(java_source.contains("public class Natives {"));
+        assert!(java_source.contains("System.loadLibrary("));
+        // Overloads are declared along with each other
+        assert!(java_source.contains("    public static native int add(int a, int b);"));
+        assert!(java_source.contains("    public static native int add(long a);"));
+
+        // Generating the same declaration again does not write the source
+        let modified = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        fs::File::options().write(true).open(&java_source_path).unwrap().set_modified(modified).unwrap();
+        generate_java_stub(&java_stubs_dir, &declarations_dir, "1", &native_method("add", &[("long", "a")])).unwrap();
+        assert_eq!(fs::metadata(&java_source_path).unwrap().modified().unwrap(), modified);
+        assert_eq!(fs::read_to_string(&java_source_path).unwrap(), java_source);
+
+        // Nothing is kept in the directory of the Java sources, besides the sources
+        assert_eq!(fs::read_dir(&java_stubs_dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&java_stubs_dir).unwrap();
+    }
+
+    #[test]
+    fn declarations_of_previous_compilations_are_removed() {
+        let java_stubs_dir = env::temp_dir().join(format!("j4rs_java_stubs_pruned_{}", std::process::id()));
+        let declarations_dir = java_stubs_dir.join("target");
+        let _ = fs::remove_dir_all(&java_stubs_dir);
+
+        generate_java_stub(&java_stubs_dir, &declarations_dir, "1", &native_method("add", &[("int", "a"), ("int", "b")])).unwrap();
+        generate_java_stub(&java_stubs_dir, &declarations_dir, "1", &native_method("sub", &[("int", "a"), ("int", "b")])).unwrap();
+        // The next compilation changes the signature of add and removes sub
+        let java_source_path = generate_java_stub(&java_stubs_dir, &declarations_dir, "2", &native_method("add", &[("long", "a"), ("long", "b")])).unwrap();
+        let java_source = fs::read_to_string(&java_source_path).unwrap();
+        assert!(java_source.contains("    public static native int add(long a, long b);"));
+        assert!(!java_source.contains("add(int a, int b)"));
+        assert!(!java_source.contains("sub("));
+        assert_eq!(fs::read_dir(declarations_dir.join("org.astonbitecode.j4rs.tests.Natives")).unwrap().count(), 1);
+
+        fs::remove_dir_all(&java_stubs_dir).unwrap();
+    }
+}
@@ -16,11 +16,19 @@ extern crate proc_macro2;
 
 use proc_macro::TokenStream;
 
-use proc_macro2::{Ident, Span};
-use syn::{AttributeArgs, Expr, FnArg, ItemFn, Lit, NestedMeta, parse_macro_input, ReturnType};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::{AttributeArgs, DeriveInput, FnArg, GenericArgument, ItemFn, Lit, Meta, NestedMeta, parse_macro_input, Pat, PathArguments, ReturnType, Type};
 
 use quote::quote;
 
+use crate::java_stubs::NativeMethod;
+
+mod java_attributes;
+mod java_class;
+mod java_enum;
+mod java_object;
+mod java_stubs;
+
 #[proc_macro_attribute]
 pub fn call_from_java(macro_args: TokenStream, user_function: TokenStream) -> TokenStream {
     let cloned_user_function = user_function.clone();
@@ -32,92 +40,500 @@ pub fn call_from_java(macro_args: TokenStream, user_function: TokenStream) -> To
     generated
 }
 
+/// Marks a function to be executed when the library is loaded by Java, using the `JNI_OnLoad` of j4rs.
+///
+/// The function must have the signature `fn(&Jvm) -> j4rs::errors::Result<()>`.
+#[proc_macro_attribute]
+pub fn jni_onload(_macro_args: TokenStream, user_function: TokenStream) -> TokenStream {
+    let cloned_user_function = user_function.clone();
+    let user_function = parse_macro_input!(user_function as ItemFn);
+    let user_function_name = &user_function.sig.ident;
+    let mut generated: TokenStream = quote! {
+        inventory::submit! {
+            JniOnLoadHook::new(#user_function_name)
+        }
+    }.into();
+
+    generated.extend(cloned_user_function);
+    generated
+}
+
+/// Maps a fieldless enum to a Java enum, variant by constant. The Java class is defined with the `java` attribute:
+///
+/// ```ignore
+/// #[derive(JavaEnum)]
+/// #[java(class = "java.util.concurrent.TimeUnit")]
+/// enum Unit {
+///     Seconds,
+///     #[java(rename = "MILLISECONDS")]
+///     Millis,
+/// }
+/// ```
+///
+/// By default, the Java constant of a variant is its name in upper snake case (eg. `TIME_UNIT` for `TimeUnit`).
+/// Besides `JavaEnum`, `TryFrom<E> for InvocationArg` is implemented, as well as `Serialize` and `Deserialize`, using the names of the Java constants.
+#[proc_macro_derive(JavaEnum, attributes(java))]
+pub fn derive_java_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    java_enum::impl_java_enum(&input).into()
+}
+
+/// Maps a struct to a Java class, field by field. The Java class is defined with the `java` attribute:
+///
+/// ```ignore
+/// #[derive(JavaObject)]
+/// #[java(class = "com.acme.Order")]
+/// struct Order {
+///     id: i64,
+///     // Matches both a `customer_name` and a `customerName` Java field
+///     customer_name: String,
+///     #[java(rename = "lines")]
+///     items: Vec<String>,
+/// }
+/// ```
+///
+/// Besides `JavaObject`, `TryFrom<&S> for InvocationArg` and `TryFrom<&Instance> for S` are implemented.
+/// The types of the fields should implement `Serialize` and `Deserialize`.
+#[proc_macro_derive(JavaObject, attributes(java))]
+pub fn derive_java_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    java_object::impl_java_object(&input).into()
+}
+
+/// Declares typed wrappers of Java classes. Each class becomes a newtype over `Instance`, with the declared methods:
+///
+/// ```ignore
+/// java_class! {
+///     pub ArrayList = "java.util.ArrayList" {
+///         // Constructors return Self
+///         pub fn new() -> Self;
+///         pub fn size(&self) -> i32;
+///         pub fn add(&self, e: Instance) -> bool;
+///         // Overloads are declared with different Rust names
+///         #[java(name = "add")]
+///         pub fn insert(&self, index: i32, e: Instance);
+///         pub fn get(&self, index: i32) -> Instance;
+///     }
+///
+///     pub Collections = "java.util.Collections" {
+///         pub static fn unmodifiable_list(list: &ArrayList) -> Instance;
+///         pub static fn n_copies(n: i32, o: Instance) -> Instance;
+///     }
+/// }
+/// ```
+///
+/// Every method takes a `&Jvm` after `&self` and returns a `j4rs::errors::Result`.
+/// By default, the Java name of a method is the Rust name in lower camel case (eg. `getFirstName` for `get_first_name`).
+///
+/// The arguments are converted to `InvocationArg`s with `TryFrom`. The Rust primitives are passed as Java primitives,
+/// unless they are marked with `#[java(boxed)]`. References to `Instance`s and to the classes that are declared
+/// in the same `java_class!` are passed as clones of the `Instance`s.
+/// The results are returned as `Instance`s or as the declared classes, while any other type is retrieved with `Jvm::to_rust`.
+#[proc_macro]
+pub fn java_class(input: TokenStream) -> TokenStream {
+    let java_classes = parse_macro_input!(input as java_class::JavaClasses);
+    java_class::impl_java_classes(&java_classes).into()
+}
+
 fn impl_call_from_java_macro(user_function: &ItemFn, macro_args: AttributeArgs) -> TokenStream {
-    let mut macro_args = macro_args;
-    // Retrieve the Ident for the jni function
-    let jni_ident_string = match macro_args.pop().expect("No args found in call_from_java. Usage: #[call_from_java(\"full.class.name\")]") {
-        NestedMeta::Lit(Lit::Str(litstr)) => {
-            format!("Java_{}", litstr.value().replace(".", "_"))
+    // The fully qualified name of the Java native method
+    let mut java_method_full_name = None;
+    // Whether the native method is registered using the JNI RegisterNatives, instead of being located by its symbol name
+    let mut register_natives = false;
+    for macro_arg in macro_args {
+        match macro_arg {
+            NestedMeta::Lit(Lit::Str(litstr)) => java_method_full_name = Some(litstr.value()),
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("register_natives") => register_natives = true,
+            _ => panic!("No valid args found in call_from_java. Usage: #[call_from_java(\"full.class.name\")] or #[call_from_java(\"full.class.name\", register_natives)]"),
         }
-        _ => panic!("No valid args found in call_from_java. Usage: #[call_from_java(\"full.class.name\")]"),
+    }
+    let java_method_full_name = java_method_full_name.expect("No args found in call_from_java. Usage: #[call_from_java(\"full.class.name\")]");
+    let (class_name, method_name) = match java_method_full_name.rfind('.') {
+        Some(index) => (&java_method_full_name[..index], &java_method_full_name[index + 1..]),
+        None => panic!("Could not locate the class of the method {}. Usage: #[call_from_java(\"full.class.name\")]", java_method_full_name),
     };
-    let ref jni_ident = Ident::new(jni_ident_string.as_ref(), Span::call_site());
     // Retrieve the user function Ident, input arguments and return output
     // Ident
     let user_function_signature = &user_function.sig;
     let user_function_name = &user_function_signature.ident;
+    // Retrieve the Ident for the jni function.
+    // Registered natives do not need to follow the JNI naming, so the name of the user function is used to support overloading.
+    let jni_ident_string = if register_natives {
+        format!("__j4rs_native_{}", user_function_name)
+    } else {
+        format!("Java_{}_{}", jni_mangle(class_name), jni_mangle(method_name))
+    };
+    let ref jni_ident = Ident::new(jni_ident_string.as_ref(), Span::call_site());
     // Arguments
     let user_function_args = &user_function_signature.inputs;
-    // The argument names as defined by the user
-    let user_function_arg_names: Vec<String> = user_function_args.iter()
+    // The argument names as defined by the user, along with their types
+    let user_function_arg_names_and_types: Vec<(Ident, JniType)> = user_function_args.iter()
         .map(|arg| {
-            let a = arg.clone();
-            let q = quote!(#a).to_string();
-            let v: Vec<&str> = q.split(' ').collect();
-            v.get(0).expect(&format!("Could not locate the argument name for: {}", q)).to_string()
+            match arg {
+                FnArg::Typed(pat_type) => {
+                    let name = match pat_type.pat.as_ref() {
+                        Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                        other => panic!("Could not locate the argument name for: {}", quote!(#other)),
+                    };
+                    (name, JniType::from_type(&pat_type.ty))
+                }
+                FnArg::Receiver(_) => panic!("Functions that are called from Java cannot have a self argument"),
+            }
         })
         .collect();
     // The arguments of the jni function
-    let jni_function_args: Vec<FnArg> = user_function_arg_names.iter()
-        .map(|arg| {
-            let a: FnArg = syn::parse_str(&format!("{}: jobject", arg)).unwrap();
-            a
+    let jni_function_args: Vec<TokenStream2> = user_function_arg_names_and_types.iter()
+        .map(|(name, jni_type)| {
+            let t = jni_type.jni_type();
+            quote!(#name: #t)
         })
         .collect();
+    let user_return = UserReturn::from_return_type(&user_function_signature.output);
     // The jni function return type
-    let ref jni_function_output = match &user_function_signature.output {
-        ReturnType::Default => ReturnType::Default,
-        _ => {
-            let ret_type: ReturnType = syn::parse_str("-> jobject").unwrap();
-            ret_type
+    let jni_function_output = user_return.jni_function_output();
+    // The value to return to Java in case of an exception or a panic. The exception is pending, so the value is ignored.
+    let jni_default_return_value = user_return.jni_default_value();
+    // The statements that convert the jni arguments to the arguments of the user function
+    let user_function_arg_conversions: Vec<TokenStream2> = user_function_arg_names_and_types.iter()
+        .map(|(name, jni_type)| jni_type.to_rust(name, &java_method_full_name, &jni_default_return_value))
+        .collect();
+    let user_function_arg_names: Vec<&Ident> = user_function_arg_names_and_types.iter().map(|(name, _)| name).collect();
+    // The jni return value
+    let return_value = user_return.return_value(quote!(#user_function_name(#(#user_function_arg_names),*)));
+    // Generate the Java source that declares the native method, if requested
+    if let Some(java_stubs_dir) = java_stubs::java_stubs_dir() {
+        let native_method = NativeMethod {
+            class_name: class_name.to_string(),
+            method_name: method_name.to_string(),
+            args: user_function_arg_names_and_types.iter()
+                .map(|(name, jni_type)| (jni_type.java_type().to_string(), name.to_string()))
+                .collect(),
+            return_type: user_return.java_type().to_string(),
+        };
+        if let Err(error) = java_stubs::generate_java_stub(&java_stubs_dir, &java_stubs::native_declarations_dir(), java_stubs::compilation_id(), &native_method) {
+            panic!("Could not generate the Java source for {} in {:?}: {}", java_method_full_name, java_stubs_dir, error);
         }
+    }
+    let java_stubs_dir_env_var = java_stubs::JAVA_STUBS_DIR_ENV_VAR;
+    // Registered natives are submitted in order to be registered by `Jvm::register_natives`.
+    // Other natives are exported, so that the JVM locates them by their symbol name.
+    let (export, submission) = if register_natives {
+        let jni_signature = format!(
+            "({}){}",
+            user_function_arg_names_and_types.iter().map(|(_, jni_type)| jni_type.jni_signature()).collect::<String>(),
+            user_return.jni_signature());
+        (quote!(), quote! {
+            inventory::submit! {
+                JavaNativeMethod::new(#class_name, #method_name, #jni_signature, {
+                    fn fn_ptr() -> *mut c_void {
+                        #jni_ident as *mut c_void
+                    }
+                    fn_ptr
+                })
+            }
+        })
+    } else {
+        (quote!(#[no_mangle] pub), quote!())
     };
-    // The jni return value. This may be void or jobject
-    let return_value = match &user_function_signature.output {
-        ReturnType::Default => {
-            let ret_value: Expr = syn::parse_str("()").unwrap();
-            ret_value
-        }
-        _ => {
-            let ret_value: Expr = syn::parse_str(
-                r#"match instance_to_return {
-                    Ok(i) => {
-                        i.java_object()
-                        // i.as_java_ptr_with_local_ref(jni_env).unwrap()
+
+    let gen = quote! {
+        // Makes cargo recompile when the directory of the generated Java sources changes
+        const _: Option<&str> = option_env!(#java_stubs_dir_env_var);
+
+        #export
+        extern "system" fn #jni_ident(jni_env: *mut JNIEnv, _class: *const c_void, #(#jni_function_args),*) #jni_function_output {
+            // Panics must not unwind into the JVM. They are thrown to Java as exceptions instead.
+            let to_return = catch_unwind_into_java_exception(jni_env, || {
+                match Jvm::try_from(jni_env) {
+                    Ok(mut jvm) => {
+                        jvm.detach_thread_on_drop(false);
+                        #(#user_function_arg_conversions)*
+                        #return_value
                     },
                     Err(error) => {
-                        let message = format!("{}", error);
-                        let _ = jvm.throw_invocation_exception(&message);
-                        ptr::null_mut()
+                        panic!("Could not attach to the JVM thread: {}", error);
                     },
-                }"#).unwrap();
-            ret_value
+                }
+            });
+            to_return.unwrap_or(#jni_default_return_value)
         }
+
+        #submission
     };
-    let instance_args_to_pass_to_user_function: Vec<Expr> = user_function_arg_names.iter()
-        .map(|jobj_arg_name| {
-            let expression: Expr = syn::parse_str(&format!("Instance::from_jobject_with_global_ref({}).expect(\"Could not create Instance from jobject\")", jobj_arg_name)).unwrap();
-            expression
-        })
-        .collect();
+    gen.into()
+}
 
-    let gen = quote! {
-        #[no_mangle]
-        pub fn #jni_ident(jni_env: *mut JNIEnv, _class: *const c_void, #(#jni_function_args),*) #jni_function_output {
-            match Jvm::try_from(jni_env) {
-                Ok(mut jvm) => {
-                    jvm.detach_thread_on_drop(false);
-                    // println!("Called {}. Calling now  {}", stringify!(#jni_ident), stringify!(#user_function_name));
-                    let instance_to_return = #user_function_name(#(#instance_args_to_pass_to_user_function),*);
-                    #return_value
-                },
+// Escapes a class or method name, as the JNI specification defines for the names of the native functions
+fn jni_mangle(name: &str) -> String {
+    let mut mangled = String::new();
+    for c in name.chars() {
+        match c {
+            '.' | '/' => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    mangled.push_str(&format!("_0{:04x}", unit));
+                }
+            }
+        }
+    }
+    mangled
+}
+
+/// The types that may be used as arguments and return values of the functions that are called from Java.
+enum JniType {
+    Int,
+    Long,
+    Double,
+    Boolean,
+    String,
+    Bytes,
+    Instance,
+}
+
+impl JniType {
+    fn from_type(ty: &Type) -> JniType {
+        let jni_type = match last_path_segment_name(ty).as_deref() {
+            Some("i32") => Some(JniType::Int),
+            Some("i64") => Some(JniType::Long),
+            Some("f64") => Some(JniType::Double),
+            Some("bool") => Some(JniType::Boolean),
+            Some("String") => Some(JniType::String),
+            Some("Instance") => Some(JniType::Instance),
+            Some("Vec") => match first_generic_type(ty).and_then(last_path_segment_name).as_deref() {
+                Some("u8") => Some(JniType::Bytes),
+                _ => None,
+            },
+            _ => None,
+        };
+        jni_type.unwrap_or_else(|| panic!(
+            "Unsupported type in call_from_java: {}. The supported types are Instance, i32, i64, f64, bool, String and Vec<u8>",
+            quote!(#ty)))
+    }
+
+    /// The type that is used in the jni function
+    fn jni_type(&self) -> TokenStream2 {
+        match self {
+            JniType::Int => quote!(jint),
+            JniType::Long => quote!(jlong),
+            JniType::Double => quote!(jdouble),
+            JniType::Boolean => quote!(jboolean),
+            JniType::String => quote!(jstring),
+            JniType::Bytes => quote!(jbyteArray),
+            JniType::Instance => quote!(jobject),
+        }
+    }
+
+    /// The type that is used in the Java native method declaration
+    fn java_type(&self) -> &'static str {
+        match self {
+            JniType::Int => "int",
+            JniType::Long => "long",
+            JniType::Double => "double",
+            JniType::Boolean => "boolean",
+            JniType::String => "String",
+            JniType::Bytes => "byte[]",
+            JniType::Instance => "Instance",
+        }
+    }
+
+    /// The type signature, as used by the JNI
+    fn jni_signature(&self) -> &'static str {
+        match self {
+            JniType::Int => "I",
+            JniType::Long => "J",
+            JniType::Double => "D",
+            JniType::Boolean => "Z",
+            JniType::String => "Ljava/lang/String;",
+            JniType::Bytes => "[B",
+            JniType::Instance => "Lorg/astonbitecode/j4rs/api/Instance;",
+        }
+    }
+
+    fn jni_default_value(&self) -> TokenStream2 {
+        match self {
+            JniType::Int | JniType::Long => quote!(0),
+            JniType::Double => quote!(0.0),
+            JniType::Boolean => quote!(JNI_FALSE),
+            JniType::String | JniType::Bytes | JniType::Instance => quote!(ptr::null_mut()),
+        }
+    }
+
+    /// The statements that shadow the jni argument with the passed name with the Rust value that the user function expects.
+    /// Null Strings and arrays are thrown to Java as `NullPointerException`s and any other conversion error as an
+    /// `InvocationException`. In these cases, `default_value` is returned without calling the user function.
+    fn to_rust(&self, arg_name: &Ident, java_method_full_name: &str, default_value: &TokenStream2) -> TokenStream2 {
+        let conversion = match self {
+            JniType::Int | JniType::Long | JniType::Double => return quote!(),
+            JniType::Boolean => return quote!(let #arg_name = #arg_name != JNI_FALSE;),
+            JniType::String => quote!(jstring_to_rust_string(&jvm, #arg_name)),
+            JniType::Bytes => quote!(jbyte_array_to_rust_vec(&jvm, #arg_name)),
+            JniType::Instance => quote!(Instance::from_jobject_with_global_ref(#arg_name)),
+        };
+        let null_check = match self {
+            JniType::String | JniType::Bytes => {
+                let message = format!("The argument {} of {} is null", arg_name, java_method_full_name);
+                quote! {
+                    if #arg_name.is_null() {
+                        let _ = jvm.throw_exception("java.lang.NullPointerException", #message);
+                        return #default_value;
+                    }
+                }
+            }
+            _ => quote!(),
+        };
+        let message = format!("Could not convert the argument {} of {}", arg_name, java_method_full_name);
+        quote! {
+            #null_check
+            let #arg_name = match #conversion {
+                Ok(converted) => converted,
                 Err(error) => {
-                    let message = format!("Could not attach to the JVM thread: {}", error);
-                    println!("{}", message);
-                    panic!(message);
+                    let _ = jvm.throw_invocation_exception(&format!("{}: {}", #message, error));
+                    return #default_value;
+                }
+            };
+        }
+    }
+
+    /// Converts the Rust value with the passed name to the jni type. The generated expression is a `Result<jni type, String>`
+    fn to_jni(&self, value_name: &Ident) -> TokenStream2 {
+        match self {
+            JniType::Int | JniType::Long | JniType::Double => quote!(Ok(#value_name)),
+            JniType::Boolean => quote!(Ok(if #value_name { JNI_TRUE } else { JNI_FALSE })),
+            JniType::String => quote!(rust_string_to_jstring(&jvm, &#value_name).map_err(|error| format!("{}", error))),
+            JniType::Bytes => quote!(rust_bytes_to_jbyte_array(&jvm, &#value_name).map_err(|error| format!("{}", error))),
+            JniType::Instance => quote!(Ok(#value_name.java_object())),
+        }
+    }
+}
+
+/// The return type of the user function
+enum UserReturn {
+    Void,
+    // A `Result<(), E>`
+    VoidResult,
+    Value(JniType),
+    Result(JniType),
+}
+
+impl UserReturn {
+    fn from_return_type(return_type: &ReturnType) -> UserReturn {
+        match return_type {
+            ReturnType::Default => UserReturn::Void,
+            ReturnType::Type(_, ty) => match ty.as_ref() {
+                Type::Tuple(tuple) if tuple.elems.is_empty() => UserReturn::Void,
+                _ if last_path_segment_name(ty).as_deref() == Some("Result") => {
+                    let ok_type = first_generic_type(ty)
+                        .unwrap_or_else(|| panic!("Could not locate the Ok type of {}", quote!(#ty)));
+                    match ok_type {
+                        Type::Tuple(tuple) if tuple.elems.is_empty() => UserReturn::VoidResult,
+                        _ => UserReturn::Result(JniType::from_type(ok_type)),
+                    }
+                }
+                _ => UserReturn::Value(JniType::from_type(ty)),
+            }
+        }
+    }
+
+    fn jni_function_output(&self) -> TokenStream2 {
+        match self {
+            UserReturn::Void | UserReturn::VoidResult => quote!(),
+            UserReturn::Value(jni_type) | UserReturn::Result(jni_type) => {
+                let t = jni_type.jni_type();
+                quote!(-> #t)
+            }
+        }
+    }
+
+    fn java_type(&self) -> &'static str {
+        match self {
+            UserReturn::Void | UserReturn::VoidResult => "void",
+            UserReturn::Value(jni_type) | UserReturn::Result(jni_type) => jni_type.java_type(),
+        }
+    }
+
+    fn jni_signature(&self) -> &'static str {
+        match self {
+            UserReturn::Void | UserReturn::VoidResult => "V",
+            UserReturn::Value(jni_type) | UserReturn::Result(jni_type) => jni_type.jni_signature(),
+        }
+    }
+
+    fn jni_default_value(&self) -> TokenStream2 {
+        match self {
+            UserReturn::Void | UserReturn::VoidResult => quote!(()),
+            UserReturn::Value(jni_type) | UserReturn::Result(jni_type) => jni_type.jni_default_value(),
+        }
+    }
+
+    /// Calls the user function and converts the returned value to the jni type.
+    /// Errors are thrown to Java as `InvocationException`s.
+    fn return_value(&self, user_function_call: TokenStream2) -> TokenStream2 {
+        let value_name = Ident::new("value", Span::call_site());
+        let (jni_type, converted) = match self {
+            UserReturn::Void => return quote! {
+                #user_function_call;
+            },
+            UserReturn::VoidResult => return quote! {
+                if let Err(error) = #user_function_call {
+                    let _ = jvm.throw_invocation_exception(&format!("{}", error));
+                }
+            },
+            UserReturn::Value(jni_type) => {
+                let to_jni = jni_type.to_jni(&value_name);
+                (jni_type, quote! {
+                    let #value_name = #user_function_call;
+                    #to_jni
+                })
+            }
+            UserReturn::Result(jni_type) => {
+                let to_jni = jni_type.to_jni(&value_name);
+                (jni_type, quote! {
+                    #user_function_call
+                        .map_err(|error| format!("{}", error))
+                        .and_then(|#value_name| #to_jni)
+                })
+            }
+        };
+        let t = jni_type.jni_type();
+        let default_value = jni_type.jni_default_value();
+        quote! {
+            let converted: std::result::Result<#t, String> = { #converted };
+            match converted {
+                Ok(to_return) => to_return,
+                Err(message) => {
+                    let _ = jvm.throw_invocation_exception(&message);
+                    #default_value
                 },
             }
         }
-    };
-    gen.into()
+    }
+}
+
+// The name of the last path segment of a type. For example, `Vec` for `std::vec::Vec<u8>`
+fn last_path_segment_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+// The first generic type argument of a type. For example, `u8` for `Vec<u8>`
+fn first_generic_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(type_path) => match type_path.path.segments.last().map(|segment| &segment.arguments) {
+            Some(PathArguments::AngleBracketed(args)) => args.args.iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(t) => Some(t),
+                    _ => None,
+                })
+                .next(),
+            _ => None,
+        },
+        _ => None,
+    }
 }
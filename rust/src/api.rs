@@ -14,6 +14,7 @@
 
 use std::{fs, mem, thread, time};
 use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::env;
 use std::ops::Drop;
@@ -21,10 +22,14 @@ use std::os::raw::c_void;
 use std::path::{MAIN_SEPARATOR, Path, PathBuf};
 use std::ptr;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, SystemTime};
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone};
 use fs_extra::dir::get_dir_content;
 use jni_sys::{
     self,
+    jboolean,
     JavaVM,
     JavaVMInitArgs,
     JavaVMOption,
@@ -40,19 +45,32 @@ use jni_sys::{
     JNI_VERSION_1_8,
     JNIEnv,
     jobject,
+    jobjectArray,
     jsize,
     jstring,
+    jvalue,
 };
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json;
 
 use crate::{api_tweaks as tweaks, cache, MavenSettings};
+use crate::arrays::{self, JavaPrimitive};
+use crate::buffers::{self, DirectBufferMemory, DirectByteBuffer};
+use crate::codec::Codec;
+use crate::datetime;
+use crate::enums::JavaEnum;
 use crate::errors;
 use crate::errors::{J4RsError, opt_to_res};
 use crate::jni_utils;
+use crate::natives;
+use crate::numbers::{self, BigDecimal, BigInteger};
+use crate::objects::JavaObject;
 use crate::provisioning::{get_maven_settings, JavaArtifact, LocalJarArtifact, MavenArtifact};
 use crate::provisioning;
+use crate::proxy;
+use crate::reflection::{self, ClassDescription};
+use crate::serde_jni;
 use crate::utils;
 
 use super::logger::{debug, error, info, warn};
@@ -69,7 +87,21 @@ pub(crate) const CLASS_INTEGER: &'static str = "java.lang.Integer";
 pub(crate) const CLASS_LONG: &'static str = "java.lang.Long";
 pub(crate) const CLASS_FLOAT: &'static str = "java.lang.Float";
 pub(crate) const CLASS_DOUBLE: &'static str = "java.lang.Double";
+pub(crate) const CLASS_BIG_INTEGER: &'static str = "java.math.BigInteger";
+pub(crate) const CLASS_BIG_DECIMAL: &'static str = "java.math.BigDecimal";
+pub(crate) const CLASS_DURATION: &'static str = "java.time.Duration";
+pub(crate) const CLASS_INSTANT: &'static str = "java.time.Instant";
+pub(crate) const CLASS_DATE: &'static str = "java.util.Date";
+#[cfg(feature = "chrono")]
+pub(crate) const CLASS_LOCAL_DATE: &'static str = "java.time.LocalDate";
+#[cfg(feature = "chrono")]
+pub(crate) const CLASS_LOCAL_DATE_TIME: &'static str = "java.time.LocalDateTime";
+#[cfg(feature = "chrono")]
+pub(crate) const CLASS_ZONED_DATE_TIME: &'static str = "java.time.ZonedDateTime";
 pub(crate) const CLASS_LIST: &'static str = "java.util.List";
+pub(crate) const CLASS_SET: &'static str = "java.util.Set";
+pub(crate) const CLASS_MAP: &'static str = "java.util.Map";
+pub(crate) const CLASS_OPTIONAL: &'static str = "java.util.Optional";
 pub(crate) const CLASS_J4RS_EVENT_HANDLER: &'static str = "org.astonbitecode.j4rs.api.jfx.handlers.J4rsEventHandler";
 pub const _JNI_VERSION_10: jint = 0x000a0000;
 
@@ -80,6 +112,8 @@ pub type Callback = fn(Jvm, Instance) -> ();
 pub struct Jvm {
     pub(crate) jni_env: *mut JNIEnv,
     detach_thread_on_drop: bool,
+    codec: Codec,
+    force_accessible: bool,
 }
 
 impl Jvm {
@@ -113,6 +147,40 @@ impl Jvm {
         self.detach_thread_on_drop = detach;
     }
 
+    /// Sets the `Codec` that this Jvm uses for serializing the values that are transferred between Rust and Java.
+    ///
+    /// It is used by `Jvm::to_rust` and `Jvm::create_invocation_arg`.
+    pub fn set_codec(&mut self, codec: Codec) {
+        self.codec = codec;
+    }
+
+    /// Returns the `Codec` that this Jvm uses for serializing the values that are transferred between Rust and Java.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Defines whether this Jvm forces the accessibility of the fields that it reads and writes.
+    ///
+    /// If true, the fields may be private, or declared by any superclass of the class, and final instance fields may be set.
+    /// It is used by `Jvm::field`, `Jvm::set_field`, `Jvm::static_field` and `Jvm::set_static_field`.
+    pub fn set_force_accessible(&mut self, force_accessible: bool) {
+        self.force_accessible = force_accessible;
+    }
+
+    /// Returns whether this Jvm forces the accessibility of the fields that it reads and writes.
+    pub fn force_accessible(&self) -> bool {
+        self.force_accessible
+    }
+
+    /// Creates an InvocationArg, serializing the `arg` with the `Codec` of this Jvm.
+    ///
+    /// Like `InvocationArg::new`, the basic types (eg. String, i32 etc.) are not serialized.
+    pub fn create_invocation_arg<T>(&self, arg: &T, class_name: &str) -> errors::Result<InvocationArg>
+        where T: Serialize + Any
+    {
+        InvocationArg::new_with_codec_and_env(arg, class_name, self.codec, self.jni_env)
+    }
+
     /// Creates a new Jvm.
     /// If a JavaVM is already created by the current process, it attempts to attach the current thread to it.
     fn create_jvm(jvm_options: &[String], lib_name_to_load: Option<String>) -> errors::Result<Jvm> {
@@ -213,11 +281,31 @@ impl Jvm {
             let ec = cache::get_jni_exception_check().or_else(|| cache::set_jni_exception_check((**jni_environment).ExceptionCheck));
             let ed = cache::get_jni_exception_describe().or_else(|| cache::set_jni_exception_describe((**jni_environment).ExceptionDescribe));
             let exclear = cache::get_jni_exception_clear().or_else(|| cache::set_jni_exception_clear((**jni_environment).ExceptionClear));
+            let _ = cache::get_jni_exception_occurred().or_else(|| cache::set_jni_exception_occurred((**jni_environment).ExceptionOccurred));
             let _ = cache::get_jni_delete_local_ref().or_else(|| cache::set_jni_delete_local_ref((**jni_environment).DeleteLocalRef));
             let _ = cache::get_jni_delete_global_ref().or_else(|| cache::set_jni_delete_global_ref((**jni_environment).DeleteGlobalRef));
             let _ = cache::get_jni_new_global_ref().or_else(|| cache::set_jni_new_global_ref((**jni_environment).NewGlobalRef));
             let _ = cache::get_jni_throw_new().or_else(|| cache::set_jni_throw_new((**jni_environment).ThrowNew));
             let _ = cache::get_is_same_object().or_else(|| cache::set_is_same_object((**jni_environment).IsSameObject));
+            let _ = cache::get_jni_throw().or_else(|| cache::set_jni_throw((**jni_environment).Throw));
+            let _ = cache::get_is_instance_of().or_else(|| cache::set_is_instance_of((**jni_environment).IsInstanceOf));
+            let _ = cache::get_jni_get_array_length().or_else(|| cache::set_jni_get_array_length((**jni_environment).GetArrayLength));
+            let _ = cache::get_jni_new_byte_array().or_else(|| cache::set_jni_new_byte_array((**jni_environment).NewByteArray));
+            let _ = cache::get_jni_get_byte_array_region().or_else(|| cache::set_jni_get_byte_array_region((**jni_environment).GetByteArrayRegion));
+            let _ = cache::get_jni_set_byte_array_region().or_else(|| cache::set_jni_set_byte_array_region((**jni_environment).SetByteArrayRegion));
+            let _ = cache::get_jni_get_object_array_element().or_else(|| cache::set_jni_get_object_array_element((**jni_environment).GetObjectArrayElement));
+            let _ = cache::get_jni_new_local_ref().or_else(|| cache::set_jni_new_local_ref((**jni_environment).NewLocalRef));
+            let _ = cache::get_jni_call_object_method_a().or_else(|| cache::set_jni_call_object_method_a((**jni_environment).CallObjectMethodA));
+            let _ = cache::get_jni_call_static_object_method_a().or_else(|| cache::set_jni_call_static_object_method_a((**jni_environment).CallStaticObjectMethodA));
+            let _ = cache::get_jni_call_static_void_method_a().or_else(|| cache::set_jni_call_static_void_method_a((**jni_environment).CallStaticVoidMethodA));
+            let _ = cache::get_jni_call_boolean_method_a().or_else(|| cache::set_jni_call_boolean_method_a((**jni_environment).CallBooleanMethodA));
+            let _ = cache::get_jni_call_byte_method_a().or_else(|| cache::set_jni_call_byte_method_a((**jni_environment).CallByteMethodA));
+            let _ = cache::get_jni_call_char_method_a().or_else(|| cache::set_jni_call_char_method_a((**jni_environment).CallCharMethodA));
+            let _ = cache::get_jni_call_short_method_a().or_else(|| cache::set_jni_call_short_method_a((**jni_environment).CallShortMethodA));
+            let _ = cache::get_jni_call_int_method_a().or_else(|| cache::set_jni_call_int_method_a((**jni_environment).CallIntMethodA));
+            let _ = cache::get_jni_call_long_method_a().or_else(|| cache::set_jni_call_long_method_a((**jni_environment).CallLongMethodA));
+            let _ = cache::get_jni_call_float_method_a().or_else(|| cache::set_jni_call_float_method_a((**jni_environment).CallFloatMethodA));
+            let _ = cache::get_jni_call_double_method_a().or_else(|| cache::set_jni_call_double_method_a((**jni_environment).CallDoubleMethodA));
 
             match (ec, ed, exclear) {
                 (Some(ec), Some(ed), Some(exclear)) => {
@@ -229,6 +317,8 @@ impl Jvm {
                         let jvm = Jvm {
                             jni_env: jni_environment,
                             detach_thread_on_drop: true,
+                            codec: Codec::default(),
+                            force_accessible: false,
                         };
 
                         if cache::get_thread_local_env_opt().is_none() {
@@ -327,6 +417,9 @@ impl Jvm {
 
             jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
 
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+
             // Create and return the Instance.
             Self::do_return(self.jni_env, Instance::from_jobject_with_global_ref(java_instance)?)
         }
@@ -411,31 +504,8 @@ impl Jvm {
             let class_name_jstring: jstring = jni_utils::global_jobject_from_str(&class_name, jni_env)?;
 
             // Factory invocation - rest of the arguments: Create a new object list of class InvocationArg
-            let size = inv_args.len() as i32;
-            let array_ptr = {
-                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
-                    jni_env,
-                    size,
-                    cache::get_invocation_arg_class()?,
-                    ptr::null_mut(),
-                );
-                jni_utils::create_global_ref_from_local_ref(j, jni_env)?
-            };
-            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
-
-            // Factory invocation - rest of the arguments: populate the array
-            for i in 0..size {
-                // Create an InvocationArg Java Object
-                let inv_arg_java = inv_args[i as usize].as_java_ptr_with_global_ref(jni_env)?;
-                // Set it in the array
-                (opt_to_res(cache::get_jni_set_object_array_element())?)(
-                    jni_env,
-                    array_ptr,
-                    i,
-                    inv_arg_java,
-                );
-                inv_arg_jobjects.push(inv_arg_java);
-            }
+            let inv_args: Vec<&InvocationArg> = inv_args.iter().collect();
+            let (array_ptr, inv_arg_jobjects) = Self::java_invocation_args(jni_env, &inv_args)?;
             // Call the method of the factory that instantiates a new Java Array of `class_name`.
             // This returns a Instance that acts like a proxy to the Java world.
             let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
@@ -466,6 +536,120 @@ impl Jvm {
         }
     }
 
+    /// Creates a new Java Set that contains the `InvocationArg`s. The elements may be of any class.
+    pub fn create_java_set(&self, inv_args: &[InvocationArg]) -> errors::Result<Instance> {
+        let inv_args: Vec<&InvocationArg> = inv_args.iter().collect();
+        Jvm::do_create_java_collection(self.jni_env, "createJavaSet", &inv_args, CLASS_SET)
+    }
+
+    /// Creates a new Java Map that contains the `entries`, which are pairs of keys and values. The keys and values may be of any class.
+    pub fn create_java_map(&self, entries: &[(InvocationArg, InvocationArg)]) -> errors::Result<Instance> {
+        let keys_and_values: Vec<&InvocationArg> = entries.iter().flat_map(|(key, value)| vec![key, value]).collect();
+        Jvm::do_create_java_collection(self.jni_env, "createJavaMap", &keys_and_values, CLASS_MAP)
+    }
+
+    /// Creates a new `java.util.Optional` that contains the `value`, or an empty `Optional` if the `value` is `None`.
+    pub fn create_java_optional(&self, value: Option<InvocationArg>) -> errors::Result<Instance> {
+        let inv_args: Vec<&InvocationArg> = value.iter().collect();
+        Jvm::do_create_java_collection(self.jni_env, "createJavaOptional", &inv_args, CLASS_OPTIONAL)
+    }
+
+    /// Retrieves the constant with the provided `name`, of the Java enum with the provided `class_name`.
+    pub fn create_java_enum(&self, class_name: &str, name: &str) -> errors::Result<Instance> {
+        Jvm::do_create_java_enum(self.jni_env, class_name, name)
+    }
+
+    // Calls a method of the factory that creates a collection from an `InvocationArg[]`
+    fn do_create_java_collection(jni_env: *mut JNIEnv, factory_method: &'static str, inv_args: &[&InvocationArg], class_name: &str) -> errors::Result<Instance> {
+        debug(&format!("Creating a {} with {} elements", class_name, inv_args.len()));
+        unsafe {
+            let (array_ptr, inv_arg_jobjects) = Self::java_invocation_args(jni_env, inv_args)?;
+            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method_a())?)(
+                jni_env,
+                cache::get_class(cache::INST_CLASS_NAME)?,
+                cache::get_static_method_id(
+                    cache::INST_CLASS_NAME,
+                    factory_method,
+                    "([Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)Lorg/astonbitecode/j4rs/api/Instance;",
+                )?,
+                [jvalue { l: array_ptr }].as_ptr(),
+            );
+            // Prevent memory leaks from the created references
+            for inv_arg_jobject in inv_arg_jobjects {
+                jni_utils::delete_java_ref(jni_env, inv_arg_jobject);
+            }
+            jni_utils::delete_java_ref(jni_env, array_ptr);
+
+            let java_instance = Self::do_return(jni_env, java_instance)?;
+            Ok(Instance {
+                jinstance: jni_utils::create_global_ref_from_local_ref(java_instance, jni_env)?,
+                class_name: class_name.to_string(),
+                skip_deleting_jobject: false,
+            })
+        }
+    }
+
+    fn do_create_java_enum(jni_env: *mut JNIEnv, class_name: &str, name: &str) -> errors::Result<Instance> {
+        debug(&format!("Retrieving the constant {} of the enum {}", name, class_name));
+        unsafe {
+            let class_name_jstring: jstring = jni_utils::global_jobject_from_str(class_name, jni_env)?;
+            let name_jstring: jstring = jni_utils::global_jobject_from_str(name, jni_env)?;
+            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method_a())?)(
+                jni_env,
+                cache::get_class(cache::INST_CLASS_NAME)?,
+                cache::get_static_method_id(
+                    cache::INST_CLASS_NAME,
+                    "createJavaEnum",
+                    "(Ljava/lang/String;Ljava/lang/String;)Lorg/astonbitecode/j4rs/api/Instance;",
+                )?,
+                [jvalue { l: class_name_jstring }, jvalue { l: name_jstring }].as_ptr(),
+            );
+            // Prevent memory leaks from the created references
+            jni_utils::delete_java_ref(jni_env, class_name_jstring);
+            jni_utils::delete_java_ref(jni_env, name_jstring);
+
+            let java_instance = Self::do_return(jni_env, java_instance)?;
+            Ok(Instance {
+                jinstance: jni_utils::create_global_ref_from_local_ref(java_instance, jni_env)?,
+                class_name: class_name.to_string(),
+                skip_deleting_jobject: false,
+            })
+        }
+    }
+
+    // Creates a Java InvocationArg[] that contains the `inv_args`.
+    // The array and its elements are global references that should be deleted after use.
+    fn java_invocation_args(jni_env: *mut JNIEnv, inv_args: &[&InvocationArg]) -> errors::Result<(jobjectArray, Vec<jobject>)> {
+        unsafe {
+            let size = inv_args.len() as i32;
+            let array_ptr = {
+                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
+                    jni_env,
+                    size,
+                    cache::get_invocation_arg_class()?,
+                    ptr::null_mut(),
+                );
+                jni_utils::create_global_ref_from_local_ref(j, jni_env)?
+            };
+            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
+
+            // Populate the array
+            for (i, inv_arg) in inv_args.iter().enumerate() {
+                // Create an InvocationArg Java Object
+                let inv_arg_java = inv_arg.as_java_ptr_with_global_ref(jni_env)?;
+                // Set it in the array
+                (opt_to_res(cache::get_jni_set_object_array_element())?)(
+                    jni_env,
+                    array_ptr,
+                    i as jsize,
+                    inv_arg_java,
+                );
+                inv_arg_jobjects.push(inv_arg_java);
+            }
+            Ok((array_ptr, inv_arg_jobjects))
+        }
+    }
+
     /// Invokes the method `method_name` of a created `Instance`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
     pub fn invoke(&self, instance: &Instance, method_name: &str, inv_args: &[InvocationArg]) -> errors::Result<Instance> {
         debug(&format!("Invoking method {} of class {} using {} arguments", method_name, instance.class_name, inv_args.len()));
@@ -530,33 +714,127 @@ impl Jvm {
     }
 
     /// Retrieves the field `field_name` of a created `Instance`.
+    ///
+    /// The class name of the returned `Instance` is the declared type of the field.
     pub fn field(&self, instance: &Instance, field_name: &str) -> errors::Result<Instance> {
         debug(&format!("Retrieving field {} of class {}", field_name, instance.class_name));
+        if self.force_accessible {
+            return self.do_get_field("getField", "(Lorg/astonbitecode/j4rs/api/Instance;Ljava/lang/String;Z)Lorg/astonbitecode/j4rs/api/Instance;", instance.jinstance, field_name);
+        }
         unsafe {
+            let call_object_method = opt_to_res(cache::get_jni_call_object_method())?;
+            let field_method = cache::get_field_method()?;
             // First argument: create a jstring to pass as argument for the field_name
             let field_name_jstring: jstring = jni_utils::global_jobject_from_str(&field_name, self.jni_env)?;
 
             // Call the method of the instance
-            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+            let java_instance = (call_object_method)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_field_method()?,
+                field_method,
                 field_name_jstring,
             );
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
-
-            let java_instance_global_instance = jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
             // Prevent memory leaks from the created local references
             jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
 
-            // Create and return the Instance
-            Self::do_return(self.jni_env, Instance {
-                jinstance: java_instance_global_instance,
-                class_name: cache::UNKNOWN_FOR_RUST.to_string(),
+            let java_instance = Self::do_return(self.jni_env, java_instance)?;
+            self.field_instance(java_instance)
+        }
+    }
+
+    /// Sets the field `field_name` of a created `Instance` to the provided `value`.
+    ///
+    /// The `value` is converted to the type of the field if needed (eg. an `i64` to an `int`), unless precision would be lost.
+    pub fn set_field(&self, instance: &Instance, field_name: &str, value: InvocationArg) -> errors::Result<()> {
+        debug(&format!("Setting field {} of class {}", field_name, instance.class_name));
+        self.do_set_field("setField", "(Lorg/astonbitecode/j4rs/api/Instance;Ljava/lang/String;Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;Z)V", instance.jinstance, field_name, &value)
+    }
+
+    /// Retrieves the static field `field_name` of the class `class_name` (eg. `Integer.MAX_VALUE`).
+    ///
+    /// The class name of the returned `Instance` is the declared type of the field.
+    pub fn static_field(&self, class_name: &str, field_name: &str) -> errors::Result<Instance> {
+        debug(&format!("Retrieving static field {} of class {}", field_name, class_name));
+        let class_name_jstring: jstring = jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+        let result = self.do_get_field("getStaticField", "(Ljava/lang/String;Ljava/lang/String;Z)Lorg/astonbitecode/j4rs/api/Instance;", class_name_jstring, field_name);
+        jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+        result
+    }
+
+    /// Sets the static field `field_name` of the class `class_name` to the provided `value`.
+    ///
+    /// The `value` is converted to the type of the field if needed (eg. an `i64` to an `int`), unless precision would be lost.
+    pub fn set_static_field(&self, class_name: &str, field_name: &str, value: InvocationArg) -> errors::Result<()> {
+        debug(&format!("Setting static field {} of class {}", field_name, class_name));
+        let class_name_jstring: jstring = jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+        let result = self.do_set_field("setStaticField", "(Ljava/lang/String;Ljava/lang/String;Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;Z)V", class_name_jstring, field_name, &value);
+        jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+        result
+    }
+
+    // Calls a static method of the factory that retrieves a field of the `target` (an Instance or a class name)
+    fn do_get_field(&self, factory_method: &'static str, signature: &'static str, target: jobject, field_name: &str) -> errors::Result<Instance> {
+        unsafe {
+            let call_static_object_method = opt_to_res(cache::get_jni_call_static_object_method_a())?;
+            let class = cache::get_class(cache::INST_CLASS_NAME)?;
+            let method_id = cache::get_static_method_id(cache::INST_CLASS_NAME, factory_method, signature)?;
+            let field_name_jstring: jstring = jni_utils::global_jobject_from_str(field_name, self.jni_env)?;
+            let java_instance = (call_static_object_method)(
+                self.jni_env,
+                class,
+                method_id,
+                [jvalue { l: target }, jvalue { l: field_name_jstring }, jvalue { z: self.force_accessible as jboolean }].as_ptr(),
+            );
+            // Prevent memory leaks from the created references
+            jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
+
+            let java_instance = Self::do_return(self.jni_env, java_instance)?;
+            self.field_instance(java_instance)
+        }
+    }
+
+    // Creates the Instance of a retrieved field. The Java Instance has the declared type of the field.
+    fn field_instance(&self, java_instance: jobject) -> errors::Result<Instance> {
+        let jinstance = jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
+        match jni_utils::known_class_name(jinstance, self.jni_env) {
+            Ok(class_name) => Ok(Instance {
+                jinstance,
+                class_name,
                 skip_deleting_jobject: false,
-            })
+            }),
+            Err(error) => {
+                jni_utils::delete_java_ref(self.jni_env, jinstance);
+                Err(error)
+            }
+        }
+    }
+
+    // Calls a static method of the factory that sets a field of the `target` (an Instance or a class name)
+    fn do_set_field(&self, factory_method: &'static str, signature: &'static str, target: jobject, field_name: &str, value: &InvocationArg) -> errors::Result<()> {
+        unsafe {
+            let call_static_void_method = opt_to_res(cache::get_jni_call_static_void_method_a())?;
+            let class = cache::get_class(cache::INST_CLASS_NAME)?;
+            let method_id = cache::get_static_method_id(cache::INST_CLASS_NAME, factory_method, signature)?;
+            let field_name_jstring: jstring = jni_utils::global_jobject_from_str(field_name, self.jni_env)?;
+            let value_jobject = match value.as_java_ptr_with_global_ref(self.jni_env) {
+                Ok(value_jobject) => value_jobject,
+                Err(error) => {
+                    jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
+                    return Err(error);
+                }
+            };
+            (call_static_void_method)(
+                self.jni_env,
+                class,
+                method_id,
+                [jvalue { l: target }, jvalue { l: field_name_jstring }, jvalue { l: value_jobject }, jvalue { z: self.force_accessible as jboolean }].as_ptr(),
+            );
+            // Prevent memory leaks from the created references
+            jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
+            jni_utils::delete_java_ref(self.jni_env, value_jobject);
+
+            Self::do_return(self.jni_env, ())
         }
     }
 
@@ -657,6 +935,126 @@ impl Jvm {
         }
     }
 
+    /// Creates a Java object that implements the interface `interface_name`, using a `java.lang.reflect.Proxy`.
+    ///
+    /// Each method call on the returned `Instance` calls the closure `f`, passing the name of the method and its arguments.
+    /// The `Instance` returned by the closure is the return value of the method. For `void` methods it is ignored.
+    /// If the closure returns an error, an `InvocationException` is thrown in Java.
+    ///
+    /// The `equals`, `hashCode` and `toString` methods are implemented in Java, based on the identity of the proxy.
+    ///
+    /// The closure may be called by any Java thread and it is dropped after the proxy is garbage collected.
+    pub fn proxy<F>(&self, interface_name: &str, f: F) -> errors::Result<Instance>
+        where F: FnMut(&str, Vec<Instance>) -> errors::Result<Instance> + Send + 'static {
+        proxy::new_proxy(self, interface_name, Box::new(f))
+    }
+
+    /// Creates a `java.util.function.Function` that calls the closure `f`.
+    ///
+    /// The argument of the `Function` is deserialized to `T` and the value returned by `f` is serialized to a Java object,
+    /// like the `InvocationArg`s that are created from serializable Rust values.
+    pub fn java_function<T, R, F>(&self, f: F) -> errors::Result<Instance>
+        where T: DeserializeOwned + Any, R: Serialize + Any, F: Fn(T) -> R + Send + 'static {
+        proxy::new_functional_proxy(self, "java.util.function.Function", "apply", move |jvm, args| {
+            let mut args = args.into_iter();
+            let t = proxy::next_arg(jvm, &mut args)?;
+            proxy::return_value(jvm, &f(t))
+        })
+    }
+
+    /// Creates a `java.util.function.BiFunction` that calls the closure `f`.
+    ///
+    /// See `java_function`.
+    pub fn java_bi_function<T, U, R, F>(&self, f: F) -> errors::Result<Instance>
+        where T: DeserializeOwned + Any, U: DeserializeOwned + Any, R: Serialize + Any, F: Fn(T, U) -> R + Send + 'static {
+        proxy::new_functional_proxy(self, "java.util.function.BiFunction", "apply", move |jvm, args| {
+            let mut args = args.into_iter();
+            let t = proxy::next_arg(jvm, &mut args)?;
+            let u = proxy::next_arg(jvm, &mut args)?;
+            proxy::return_value(jvm, &f(t, u))
+        })
+    }
+
+    /// Creates a `java.util.function.Consumer` that calls the closure `f`.
+    ///
+    /// See `java_function`.
+    pub fn java_consumer<T, F>(&self, f: F) -> errors::Result<Instance>
+        where T: DeserializeOwned + Any, F: Fn(T) + Send + 'static {
+        proxy::new_functional_proxy(self, "java.util.function.Consumer", "accept", move |jvm, args| {
+            let mut args = args.into_iter();
+            f(proxy::next_arg(jvm, &mut args)?);
+            proxy::return_void()
+        })
+    }
+
+    /// Creates a `java.util.function.Supplier` that calls the closure `f`.
+    ///
+    /// See `java_function`.
+    pub fn java_supplier<R, F>(&self, f: F) -> errors::Result<Instance>
+        where R: Serialize + Any, F: Fn() -> R + Send + 'static {
+        proxy::new_functional_proxy(self, "java.util.function.Supplier", "get", move |jvm, _| {
+            proxy::return_value(jvm, &f())
+        })
+    }
+
+    /// Creates a `java.util.function.Predicate` that calls the closure `f`.
+    ///
+    /// See `java_function`.
+    pub fn java_predicate<T, F>(&self, f: F) -> errors::Result<Instance>
+        where T: DeserializeOwned + Any, F: Fn(T) -> bool + Send + 'static {
+        proxy::new_functional_proxy(self, "java.util.function.Predicate", "test", move |jvm, args| {
+            let mut args = args.into_iter();
+            let t = proxy::next_arg(jvm, &mut args)?;
+            proxy::return_value(jvm, &f(t))
+        })
+    }
+
+    /// Creates a direct `java.nio.ByteBuffer` that uses the `memory`, without copying it.
+    ///
+    /// The `memory` may be borrowed (`&mut [u8]`) or owned (eg. `Vec<u8>` or `Box<[u8]>`) by the returned `DirectByteBuffer`.
+    ///
+    /// # Safety
+    ///
+    /// Java cannot know when Rust frees the memory, so the caller must ensure that:
+    ///
+    /// * Java does not use the `ByteBuffer` after the `DirectByteBuffer` is dropped, or after its memory is returned by `into_inner`.
+    ///   This includes any reference to the `ByteBuffer` that Java keeps (eg. in a field), or that is kept by the `InvocationArg`s
+    ///   and the `Instance`s that are created from it, as well as the buffers that share its memory (eg. the result of `ByteBuffer.duplicate`).
+    /// * Java does not access the memory while a slice that is returned by `as_slice` or `as_mut_slice` is used
+    ///   (eg. from another Java thread).
+    pub unsafe fn direct_byte_buffer<B: DirectBufferMemory>(&self, memory: B) -> errors::Result<DirectByteBuffer<B>> {
+        DirectByteBuffer::new(self, memory)
+    }
+
+    /// Returns the memory of a direct `java.nio.ByteBuffer`, without copying it.
+    ///
+    /// An error is returned if the `Instance` does not contain a direct buffer.
+    ///
+    /// This is `unsafe`, rather than offering safe access, because the lifetime of the memory and the access to it cannot be tied to the `Instance`.
+    ///
+    /// # Safety
+    ///
+    /// Other `Instance`s may refer to the same `ByteBuffer` (eg. clones, or the `Instance`s that Java returns),
+    /// or to buffers that share its memory (eg. the result of `ByteBuffer.duplicate`). The caller must ensure that:
+    ///
+    /// * The memory stays valid while the slice is used. The `Instance` keeps a `ByteBuffer` that is allocated by Java alive,
+    ///   but the memory of a `DirectByteBuffer` lives only as long as the `DirectByteBuffer`.
+    /// * The memory is not modified while the slice is used, neither by Java nor by a slice that is returned by `direct_buffer_slice_mut`.
+    pub unsafe fn direct_buffer_slice<'a>(&self, byte_buffer: &'a Instance) -> errors::Result<&'a [u8]> {
+        let (address, capacity) = buffers::direct_buffer_memory(self, byte_buffer)?;
+        Ok(buffers::slice_from_memory(address, capacity))
+    }
+
+    /// Returns the memory of a direct `java.nio.ByteBuffer` mutably, without copying it.
+    ///
+    /// # Safety
+    ///
+    /// See `direct_buffer_slice`. In addition, the memory must not be accessed by any other slice, or by Java, while the returned slice is used.
+    pub unsafe fn direct_buffer_slice_mut<'a>(&self, byte_buffer: &'a mut Instance) -> errors::Result<&'a mut [u8]> {
+        let (address, capacity) = buffers::direct_buffer_memory(self, byte_buffer)?;
+        Ok(buffers::slice_from_memory(address, capacity))
+    }
+
     /// Invokes the static method `method_name` of the class `class_name`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
     pub fn invoke_static(&self, class_name: &str, method_name: &str, inv_args: &[InvocationArg]) -> errors::Result<Instance> {
         debug(&format!("Invoking static method {} of class {} using {} arguments", method_name, class_name, inv_args.len()));
@@ -734,6 +1132,9 @@ impl Jvm {
                 instance.jinstance,
             );
 
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+
             // Create and return the Instance
             Self::do_return(self.jni_env, Instance::from_jobject_with_global_ref(java_instance)?)
         }
@@ -767,6 +1168,21 @@ impl Jvm {
         }
     }
 
+    /// Returns true if the Java object that the `instance` contains is an instance of the class or interface with the provided name.
+    /// Like the Java `instanceof`, a null object is not an instance of any class.
+    pub fn is_instance_of(&self, instance: &Instance, class_name: &str) -> errors::Result<bool> {
+        jni_utils::is_instance_of_class_name(instance, class_name, self.jni_env)
+    }
+
+    /// Describes the Java class with the provided name, using the Java reflection. Primitive types (eg. `int`) are described as well.
+    ///
+    /// The description contains the constructors, methods and fields that the class declares, whatever their visibility,
+    /// along with its superclass, interfaces, annotations and modifiers.
+    pub fn describe_class(&self, class_name: &str) -> errors::Result<ClassDescription> {
+        debug(&format!("Describing the class {}", class_name));
+        reflection::describe_class(self, class_name)
+    }
+
     /// Returns the Rust representation of the provided instance, boxed
     pub fn to_rust_boxed<T>(&self, instance: Instance) -> errors::Result<Box<T>>
         where T: DeserializeOwned + Any {
@@ -796,6 +1212,23 @@ impl Jvm {
             };
         }
 
+        // Java primitive arrays are copied without serialization
+        if let Some(v) = arrays::vec_from_instance::<T>(&instance, self.jni_env)? {
+            return Ok(v);
+        }
+        // The unsigned and 128-bit integers are converted with overflow checks and the big numbers exactly
+        if let Some(v) = numbers::number_from_instance::<T>(&instance, self.jni_env)? {
+            return Ok(v);
+        }
+        // The dates and times are converted from the java.time classes
+        if let Some(v) = datetime::time_from_instance::<T>(&instance, self.jni_env)? {
+            return Ok(v);
+        }
+        // The Maps, Collections and Optionals are deserialized directly from the Java objects
+        if let Some(v) = serde_jni::collection_from_instance::<T>(&instance, self.jni_env)? {
+            return Ok(Box::new(v));
+        }
+
         unsafe {
             // Call the getObjectClassMethod. This returns a localref
             let object_class_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
@@ -833,6 +1266,13 @@ impl Jvm {
     }
 
     pub fn to_rust_deserialized<T>(&self, instance: Instance) -> errors::Result<T> where T: DeserializeOwned + Any {
+        if self.codec == Codec::Jni {
+            return serde_jni::from_instance(&instance, self.jni_env);
+        }
+        if self.codec != Codec::Json {
+            let payload = self.encode_instance(&instance)?;
+            return self.codec.decode(&payload);
+        }
         unsafe {
             debug("Invoking the getJson method");
             // Call the getJson method. This returns a localref
@@ -850,6 +1290,27 @@ impl Jvm {
         }
     }
 
+    // Encodes the object of the instance in Java, using the codec of this Jvm
+    fn encode_instance(&self, instance: &Instance) -> errors::Result<Vec<u8>> {
+        unsafe {
+            debug(&format!("Encoding an instance of {} to {:?}", instance.class_name(), self.codec));
+            let format_jstring = jni_utils::global_jobject_from_str(self.codec.java_format()?, self.jni_env)?;
+            // Call the encode method. This returns a localref
+            let payload = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+                self.jni_env,
+                cache::get_java2rust_utils_class()?,
+                cache::get_java2rust_utils_encode_method()?,
+                instance.jinstance,
+                format_jstring,
+            );
+            jni_utils::delete_java_ref(self.jni_env, format_jstring);
+            Self::do_return(self.jni_env, ())?;
+            let bytes = u8::from_java_array(payload, self.jni_env);
+            jni_utils::delete_java_local_ref(self.jni_env, payload);
+            bytes
+        }
+    }
+
     /// Deploys an artifact in the default j4rs jars location.
     ///
     /// This is useful for build scripts that need jars for the runtime that can be downloaded from e.g. Maven.
@@ -930,17 +1391,129 @@ impl Jvm {
     }
 
     /// Throws an exception in the Java World
+    ///
+    /// If an exception is already pending in the current thread, it is kept and this call has no effect.
     pub fn throw_invocation_exception(&self, message: &str) -> errors::Result<()> {
-        let _ = jni_utils::throw_exception(message, self.jni_env)?;
+        if !jni_utils::is_exception_pending(self.jni_env)? {
+            let _ = jni_utils::throw_exception(message, self.jni_env)?;
+        }
+        Ok(())
+    }
+
+    /// Registers the native methods of the class `class_name`, using the JNI `RegisterNatives`.
+    ///
+    /// The registered methods are the ones implemented by the Rust functions that are annotated with
+    /// `#[call_from_java("...", register_natives)]` for this class.
+    pub fn register_natives(&self, class_name: &str) -> errors::Result<()> {
+        let native_methods = natives::native_methods_of(class_name);
+        if native_methods.is_empty() {
+            return Err(errors::J4RsError::GeneralError(format!("No native methods found for class {}", class_name)));
+        }
+        let res = natives::register_natives(self.jni_env, class_name, &native_methods);
+        Self::do_return(self.jni_env, ())?;
+        res
+    }
+
+    /// Registers the native methods of all the classes that are implemented by Rust functions annotated with
+    /// `#[call_from_java("...", register_natives)]`, using the JNI `RegisterNatives`.
+    pub fn register_all_natives(&self) -> errors::Result<()> {
+        for (class_name, native_methods) in natives::native_methods_by_class() {
+            let res = natives::register_natives(self.jni_env, class_name, &native_methods);
+            Self::do_return(self.jni_env, ())?;
+            res?;
+        }
+        Ok(())
+    }
+
+    /// Throws a new exception of class `class_name` in the Java World, having the provided `message`.
+    ///
+    /// The class must be a `java.lang.Throwable` with a constructor that accepts a single String argument.
+    ///
+    /// If an exception is already pending in the current thread, it is kept and this call has no effect.
+    pub fn throw_exception(&self, class_name: &str, message: &str) -> errors::Result<()> {
+        if !jni_utils::is_exception_pending(self.jni_env)? {
+            let throwable_class = tweaks::find_class(self.jni_env, &class_name.replace(".", "/"))?;
+            // Check for a ClassNotFoundException/NoClassDefFoundError before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+            let throwable_class = jni_utils::create_global_ref_from_local_ref(throwable_class, self.jni_env)?;
+            if !jni_utils::is_assignable_to_throwable(throwable_class, self.jni_env)? {
+                jni_utils::delete_java_ref(self.jni_env, throwable_class);
+                return Err(errors::J4RsError::GeneralError(format!("Cannot throw {}: The class is not a java.lang.Throwable", class_name)));
+            }
+            let res = jni_utils::throw_exception_of_class(throwable_class, message, self.jni_env);
+            jni_utils::delete_java_ref(self.jni_env, throwable_class);
+            if res? != 0 {
+                Self::do_return(self.jni_env, ())?;
+                return Err(errors::J4RsError::JniError(format!("Could not throw an exception of class {}", class_name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Throws a new exception of class `class_name` in the Java World, having the provided `message` and `cause`.
+    ///
+    /// The class must be a `java.lang.Throwable` with a constructor that accepts a String and a Throwable.
+    ///
+    /// If an exception is already pending in the current thread, it is kept and this call has no effect.
+    pub fn throw_exception_with_cause(&self, class_name: &str, message: &str, cause: &Instance) -> errors::Result<()> {
+        if !jni_utils::is_exception_pending(self.jni_env)? {
+            let throwable = self.create_instance(
+                class_name,
+                &[InvocationArg::try_from(message)?, InvocationArg::from(self.clone_instance(cause)?)])?;
+            self.throw(&throwable)?;
+        }
+        Ok(())
+    }
+
+    /// Throws the provided `Instance` in the Java World. The `Instance` must be a `java.lang.Throwable`.
+    ///
+    /// This may be used to throw exceptions that are created with `Jvm::create_instance`,
+    /// or to rethrow the `Instance` of a `JavaThrowable` that was caught while calling Java.
+    ///
+    /// If an exception is already pending in the current thread, it is kept and this call has no effect.
+    pub fn throw(&self, throwable: &Instance) -> errors::Result<()> {
+        if !jni_utils::is_exception_pending(self.jni_env)? {
+            unsafe {
+                // Call the getObject method. This returns a localref
+                let throwable_object = (opt_to_res(cache::get_jni_call_object_method())?)(
+                    self.jni_env,
+                    throwable.jinstance,
+                    cache::get_get_object_method()?,
+                );
+                Self::do_return(self.jni_env, ())?;
+                let throwable_object = jni_utils::create_global_ref_from_local_ref(throwable_object, self.jni_env)?;
+                let res = if jni_utils::is_instance_of_throwable(throwable_object, self.jni_env)? {
+                    jni_utils::throw_throwable(throwable_object, self.jni_env)
+                } else {
+                    Err(errors::J4RsError::GeneralError("Cannot throw an Instance that is not a java.lang.Throwable".to_string()))
+                };
+                jni_utils::delete_java_ref(self.jni_env, throwable_object);
+                if res? != 0 {
+                    return Err(errors::J4RsError::JniError("Could not throw the Throwable Instance".to_string()));
+                }
+            }
+        }
         Ok(())
     }
 
     pub(crate) fn do_return<T>(jni_env: *mut JNIEnv, to_return: T) -> errors::Result<T> {
         unsafe {
             if (opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE {
-                (opt_to_res(cache::get_jni_exception_describe())?)(jni_env);
+                // Retrieve the Throwable before clearing the exception
+                let throwable = (opt_to_res(cache::get_jni_exception_occurred())?)(jni_env);
                 (opt_to_res(cache::get_jni_exception_clear())?)(jni_env);
-                Err(errors::J4RsError::JavaError("An Exception was thrown by Java... Please check the logs or the console.".to_string()))
+                let error = match jni_utils::describe_throwable(throwable, jni_env) {
+                    Ok(java_throwable) => {
+                        debug(&format!("An Exception was thrown by Java: {}", java_throwable));
+                        errors::J4RsError::JavaException(java_throwable)
+                    }
+                    Err(error) => {
+                        warn(&format!("Could not describe the Exception that was thrown by Java: {}", error));
+                        errors::J4RsError::JavaError("An Exception was thrown by Java... Please check the logs or the console.".to_string())
+                    }
+                };
+                jni_utils::delete_java_local_ref(jni_env, throwable);
+                Err(error)
             } else {
                 Ok(to_return)
             }
@@ -1076,6 +1649,8 @@ pub struct JvmBuilder<'a> {
     base_path: Option<String>,
     maven_settings: MavenSettings,
     javafx: bool,
+    codec: Codec,
+    force_accessible: bool,
 }
 
 impl<'a> JvmBuilder<'a> {
@@ -1091,6 +1666,8 @@ impl<'a> JvmBuilder<'a> {
             base_path: None,
             maven_settings: MavenSettings::default(),
             javafx: false,
+            codec: Codec::default(),
+            force_accessible: false,
         }
     }
 
@@ -1174,6 +1751,23 @@ impl<'a> JvmBuilder<'a> {
         self
     }
 
+    /// Defines the `Codec` that the created Jvm uses for serializing the values that are transferred between Rust and Java.
+    ///
+    /// The default is `Codec::Json`.
+    pub fn with_codec(&'a mut self, codec: Codec) -> &'a mut JvmBuilder {
+        self.codec = codec;
+        self
+    }
+
+    /// The created Jvm forces the accessibility of the fields that it reads and writes, so that private fields,
+    /// fields that are declared by superclasses and final instance fields are accessible.
+    ///
+    /// See `Jvm::set_force_accessible`.
+    pub fn with_force_accessible_fields(&'a mut self) -> &'a mut JvmBuilder {
+        self.force_accessible = true;
+        self
+    }
+
     /// Creates a Jvm
     pub fn build(&mut self) -> errors::Result<Jvm> {
         let classpath = if self.no_implicit_classpath {
@@ -1272,6 +1866,8 @@ impl<'a> JvmBuilder<'a> {
                 if !self.detach_thread_on_drop {
                     jvm.detach_thread_on_drop(false);
                 }
+                jvm.set_codec(self.codec);
+                jvm.set_force_accessible(self.force_accessible);
                 Ok(jvm)
             })
     }
@@ -1312,9 +1908,16 @@ pub enum InvocationArg {
         class_name: String,
         serialized: bool,
     },
+    /// A serialized arg that is created in the Rust world, using a binary `Codec`.
+    RustEncoded {
+        payload: Vec<u8>,
+        codec: Codec,
+        class_name: String,
+        serialized: bool,
+    },
     /// An non-serialized arg created in the Rust world, that contains a Java instance.
     ///
-    /// The instance is a Basic Java type, like Integer, Float, String etc.
+    /// The instance is a Basic Java type, like Integer, Float, String etc., or an object that was created using the `Codec::Jni`.
     RustBasic {
         instance: Instance,
         class_name: String,
@@ -1337,6 +1940,21 @@ impl InvocationArg {
 
     pub fn new_2<T>(arg: &T, class_name: &str, jni_env: *mut JNIEnv) -> errors::Result<InvocationArg>
         where T: Serialize + Any
+    {
+        Self::new_with_codec_and_env(arg, class_name, Codec::Json, jni_env)
+    }
+
+    /// Creates an InvocationArg, serializing the `arg` with the provided `codec`.
+    ///
+    /// Like `InvocationArg::new`, the basic types (eg. String, i32 etc.) are not serialized.
+    pub fn new_with_codec<T>(arg: &T, class_name: &str, codec: Codec) -> errors::Result<InvocationArg>
+        where T: Serialize + Any
+    {
+        Self::new_with_codec_and_env(arg, class_name, codec, cache::get_thread_local_env()?)
+    }
+
+    fn new_with_codec_and_env<T>(arg: &T, class_name: &str, codec: Codec, jni_env: *mut JNIEnv) -> errors::Result<InvocationArg>
+        where T: Serialize + Any
     {
         let arg_any = arg as &dyn Any;
         if let Some(a) = arg_any.downcast_ref::<String>() {
@@ -1381,13 +1999,27 @@ impl InvocationArg {
                 class_name: class_name.to_string(),
                 serialized: false,
             })
-        } else {
+        } else if codec == Codec::Jni {
+            let object = jni_utils::create_global_ref_from_local_ref(serde_jni::to_java(arg, class_name, jni_env)?, jni_env)?;
+            Ok(InvocationArg::RustBasic {
+                instance: Instance::new(object, class_name)?,
+                class_name: class_name.to_string(),
+                serialized: false,
+            })
+        } else if codec == Codec::Json {
             let json = serde_json::to_string(arg)?;
             Ok(InvocationArg::Rust {
                 json: json,
                 class_name: class_name.to_string(),
                 serialized: true,
             })
+        } else {
+            Ok(InvocationArg::RustEncoded {
+                payload: codec.encode(arg)?,
+                codec,
+                class_name: class_name.to_string(),
+                serialized: true,
+            })
         }
     }
 
@@ -1397,6 +2029,7 @@ impl InvocationArg {
                 match self {
                     &mut InvocationArg::Java { instance: _, ref mut class_name, serialized: _ } => *class_name = primitive_repr,
                     &mut InvocationArg::Rust { json: _, ref mut class_name, serialized: _ } => *class_name = primitive_repr,
+                    &mut InvocationArg::RustEncoded { ref mut class_name, .. } => *class_name = primitive_repr,
                     &mut InvocationArg::RustBasic { instance: _, ref mut class_name, serialized: _ } => *class_name = primitive_repr,
                 };
                 Ok(())
@@ -1420,6 +2053,7 @@ impl InvocationArg {
         match self {
             _s @ &InvocationArg::Java { .. } => jni_utils::invocation_arg_jobject_from_java(&self, jni_env, true),
             _s @ &InvocationArg::Rust { .. } => jni_utils::invocation_arg_jobject_from_rust_serialized(&self, jni_env, true),
+            _s @ &InvocationArg::RustEncoded { .. } => jni_utils::invocation_arg_jobject_from_rust_encoded(&self, jni_env, true),
             _s @ &InvocationArg::RustBasic { .. } => jni_utils::invocation_arg_jobject_from_rust_basic(&self, jni_env, true),
         }
     }
@@ -1429,6 +2063,7 @@ impl InvocationArg {
         match self {
             _s @ &InvocationArg::Java { .. } => jni_utils::invocation_arg_jobject_from_java(&self, jni_env, false),
             _s @ &InvocationArg::Rust { .. } => jni_utils::invocation_arg_jobject_from_rust_serialized(&self, jni_env, false),
+            _s @ &InvocationArg::RustEncoded { .. } => jni_utils::invocation_arg_jobject_from_rust_encoded(&self, jni_env, false),
             _s @ &InvocationArg::RustBasic { .. } => jni_utils::invocation_arg_jobject_from_rust_basic(&self, jni_env, false),
         }
     }
@@ -1439,6 +2074,7 @@ impl InvocationArg {
             InvocationArg::Java { instance: i, .. } => Ok(i),
             InvocationArg::RustBasic { .. } => Err(errors::J4RsError::RustError(format!("Invalid operation: Cannot get the instance of an InvocationArg::RustBasic"))),
             InvocationArg::Rust { .. } => Err(errors::J4RsError::RustError(format!("Cannot get the instance from an InvocationArg::Rust"))),
+            InvocationArg::RustEncoded { .. } => Err(errors::J4RsError::RustError(format!("Cannot get the instance from an InvocationArg::RustEncoded"))),
         }
     }
 
@@ -1446,10 +2082,56 @@ impl InvocationArg {
         match self {
             &InvocationArg::Java { instance: _, ref class_name, serialized: _ } => class_name,
             &InvocationArg::Rust { json: _, ref class_name, serialized: _ } => class_name,
+            &InvocationArg::RustEncoded { ref class_name, .. } => class_name,
             &InvocationArg::RustBasic { instance: _, ref class_name, serialized: _ } => class_name,
         }
     }
 
+    /// Creates an InvocationArg that contains a Java primitive array with the `values` (eg. an `int[]` for `&[i32]`).
+    ///
+    /// The values are copied to the Java array in bulk, without serialization. See `JavaPrimitive`.
+    ///
+    /// Note that `InvocationArg::try_from(&[i32])` creates an array of boxed values (eg. an `Integer[]`) instead.
+    pub fn from_primitive_array<T: JavaPrimitive>(values: &[T]) -> errors::Result<InvocationArg> {
+        let jni_env = cache::get_thread_local_env()?;
+        let array = unsafe { T::new_java_array(values, jni_env)? };
+        let array = jni_utils::create_global_ref_from_local_ref(array, jni_env)?;
+        Ok(InvocationArg::RustBasic {
+            instance: Instance::new(array, T::ARRAY_CLASS_NAME)?,
+            class_name: T::ARRAY_CLASS_NAME.to_string(),
+            serialized: false,
+        })
+    }
+
+    // Creates an InvocationArg that contains a Java object that is created as a local reference
+    fn from_local_ref(create: impl FnOnce(*mut JNIEnv) -> errors::Result<jobject>, class_name: &str) -> errors::Result<InvocationArg> {
+        let jni_env = cache::get_thread_local_env()?;
+        let object = jni_utils::create_global_ref_from_local_ref(create(jni_env)?, jni_env)?;
+        Ok(InvocationArg::RustBasic {
+            instance: Instance::new(object, class_name)?,
+            class_name: class_name.to_string(),
+            serialized: false,
+        })
+    }
+
+    /// Creates an InvocationArg that contains the Java constant of a `JavaEnum`.
+    pub fn from_java_enum<E: JavaEnum>(value: &E) -> errors::Result<InvocationArg> {
+        let res = Jvm::do_create_java_enum(cache::get_thread_local_env()?, E::JAVA_CLASS, value.java_name());
+        Ok(InvocationArg::from(res?))
+    }
+
+    /// Creates an InvocationArg that contains a new Java object of a `JavaObject`, with its fields set.
+    pub fn from_java_object<O: JavaObject>(value: &O) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| serde_jni::object_to_java(value, jni_env), O::JAVA_CLASS)
+    }
+
+    /// Creates an InvocationArg that contains a `java.util.Date`, for the APIs that do not use the `java.time` classes.
+    ///
+    /// The Date is truncated to milliseconds. Use `InvocationArg::try_from` to create a `java.time.Instant` instead.
+    pub fn from_system_time_as_date(time: &SystemTime) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_date(time, jni_env), CLASS_DATE)
+    }
+
     /// Creates an InvocationArg that contains null
     pub fn create_null(null: Null) -> errors::Result<InvocationArg> {
         let class_name = match null {
@@ -1670,6 +2352,257 @@ impl<'a> TryFrom<&'a [f64]> for InvocationArg {
     }
 }
 
+impl TryFrom<u8> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u8) -> errors::Result<InvocationArg> {
+        InvocationArg::new_2(&(arg as i16), CLASS_SHORT, cache::get_thread_local_env()?)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [u8]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<u16> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u16) -> errors::Result<InvocationArg> {
+        InvocationArg::new_2(&(arg as i32), CLASS_INTEGER, cache::get_thread_local_env()?)
+    }
+}
+
+impl<'a> TryFrom<&'a [u16]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [u16]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<u32> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u32) -> errors::Result<InvocationArg> {
+        InvocationArg::new_2(&(arg as i64), CLASS_LONG, cache::get_thread_local_env()?)
+    }
+}
+
+impl<'a> TryFrom<&'a [u32]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [u32]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<u64> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u64) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(BigInteger::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a [u64]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [u64]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<usize> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: usize) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(BigInteger::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a [usize]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [usize]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<i128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: i128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(BigInteger::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a [i128]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [i128]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl TryFrom<u128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(BigInteger::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a [u128]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(vec: &'a [u128]) -> errors::Result<InvocationArg> {
+        let args: errors::Result<Vec<InvocationArg>> = vec.iter().map(|&elem| InvocationArg::try_from(elem)).collect();
+        let res = Jvm::do_create_java_list(cache::get_thread_local_env()?, cache::J4RS_ARRAY, &args?);
+        Ok(InvocationArg::from(res?))
+    }
+}
+
+impl<'a> TryFrom<&'a BigInteger> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a BigInteger) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| numbers::new_big_integer(arg, jni_env), CLASS_BIG_INTEGER)
+    }
+}
+
+impl TryFrom<BigInteger> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: BigInteger) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+impl<'a> TryFrom<&'a BigDecimal> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a BigDecimal) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| numbers::new_big_decimal(arg, jni_env), CLASS_BIG_DECIMAL)
+    }
+}
+
+impl TryFrom<BigDecimal> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: BigDecimal) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+impl<'a> TryFrom<&'a Duration> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a Duration) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_duration(arg, jni_env), CLASS_DURATION)
+    }
+}
+
+impl TryFrom<Duration> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: Duration) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+impl<'a> TryFrom<&'a SystemTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a SystemTime) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_instant(arg, jni_env), CLASS_INSTANT)
+    }
+}
+
+impl TryFrom<SystemTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: SystemTime) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<&'a NaiveDate> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a NaiveDate) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_local_date(arg, jni_env), CLASS_LOCAL_DATE)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<NaiveDate> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: NaiveDate) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> TryFrom<&'a NaiveDateTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a NaiveDateTime) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_local_date_time(arg, jni_env), CLASS_LOCAL_DATE_TIME)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<NaiveDateTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: NaiveDateTime) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, Tz: TimeZone> TryFrom<&'a DateTime<Tz>> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a DateTime<Tz>) -> errors::Result<InvocationArg> {
+        InvocationArg::from_local_ref(|jni_env| datetime::new_zoned_date_time(arg, jni_env), CLASS_ZONED_DATE_TIME)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: DateTime<Tz>) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+impl<'a, K, V, S> TryFrom<&'a HashMap<K, V, S>> for InvocationArg where K: Serialize + Any, V: Serialize + Any, S: 'static {
+    type Error = errors::J4RsError;
+    fn try_from(map: &'a HashMap<K, V, S>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_with_codec(map, CLASS_MAP, Codec::Jni)
+    }
+}
+
+impl<'a, K, V> TryFrom<&'a BTreeMap<K, V>> for InvocationArg where K: Serialize + Any, V: Serialize + Any {
+    type Error = errors::J4RsError;
+    fn try_from(map: &'a BTreeMap<K, V>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_with_codec(map, CLASS_MAP, Codec::Jni)
+    }
+}
+
+impl<'a, T, S> TryFrom<&'a HashSet<T, S>> for InvocationArg where T: Serialize + Any, S: 'static {
+    type Error = errors::J4RsError;
+    fn try_from(set: &'a HashSet<T, S>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_with_codec(set, CLASS_SET, Codec::Jni)
+    }
+}
+
+impl<'a, T> TryFrom<&'a BTreeSet<T>> for InvocationArg where T: Serialize + Any {
+    type Error = errors::J4RsError;
+    fn try_from(set: &'a BTreeSet<T>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_with_codec(set, CLASS_SET, Codec::Jni)
+    }
+}
+
+impl<T> TryFrom<Option<T>> for InvocationArg where T: Serialize + Any {
+    type Error = errors::J4RsError;
+    fn try_from(opt: Option<T>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_with_codec(&opt, CLASS_OPTIONAL, Codec::Jni)
+    }
+}
+
 impl<'a, T: 'static> TryFrom<(&'a [T], &'a str)> for InvocationArg where T: Serialize {
     type Error = errors::J4RsError;
     fn try_from(vec: (&'a [T], &'a str)) -> errors::Result<InvocationArg> {
@@ -1751,6 +2684,55 @@ impl<'a> TryFrom<&'a f64> for InvocationArg {
     }
 }
 
+impl<'a> TryFrom<&'a u8> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u8) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a u16> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u16) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a u32> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u32) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a u64> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u64) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a usize> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a usize) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a i128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a i128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl<'a> TryFrom<&'a u128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
 /// A receiver for Java Instances.
 ///
 /// It keeps a channel Receiver to get callback Instances from the Java world
@@ -1819,6 +2801,19 @@ impl Instance {
         self.class_name.as_ref()
     }
 
+    /// Returns the name of the runtime class of the Java object that this instance contains.
+    /// For example, an `Instance` that is returned by a method that declares a `java.util.List` may contain a `java.util.ArrayList`.
+    ///
+    /// If the `class_name` of this instance is not known to Rust, it is populated with the runtime class name.
+    /// A null object has the class that the Java world knows for it, if any.
+    pub fn runtime_class_name(&mut self) -> errors::Result<String> {
+        let runtime_class_name = jni_utils::runtime_class_name(self, cache::get_thread_local_env()?)?;
+        if self.class_name == cache::UNKNOWN_FOR_RUST {
+            self.class_name = runtime_class_name.clone();
+        }
+        Ok(runtime_class_name)
+    }
+
     /// Consumes the Instance and returns its jobject
     pub fn java_object(mut self) -> jobject {
         self.skip_deleting_jobject = true;
@@ -1877,7 +2872,7 @@ impl Instance {
 impl TryFrom<InvocationArg> for Instance {
     type Error = errors::J4RsError;
     fn try_from(invocation_arg: InvocationArg) -> errors::Result<Instance> {
-        let obj = invocation_arg.as_java_ptr_with_local_ref(cache::get_thread_local_env()?)?;
+        let obj = invocation_arg.as_java_ptr_with_global_ref(cache::get_thread_local_env()?)?;
         Instance::new(obj, invocation_arg.class_name())
     }
 }
@@ -1939,6 +2934,27 @@ impl<'a> ChainableInstance<'a> {
         Ok(ChainableInstance::new(instance, self.jvm))
     }
 
+    /// Sets the field `field_name` of the `Instance` to the provided `value`. The chain continues with the same `Instance`.
+    pub fn set_field(&self, field_name: &str, value: InvocationArg) -> errors::Result<ChainableInstance> {
+        self.jvm.set_field(&self.instance, field_name, value)?;
+        self.clone_instance()
+    }
+
+    /// Retrieves the static field `field_name` of the runtime class of the `Instance`.
+    pub fn static_field(&self, field_name: &str) -> errors::Result<ChainableInstance> {
+        let class_name = jni_utils::runtime_class_name(&self.instance, self.jvm.jni_env)?;
+        let instance = self.jvm.static_field(&class_name, field_name)?;
+        Ok(ChainableInstance::new(instance, self.jvm))
+    }
+
+    /// Sets the static field `field_name` of the runtime class of the `Instance` to the provided `value`.
+    /// The chain continues with the same `Instance`.
+    pub fn set_static_field(&self, field_name: &str, value: InvocationArg) -> errors::Result<ChainableInstance> {
+        let class_name = jni_utils::runtime_class_name(&self.instance, self.jvm.jni_env)?;
+        self.jvm.set_static_field(&class_name, field_name, value)?;
+        self.clone_instance()
+    }
+
     /// Returns the Rust representation of the provided instance
     pub fn to_rust<T: Any>(self) -> errors::Result<T> where T: DeserializeOwned {
         self.jvm.to_rust(self.instance)
@@ -2117,6 +3133,9 @@ mod api_unit_tests {
             InvocationArg::Rust { class_name, json: _, .. } => {
                 class == class_name
             }
+            InvocationArg::RustEncoded { class_name, .. } => {
+                class == class_name
+            }
             InvocationArg::RustBasic { instance: _, class_name, serialized: _ } => {
                 class == class_name
             }
@@ -11,14 +11,18 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::HashMap;
 use std::os::raw::c_void;
 use std::path::MAIN_SEPARATOR;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 use java_locator::{get_jvm_dyn_lib_file_name, locate_jvm_dyn_library};
 use jni_sys::{
     JavaVM,
     jclass,
     jint,
+    JNI_OK,
     JNIEnv,
     jsize,
 };
@@ -26,6 +30,8 @@ use libloading;
 
 use crate::{utils, errors};
 use crate::errors::opt_to_res;
+use crate::jni_utils::create_global_ref_from_local_ref;
+use crate::logger::error;
 
 type JNIGetCreatedJavaVMs = unsafe extern "system" fn(vmBuf: *mut *mut JavaVM, bufLen: jsize, nVMs: *mut jsize) -> jint;
 
@@ -52,14 +58,47 @@ lazy_static! {
     static ref CREATE_JVM: libloading::Symbol<'static, JNICreateJavaVM> = unsafe {
         JVM_LIB.get(b"JNI_CreateJavaVM").expect("Could not find symbol: JNI_CreateJavaVM")
     };
+
+    // The JavaVM that loaded the library, if it is set. This is not necessarily the one of the located jvm dynamic library.
+    static ref JAVA_VM: Mutex<Option<J4rsJavaVM>> = Mutex::new(None);
+    // Classes that were found while class caching was enabled.
+    // Caching the classes avoids classloading issues when using them from threads that are created in Rust.
+    static ref CLASSES: Mutex<HashMap<String, J4rsJclass>> = Mutex::new(HashMap::new());
 }
 
+static CLASS_CACHING_ENABLED: AtomicBool = AtomicBool::new(false);
+
 pub(crate) fn get_created_java_vms(vm_buf: &mut Vec<*mut JavaVM>, buf_len: jsize, n_vms: *mut jsize) -> jint {
     unsafe {
-        GET_CREATED_JVMS(vm_buf.as_mut_ptr(), buf_len, n_vms)
+        match JAVA_VM.lock() {
+            Ok(g) if g.is_some() => {
+                *n_vms = 1;
+                *vm_buf = vec![g.as_ref().unwrap().java_vm];
+                JNI_OK
+            }
+            Ok(_) => GET_CREATED_JVMS(vm_buf.as_mut_ptr(), buf_len, n_vms),
+            Err(poison_error) => {
+                error(&format!("Could not get the lock for J4rsJavaVM: {:?}", poison_error));
+                GET_CREATED_JVMS(vm_buf.as_mut_ptr(), buf_len, n_vms)
+            }
+        }
     }
 }
 
+pub(crate) fn set_java_vm(java_vm: *mut JavaVM) {
+    let mut g = JAVA_VM.lock().unwrap();
+    *g = Some(J4rsJavaVM { java_vm });
+}
+
+pub(crate) fn set_class_caching(enabled: bool) {
+    CLASS_CACHING_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+pub(crate) fn class_caching() -> bool {
+    CLASS_CACHING_ENABLED.load(Ordering::SeqCst)
+}
+
 pub(crate) fn create_java_vm(
     jvm: *mut *mut JavaVM,
     penv: *mut *mut c_void,
@@ -70,8 +109,12 @@ pub(crate) fn create_java_vm(
     }
 }
 
+// Search the class in the cache first. If not found, then call the FindClass of JNI and, if class caching is enabled, insert the result to the cache.
 pub(crate) fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jclass> {
-    unsafe {
+    if let Some(j4rs_class) = CLASSES.lock()?.get(classname) {
+        return Ok(j4rs_class.class);
+    }
+    let found = unsafe {
         let cstr = utils::to_c_string(classname);
         let fc = opt_to_res((**env).FindClass)?;
         let jc = (fc)(
@@ -79,6 +122,31 @@ pub(crate) fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jc
             cstr,
         );
         utils::drop_c_string(cstr);
-        Ok(jc)
+        jc
+    };
+    if CLASS_CACHING_ENABLED.load(Ordering::SeqCst) && !found.is_null() {
+        let global = create_global_ref_from_local_ref(found, env)?;
+        CLASSES.lock()?.insert(classname.to_string(), J4rsJclass { class: global });
+        Ok(global as jclass)
+    } else {
+        Ok(found)
     }
-}
\ No newline at end of file
+}
+
+pub(crate) struct J4rsJavaVM {
+    java_vm: *mut JavaVM,
+}
+
+// The JavaVM pointer is valid for all the threads
+unsafe impl Send for J4rsJavaVM {}
+
+unsafe impl Sync for J4rsJavaVM {}
+
+pub(crate) struct J4rsJclass {
+    class: jclass,
+}
+
+// The cached classes are global references, which are valid for all the threads
+unsafe impl Send for J4rsJclass {}
+
+unsafe impl Sync for J4rsJclass {}
@@ -25,7 +25,9 @@ pub fn get_created_java_vms(vm_buf: &mut Vec<*mut JavaVM>, buf_len: jsize, n_vms
 }
 
 #[cfg(not(any(target_os = "android")))]
-pub fn set_java_vm(_: *mut JavaVM) {}
+pub fn set_java_vm(java_vm: *mut JavaVM) {
+    generic::set_java_vm(java_vm);
+}
 
 #[cfg(not(any(target_os = "android")))]
 pub fn create_java_vm(
@@ -39,6 +41,16 @@ pub fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jclass> {
     generic::find_class(env, classname)
 }
 
+#[cfg(not(any(target_os = "android")))]
+pub(crate) fn set_class_caching(enabled: bool) {
+    generic::set_class_caching(enabled);
+}
+
+#[cfg(all(test, not(any(target_os = "android"))))]
+pub(crate) fn class_caching() -> bool {
+    generic::class_caching()
+}
+
 // ++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ //
 
 #[cfg(target_os = "android")]
@@ -65,3 +77,12 @@ pub fn create_java_vm(
 pub fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jclass> {
     android::find_class(env, classname)
 }
+
+// Android caches all the classes, so this is a no-op
+#[cfg(target_os = "android")]
+pub(crate) fn set_class_caching(_: bool) {}
+
+#[cfg(all(test, target_os = "android"))]
+pub(crate) fn class_caching() -> bool {
+    true
+}
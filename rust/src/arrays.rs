@@ -0,0 +1,158 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::{Any, TypeId};
+
+use jni_sys::{jarray, jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort, jsize, JNI_TRUE, JNIEnv};
+
+use crate::{cache, errors, Instance, jni_utils, Jvm};
+use crate::errors::opt_to_res;
+
+/// A Rust primitive that is transferred to and from Java primitive arrays in bulk,
+/// using the JNI `New<Type>Array`, `Set<Type>ArrayRegion` and `Get<Type>ArrayRegion` functions.
+///
+/// | Rust        | Java        |
+/// |-------------|-------------|
+/// | `bool`      | `boolean[]` |
+/// | `i8`, `u8`  | `byte[]`    |
+/// | `u16`       | `char[]`    |
+/// | `i16`       | `short[]`   |
+/// | `i32`       | `int[]`     |
+/// | `i64`       | `long[]`    |
+/// | `f32`       | `float[]`   |
+/// | `f64`       | `double[]`  |
+pub trait JavaPrimitive: Copy + Any {
+    /// The JNI name of the class of the Java array (eg. `[I` for `int[]`)
+    const ARRAY_CLASS_NAME: &'static str;
+
+    /// Creates a new Java array that contains the `values`. The array is a local reference.
+    ///
+    /// # Safety
+    ///
+    /// The `jni_env` must be the valid JNIEnv of the current thread.
+    unsafe fn new_java_array(values: &[Self], jni_env: *mut JNIEnv) -> errors::Result<jarray>;
+
+    /// Copies the contents of the Java `array` to a Vec.
+    ///
+    /// # Safety
+    ///
+    /// The `jni_env` must be the valid JNIEnv of the current thread and the `array` must be a Java array of the respective type.
+    unsafe fn from_java_array(array: jarray, jni_env: *mut JNIEnv) -> errors::Result<Vec<Self>>;
+}
+
+// Implements JavaPrimitive for Rust types that have the same memory representation with their JNI counterparts
+macro_rules! java_primitive {
+    ($rust_type:ty, $jni_type:ty, $array_class_name:expr, $new_array:ident, $get_region:ident, $set_region:ident) => {
+        impl JavaPrimitive for $rust_type {
+            const ARRAY_CLASS_NAME: &'static str = $array_class_name;
+
+            unsafe fn new_java_array(values: &[$rust_type], jni_env: *mut JNIEnv) -> errors::Result<jarray> {
+                let array = (opt_to_res((**jni_env).$new_array)?)(jni_env, values.len() as jsize);
+                Jvm::do_return(jni_env, ())?;
+                (opt_to_res((**jni_env).$set_region)?)(
+                    jni_env,
+                    array,
+                    0,
+                    values.len() as jsize,
+                    values.as_ptr() as *const $jni_type,
+                );
+                Jvm::do_return(jni_env, array)
+            }
+
+            unsafe fn from_java_array(array: jarray, jni_env: *mut JNIEnv) -> errors::Result<Vec<$rust_type>> {
+                let length = (opt_to_res((**jni_env).GetArrayLength)?)(jni_env, array);
+                let mut values: Vec<$rust_type> = Vec::with_capacity(length as usize);
+                (opt_to_res((**jni_env).$get_region)?)(
+                    jni_env,
+                    array,
+                    0,
+                    length,
+                    values.as_mut_ptr() as *mut $jni_type,
+                );
+                Jvm::do_return(jni_env, ())?;
+                values.set_len(length as usize);
+                Ok(values)
+            }
+        }
+    };
+}
+
+java_primitive!(i8, jbyte, "[B", NewByteArray, GetByteArrayRegion, SetByteArrayRegion);
+java_primitive!(u8, jbyte, "[B", NewByteArray, GetByteArrayRegion, SetByteArrayRegion);
+java_primitive!(u16, jchar, "[C", NewCharArray, GetCharArrayRegion, SetCharArrayRegion);
+java_primitive!(i16, jshort, "[S", NewShortArray, GetShortArrayRegion, SetShortArrayRegion);
+java_primitive!(i32, jint, "[I", NewIntArray, GetIntArrayRegion, SetIntArrayRegion);
+java_primitive!(i64, jlong, "[J", NewLongArray, GetLongArrayRegion, SetLongArrayRegion);
+java_primitive!(f32, jfloat, "[F", NewFloatArray, GetFloatArrayRegion, SetFloatArrayRegion);
+java_primitive!(f64, jdouble, "[D", NewDoubleArray, GetDoubleArrayRegion, SetDoubleArrayRegion);
+
+// A jboolean may contain any non zero value for true, so booleans are converted one by one
+impl JavaPrimitive for bool {
+    const ARRAY_CLASS_NAME: &'static str = "[Z";
+
+    unsafe fn new_java_array(values: &[bool], jni_env: *mut JNIEnv) -> errors::Result<jarray> {
+        let jbooleans: Vec<jboolean> = values.iter().map(|b| *b as jboolean).collect();
+        let array = (opt_to_res((**jni_env).NewBooleanArray)?)(jni_env, values.len() as jsize);
+        Jvm::do_return(jni_env, ())?;
+        (opt_to_res((**jni_env).SetBooleanArrayRegion)?)(jni_env, array, 0, values.len() as jsize, jbooleans.as_ptr());
+        Jvm::do_return(jni_env, array)
+    }
+
+    unsafe fn from_java_array(array: jarray, jni_env: *mut JNIEnv) -> errors::Result<Vec<bool>> {
+        let length = (opt_to_res((**jni_env).GetArrayLength)?)(jni_env, array);
+        let mut jbooleans: Vec<jboolean> = vec![0; length as usize];
+        (opt_to_res((**jni_env).GetBooleanArrayRegion)?)(jni_env, array, 0, length, jbooleans.as_mut_ptr());
+        Jvm::do_return(jni_env, ())?;
+        Ok(jbooleans.into_iter().map(|b| b != 0).collect())
+    }
+}
+
+/// If `T` is a `Vec` of a `JavaPrimitive` and the `instance` contains a Java array of the respective type,
+/// the contents of the array are copied to a `Vec` that is returned. Otherwise, `None` is returned.
+pub(crate) fn vec_from_instance<T: Any>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<Option<Box<T>>> {
+    macro_rules! try_vec_of {
+        ($($rust_type:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<Vec<$rust_type>>() {
+                    let v = vec_of::<$rust_type>(instance, jni_env)?;
+                    return Ok(v.and_then(|v| (Box::new(v) as Box<dyn Any>).downcast::<T>().ok()));
+                }
+            )*
+        };
+    }
+
+    try_vec_of!(bool, i8, u8, u16, i16, i32, i64, f32, f64);
+    Ok(None)
+}
+
+fn vec_of<P: JavaPrimitive>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<Option<Vec<P>>> {
+    unsafe {
+        // Call the getObjectMethod. This returns a localref
+        let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        Jvm::do_return(jni_env, ())?;
+        let is_array = !object.is_null()
+            && (opt_to_res(cache::get_is_instance_of())?)(jni_env, object, cache::get_class(P::ARRAY_CLASS_NAME)?) == JNI_TRUE;
+        let v = if is_array {
+            P::from_java_array(object, jni_env).map(Some)
+        } else {
+            Ok(None)
+        };
+        jni_utils::delete_java_local_ref(jni_env, object);
+        v
+    }
+}
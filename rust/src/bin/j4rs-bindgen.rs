@@ -0,0 +1,67 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates typed Rust wrappers of the classes of a jar.
+//!
+//! Usage: `j4rs-bindgen <jar> [--class <class name>]... [--output <file>]`
+use std::env;
+use std::path::Path;
+use std::process;
+
+use j4rs::{BindingsGenerator, ClasspathEntry, JvmBuilder};
+
+const USAGE: &str = "Usage: j4rs-bindgen <jar> [--class <class name>]... [--output <file>]";
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut jar = None;
+    let mut class_names = Vec::new();
+    let mut output = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-c" | "--class" => class_names.push(args.next().ok_or(USAGE)?),
+            "-o" | "--output" => output = Some(args.next().ok_or(USAGE)?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if jar.is_none() && !arg.starts_with('-') => jar = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let jar = jar.ok_or(USAGE)?;
+
+    // A jar that is not in the jassets directory needs to be added to the classpath
+    let jvm = if Path::new(&jar).is_file() {
+        JvmBuilder::new().classpath_entry(ClasspathEntry::new(&jar)).build()
+    } else {
+        JvmBuilder::new().build()
+    }.map_err(|error| error.to_string())?;
+
+    let mut generator = BindingsGenerator::new(&jar);
+    for class_name in &class_names {
+        generator.class(class_name);
+    }
+    match output {
+        Some(path) => generator.write_to_file(&jvm, path),
+        None => generator.generate(&jvm).map(|source| print!("{}", source)),
+    }.map_err(|error| error.to_string())
+}
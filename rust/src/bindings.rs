@@ -0,0 +1,480 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates typed Rust wrappers of Java classes.
+//!
+//! Each wrapper is a struct that contains an `Instance` of the class. The public constructors, methods and fields that the class declares
+//! become Rust functions that call `Jvm::create_instance`, `Jvm::invoke`, `Jvm::invoke_static`, `Jvm::field` and `Jvm::static_field`:
+//!
+//! | Java                                      | Rust arguments         | Rust return values                   |
+//! |-------------------------------------------|------------------------|--------------------------------------|
+//! | primitives                                | `i32`, `bool` etc.     | `i32`, `bool` etc.                   |
+//! | boxed primitives (eg. `Integer`)          | `Option<i32>` etc.     | `Option<i32>` etc.                   |
+//! | `String`                                  | `&str`                 | `String`                             |
+//! | primitive arrays                          | `&[i32]` etc.          | `Vec<i32>` etc.                      |
+//! | classes that are generated as well        | `&Wrapper`             | `Wrapper`                            |
+//! | any other class                           | `InvocationArg`        | `Instance`                           |
+//! | `void`                                    |                        | `()`                                 |
+//!
+//! The generated code uses only fully qualified paths, so that it may be `include!`d in any module.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{errors, Jvm, utils};
+use crate::errors::J4RsError;
+use crate::reflection::{self, ClassDescription, FieldDescription, MethodDescription};
+
+// The names of the functions that every wrapper defines
+const WRAPPER_FUNCTIONS: [&str; 3] = ["from_instance", "instance", "into_instance"];
+
+// The Rust keywords, which are used as raw identifiers
+const KEYWORDS: [&str; 47] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum", "extern",
+    "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+    "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
+// The keywords that cannot be raw identifiers
+const RESERVED: [&str; 4] = ["crate", "self", "Self", "super"];
+
+/// Generates typed Rust wrappers of the public classes of a jar.
+///
+/// It may be used from a `build.rs`, in order to `include!` the generated code, or with the `j4rs-bindgen` binary.
+/// The jar should be in the classpath of the `Jvm` that is used for the generation (eg. by being deployed in the jassets directory).
+///
+/// ```no_run
+/// # use j4rs::{BindingsGenerator, JvmBuilder};
+/// # fn main() -> j4rs::errors::Result<()> {
+/// let jvm = JvmBuilder::new().build()?;
+/// BindingsGenerator::new("my-library-1.0.jar")
+///     .class("com.acme.Order")
+///     .write_to_file(&jvm, "src/bindings.rs")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BindingsGenerator {
+    jar: String,
+    class_names: Vec<String>,
+}
+
+impl BindingsGenerator {
+    /// Creates a generator for the classes of a jar.
+    /// The `jar` is either a path, or the name of a jar in the jassets directory.
+    pub fn new(jar: &str) -> BindingsGenerator {
+        BindingsGenerator {
+            jar: jar.to_string(),
+            class_names: Vec::new(),
+        }
+    }
+
+    /// Generates a wrapper only for the provided class, along with the other classes that are defined with this method.
+    /// If no class is defined, all the public classes of the jar are generated.
+    pub fn class(&mut self, class_name: &str) -> &mut BindingsGenerator {
+        self.class_names.push(class_name.to_string());
+        self
+    }
+
+    /// Generates the Rust source of the wrappers.
+    pub fn generate(&self, jvm: &Jvm) -> errors::Result<String> {
+        let jar_path = self.jar_path()?;
+        let classes = if self.class_names.is_empty() {
+            let jar_path_str = jar_path.to_str()
+                .ok_or_else(|| J4RsError::GeneralError(format!("Invalid path of jar: {:?}", jar_path)))?;
+            reflection::describe_jar(jvm, jar_path_str)?
+        } else {
+            self.class_names.iter()
+                .map(|class_name| reflection::describe_class(jvm, class_name))
+                .collect::<errors::Result<Vec<_>>>()?
+        };
+        Ok(generate_bindings(&classes, &format!("the jar {}", self.jar)))
+    }
+
+    /// Generates the Rust source of the wrappers and writes it to the file of the provided path.
+    pub fn write_to_file<P: AsRef<Path>>(&self, jvm: &Jvm, path: P) -> errors::Result<()> {
+        let source = self.generate(jvm)?;
+        fs::write(path, source)?;
+        Ok(())
+    }
+
+    fn jar_path(&self) -> errors::Result<PathBuf> {
+        let path = PathBuf::from(&self.jar);
+        let path = if path.is_file() { path } else { utils::jassets_path()?.join(&self.jar) };
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(J4RsError::GeneralError(format!("Could not find the jar {}", self.jar)))
+        }
+    }
+}
+
+/// Generates the Rust source of the wrappers of the described classes. The `origin` is mentioned in the header of the source.
+pub(crate) fn generate_bindings(classes: &[ClassDescription], origin: &str) -> String {
+    let wrappers = wrapper_names(classes);
+    let mut source = format!("// Generated by j4rs from {}. Do not edit.\n", origin);
+    for class in classes {
+        source.push('\n');
+        source.push_str(&generate_wrapper(class, &wrappers[&class.name], &wrappers));
+    }
+    source
+}
+
+// The names of the Rust structs of the classes. The simple names are used, unless they are not unique.
+fn wrapper_names(classes: &[ClassDescription]) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let mut used = HashSet::new();
+    for class in classes {
+        let mut segments: Vec<&str> = class.name.split('.').collect();
+        let mut name = pascal_case(segments.pop().unwrap_or_default());
+        // Prefix with the package, until the name is unique
+        while used.contains(&name) || RESERVED.contains(&name.as_str()) {
+            match segments.pop() {
+                Some(segment) => name = format!("{}{}", pascal_case(segment), name),
+                None => name = format!("{}_", name),
+            }
+        }
+        used.insert(name.clone());
+        names.insert(class.name.clone(), name);
+    }
+    names
+}
+
+fn generate_wrapper(class: &ClassDescription, wrapper_name: &str, wrappers: &HashMap<String, String>) -> String {
+    let mut used_names: HashSet<String> = WRAPPER_FUNCTIONS.iter().map(|name| name.to_string()).collect();
+    let mut functions = Vec::new();
+
+    // Only the public members are wrapped
+    let constructors: Vec<MethodDescription> = class.constructors.iter().filter(|constructor| constructor.is_public()).cloned().collect();
+    let constructor_names = overload_names("new", &constructors);
+    for (constructor, name) in constructors.iter().zip(constructor_names) {
+        let name = unique_name(name, &mut used_names);
+        functions.push(generate_constructor(class, constructor, &name, wrappers));
+    }
+    // The overloaded methods are grouped, in order to name them after their parameters
+    let mut methods_by_name: Vec<(&str, Vec<MethodDescription>)> = Vec::new();
+    for method in class.methods.iter().filter(|method| method.is_public()) {
+        match methods_by_name.iter_mut().find(|(name, _)| *name == method.name) {
+            Some((_, overloads)) => overloads.push(method.clone()),
+            None => methods_by_name.push((&method.name, vec![method.clone()])),
+        }
+    }
+    for (java_name, overloads) in &methods_by_name {
+        for (method, name) in overloads.iter().zip(overload_names(&snake_case(java_name), overloads)) {
+            let name = unique_name(name, &mut used_names);
+            functions.push(generate_method(class, method, &name, wrappers));
+        }
+    }
+    for field in class.fields.iter().filter(|field| field.is_public()) {
+        let mut name = snake_case(&field.name);
+        if used_names.contains(&name) {
+            name = format!("{}_field", name);
+        }
+        let name = unique_name(name, &mut used_names);
+        functions.push(generate_field(class, field, &name, wrappers));
+    }
+
+    format!(
+        r#"/// Wraps an `Instance` of the Java {kind} `{class_name}`.
+#[allow(dead_code)]
+pub struct {wrapper} {{
+    instance: j4rs::Instance,
+}}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+impl {wrapper} {{
+    /// The name of the Java class
+    pub const CLASS_NAME: &'static str = "{class_name}";
+
+    /// Wraps an `Instance`. Its class is not checked.
+    pub fn from_instance(instance: j4rs::Instance) -> {wrapper} {{
+        {wrapper} {{ instance }}
+    }}
+
+    /// The wrapped `Instance`
+    pub fn instance(&self) -> &j4rs::Instance {{
+        &self.instance
+    }}
+
+    /// Returns the wrapped `Instance`
+    pub fn into_instance(self) -> j4rs::Instance {{
+        self.instance
+    }}
+{functions}}}
+"#,
+        kind = if class.is_interface { "interface" } else { "class" },
+        class_name = class.name,
+        wrapper = wrapper_name,
+        functions = functions.iter().map(|function| format!("\n{}", function)).collect::<String>(),
+    )
+}
+
+fn generate_constructor(class: &ClassDescription, constructor: &MethodDescription, name: &str, wrappers: &HashMap<String, String>) -> String {
+    let (params, args) = params_and_args(constructor, wrappers);
+    format!(
+        r#"    /// Calls the constructor `{class_name}({java_params})`.
+    pub fn {name}(jvm: &j4rs::Jvm{params}) -> j4rs::errors::Result<Self> {{
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[{args}])?;
+        Ok(Self::from_instance(instance))
+    }}
+"#,
+        class_name = class.name,
+        java_params = constructor.parameter_types.join(", "),
+        name = name,
+        params = params,
+        args = args,
+    )
+}
+
+fn generate_method(class: &ClassDescription, method: &MethodDescription, name: &str, wrappers: &HashMap<String, String>) -> String {
+    let (params, args) = params_and_args(method, wrappers);
+    let return_type = JavaType::of(&method.return_type, wrappers);
+    let (receiver, invocation) = if method.is_static {
+        ("", format!("jvm.invoke_static(Self::CLASS_NAME, \"{}\", &[{}])", method.name, args))
+    } else {
+        ("&self, ", format!("jvm.invoke(&self.instance, \"{}\", &[{}])", method.name, args))
+    };
+    format!(
+        r#"    /// Calls `{modifier}{java_return} {class_name}.{java_name}({java_params})`.
+    pub fn {name}({receiver}jvm: &j4rs::Jvm{params}) -> j4rs::errors::Result<{rust_return}> {{
+{body}    }}
+"#,
+        modifier = if method.is_static { "static " } else { "" },
+        java_return = method.return_type,
+        class_name = class.name,
+        java_name = method.name,
+        java_params = method.parameter_types.join(", "),
+        name = name,
+        receiver = receiver,
+        params = params,
+        rust_return = return_type.rust_return_type(),
+        body = return_type.rust_return(&invocation),
+    )
+}
+
+fn generate_field(class: &ClassDescription, field: &FieldDescription, name: &str, wrappers: &HashMap<String, String>) -> String {
+    let field_type = JavaType::of(&field.field_type, wrappers);
+    let (modifier, receiver, invocation) = if field.is_static {
+        ("static ", "", format!("jvm.static_field(Self::CLASS_NAME, \"{}\")", field.name))
+    } else {
+        ("", "&self, ", format!("jvm.field(&self.instance, \"{}\")", field.name))
+    };
+    format!(
+        r#"    /// Reads the field `{modifier}{java_type} {class_name}.{java_name}`.
+    pub fn {name}({receiver}jvm: &j4rs::Jvm) -> j4rs::errors::Result<{rust_return}> {{
+{body}    }}
+"#,
+        modifier = modifier,
+        java_type = field.field_type,
+        class_name = class.name,
+        java_name = field.name,
+        name = name,
+        receiver = receiver,
+        rust_return = field_type.rust_return_type(),
+        body = field_type.rust_return(&invocation),
+    )
+}
+
+// The Rust parameters of a function (each one preceded by a comma), along with the respective InvocationArgs
+fn params_and_args(method: &MethodDescription, wrappers: &HashMap<String, String>) -> (String, String) {
+    let mut params = String::new();
+    let mut args = Vec::new();
+    for (i, java_type) in method.parameter_types.iter().enumerate() {
+        let name = format!("arg{}", i);
+        let java_type = JavaType::of(java_type, wrappers);
+        params.push_str(&format!(", {}: {}", name, java_type.rust_param_type()));
+        args.push(java_type.invocation_arg(&name));
+    }
+    (params, args.join(", "))
+}
+
+/// How a Java type is exchanged with Rust
+enum JavaType<'a> {
+    Void,
+    // The Rust type of a Java primitive
+    Primitive(&'static str),
+    // The Rust type of a boxed Java primitive and the respective variant of `Null`
+    Boxed(&'static str, &'static str),
+    String,
+    // The Rust type of the elements of a Java primitive array
+    PrimitiveArray(&'static str),
+    // The name of a generated wrapper
+    Wrapper(&'a str),
+    Object,
+}
+
+impl<'a> JavaType<'a> {
+    fn of(type_name: &str, wrappers: &'a HashMap<String, String>) -> JavaType<'a> {
+        match type_name {
+            "void" => JavaType::Void,
+            "boolean" => JavaType::Primitive("bool"),
+            "byte" => JavaType::Primitive("i8"),
+            "short" => JavaType::Primitive("i16"),
+            "int" => JavaType::Primitive("i32"),
+            "long" => JavaType::Primitive("i64"),
+            "float" => JavaType::Primitive("f32"),
+            "double" => JavaType::Primitive("f64"),
+            "char" => JavaType::Primitive("char"),
+            "java.lang.Boolean" => JavaType::Boxed("bool", "Boolean"),
+            "java.lang.Byte" => JavaType::Boxed("i8", "Byte"),
+            "java.lang.Short" => JavaType::Boxed("i16", "Short"),
+            "java.lang.Integer" => JavaType::Boxed("i32", "Integer"),
+            "java.lang.Long" => JavaType::Boxed("i64", "Long"),
+            "java.lang.Float" => JavaType::Boxed("f32", "Float"),
+            "java.lang.Double" => JavaType::Boxed("f64", "Double"),
+            "java.lang.Character" => JavaType::Boxed("char", "Character"),
+            "java.lang.String" => JavaType::String,
+            "boolean[]" => JavaType::PrimitiveArray("bool"),
+            "byte[]" => JavaType::PrimitiveArray("i8"),
+            "short[]" => JavaType::PrimitiveArray("i16"),
+            "int[]" => JavaType::PrimitiveArray("i32"),
+            "long[]" => JavaType::PrimitiveArray("i64"),
+            "float[]" => JavaType::PrimitiveArray("f32"),
+            "double[]" => JavaType::PrimitiveArray("f64"),
+            "char[]" => JavaType::PrimitiveArray("u16"),
+            _ => match wrappers.get(type_name) {
+                Some(wrapper) => JavaType::Wrapper(wrapper),
+                None => JavaType::Object,
+            },
+        }
+    }
+
+    fn rust_param_type(&self) -> String {
+        match self {
+            JavaType::Primitive(rust_type) => rust_type.to_string(),
+            JavaType::Boxed(rust_type, _) => format!("Option<{}>", rust_type),
+            JavaType::String => "&str".to_string(),
+            JavaType::PrimitiveArray(rust_type) => format!("&[{}]", rust_type),
+            JavaType::Wrapper(wrapper) => format!("&{}", wrapper),
+            JavaType::Void | JavaType::Object => "j4rs::InvocationArg".to_string(),
+        }
+    }
+
+    // The expression that creates the InvocationArg of the parameter with the provided name
+    fn invocation_arg(&self, name: &str) -> String {
+        match self {
+            JavaType::Primitive(_) => format!("<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from({})?.into_primitive()?", name),
+            JavaType::Boxed(_, null) => format!(
+                "match {} {{ Some(value) => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(value)?, None => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(j4rs::Null::{})? }}",
+                name, null),
+            JavaType::String => format!("<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from({})?", name),
+            JavaType::PrimitiveArray(_) => format!("j4rs::InvocationArg::from_primitive_array({})?", name),
+            JavaType::Wrapper(_) => format!("j4rs::InvocationArg::from(jvm.clone_instance({}.instance())?)", name),
+            JavaType::Void | JavaType::Object => name.to_string(),
+        }
+    }
+
+    fn rust_return_type(&self) -> String {
+        match self {
+            JavaType::Void => "()".to_string(),
+            JavaType::Primitive(rust_type) => rust_type.to_string(),
+            JavaType::Boxed(rust_type, _) => format!("Option<{}>", rust_type),
+            JavaType::String => "String".to_string(),
+            JavaType::PrimitiveArray(rust_type) => format!("Vec<{}>", rust_type),
+            JavaType::Wrapper(wrapper) => wrapper.to_string(),
+            JavaType::Object => "j4rs::Instance".to_string(),
+        }
+    }
+
+    // The statements that return the result of the provided invocation
+    fn rust_return(&self, invocation: &str) -> String {
+        match self {
+            JavaType::Void => format!("        {}?;\n        Ok(())\n", invocation),
+            JavaType::Wrapper(wrapper) => format!("        let result = {}?;\n        Ok({}::from_instance(result))\n", invocation, wrapper),
+            JavaType::Object => format!("        {}\n", invocation),
+            // Deserialized, because the boxed primitives may be null
+            JavaType::Boxed(..) => format!("        let result = {}?;\n        jvm.to_rust_deserialized(result)\n", invocation),
+            _ => format!("        let result = {}?;\n        jvm.to_rust(result)\n", invocation),
+        }
+    }
+}
+
+// The names of overloaded constructors or methods. If there are overloads, the names contain the types of the parameters.
+fn overload_names(base_name: &str, overloads: &[MethodDescription]) -> Vec<String> {
+    if overloads.len() == 1 {
+        return vec![base_name.to_string()];
+    }
+    overloads.iter()
+        .map(|overload| {
+            let mut name = base_name.to_string();
+            for parameter_type in &overload.parameter_types {
+                name.push('_');
+                name.push_str(&type_suffix(parameter_type));
+            }
+            name
+        })
+        .collect()
+}
+
+// A name for a type, to be used in the names of overloads. For example, `string_array` for `java.lang.String[]`
+fn type_suffix(type_name: &str) -> String {
+    let element_type = type_name.trim_end_matches("[]");
+    let dimensions = (type_name.len() - element_type.len()) / 2;
+    let simple_name = element_type.rsplit('.').next().unwrap_or(element_type);
+    let mut suffix = snake_case(simple_name);
+    for _ in 0..dimensions {
+        suffix.push_str("_array");
+    }
+    suffix
+}
+
+// Makes the `name` unique, by appending a number if needed. The name becomes a valid Rust identifier.
+fn unique_name(name: String, used_names: &mut HashSet<String>) -> String {
+    let mut unique = name.clone();
+    let mut i = 2;
+    while used_names.contains(&unique) {
+        unique = format!("{}_{}", name, i);
+        i += 1;
+    }
+    used_names.insert(unique.clone());
+    if RESERVED.contains(&unique.as_str()) {
+        format!("{}_", unique)
+    } else if KEYWORDS.contains(&unique.as_str()) {
+        format!("r#{}", unique)
+    } else {
+        unique
+    }
+}
+
+// Converts a Java name to snake case. For example, `to_url` for `toURL`
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().map(|c| if c == '$' { '_' } else { c }).collect();
+    let mut snake = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map(|next| next.is_lowercase()).unwrap_or(false);
+            if previous != '_' && (previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lower)) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+// Converts a simple Java class name to a Rust struct name. For example, `MapEntry` for `Map$Entry`
+fn pascal_case(name: &str) -> String {
+    name.split(['$', '_'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,143 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::ops::DerefMut;
+use std::os::raw::c_void;
+use std::slice;
+
+use jni_sys::{jlong, jobject};
+
+use crate::{cache, errors, Instance, InvocationArg, jni_utils, Jvm};
+use crate::errors::opt_to_res;
+use crate::logger::debug;
+
+const CLASS_BYTE_BUFFER: &str = "java.nio.ByteBuffer";
+
+/// Memory that may back a `java.nio.ByteBuffer`. See `Jvm::direct_byte_buffer`.
+///
+/// # Safety
+///
+/// The bytes must not move in memory when the implementor is moved
+/// (eg. a `Vec<u8>` can be used, because its contents are allocated on the heap).
+pub unsafe trait DirectBufferMemory: DerefMut<Target=[u8]> {}
+
+unsafe impl DirectBufferMemory for &mut [u8] {}
+
+unsafe impl DirectBufferMemory for Vec<u8> {}
+
+unsafe impl DirectBufferMemory for Box<[u8]> {}
+
+/// A direct `java.nio.ByteBuffer` that uses memory owned by Rust, without copying it. Created with `Jvm::direct_byte_buffer`.
+///
+/// The changes that Java makes to the buffer are visible in Rust and vice versa.
+/// The memory lives as long as this `DirectByteBuffer`: Java code must not keep using the `ByteBuffer` after it is dropped.
+pub struct DirectByteBuffer<B: DirectBufferMemory> {
+    // Declared before the memory, in order to be dropped first
+    instance: Instance,
+    memory: B,
+}
+
+impl<B: DirectBufferMemory> DirectByteBuffer<B> {
+    pub(crate) fn new(jvm: &Jvm, mut memory: B) -> errors::Result<DirectByteBuffer<B>> {
+        debug(&format!("Creating a direct ByteBuffer of {} bytes", memory.len()));
+        let byte_buffer = unsafe {
+            (opt_to_res((**jvm.jni_env).NewDirectByteBuffer)?)(
+                jvm.jni_env,
+                memory.as_mut_ptr() as *mut c_void,
+                memory.len() as jlong,
+            )
+        };
+        Jvm::do_return(jvm.jni_env, ())?;
+        let instance = instance_from_jobject(jvm, byte_buffer)?;
+        Ok(DirectByteBuffer { instance: jvm.cast(&instance, CLASS_BYTE_BUFFER)?, memory })
+    }
+
+    /// Returns the `Instance` of the `java.nio.ByteBuffer`.
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.memory
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.memory
+    }
+
+    /// Drops the `Instance` of the `ByteBuffer` and returns the memory.
+    pub fn into_inner(self) -> B {
+        let DirectByteBuffer { instance, memory } = self;
+        drop(instance);
+        memory
+    }
+}
+
+/// The `InvocationArg` refers to the memory of the `DirectByteBuffer`, so it must be used while the `DirectByteBuffer` lives.
+/// See the safety section of `Jvm::direct_byte_buffer`.
+impl<'a, B: DirectBufferMemory> TryFrom<&'a DirectByteBuffer<B>> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(byte_buffer: &'a DirectByteBuffer<B>) -> errors::Result<InvocationArg> {
+        let jni_env = cache::get_thread_local_env()?;
+        let global = jni_utils::create_global_ref_from_local_ref(byte_buffer.instance.jinstance, jni_env)?;
+        Ok(InvocationArg::from(Instance::new(global, byte_buffer.instance.class_name())?))
+    }
+}
+
+/// Returns the memory of the direct `java.nio.ByteBuffer` that is contained in the `instance`.
+///
+/// The returned pointer is valid as long as the `ByteBuffer` is reachable.
+pub(crate) fn direct_buffer_memory(jvm: &Jvm, instance: &Instance) -> errors::Result<(*mut u8, usize)> {
+    unsafe {
+        // Call the getObjectMethod. This returns a localref
+        let byte_buffer = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jvm.jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        Jvm::do_return(jvm.jni_env, ())?;
+        let address = (opt_to_res((**jvm.jni_env).GetDirectBufferAddress)?)(jvm.jni_env, byte_buffer);
+        let capacity = (opt_to_res((**jvm.jni_env).GetDirectBufferCapacity)?)(jvm.jni_env, byte_buffer);
+        jni_utils::delete_java_local_ref(jvm.jni_env, byte_buffer);
+        if address.is_null() || capacity < 0 {
+            Err(errors::J4RsError::JavaError("The Instance does not contain a direct java.nio.Buffer".to_string()))
+        } else {
+            Ok((address as *mut u8, capacity as usize))
+        }
+    }
+}
+
+/// Creates a slice from the memory of a direct buffer.
+pub(crate) unsafe fn slice_from_memory<'a>(address: *mut u8, capacity: usize) -> &'a mut [u8] {
+    if capacity == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(address, capacity)
+    }
+}
+
+fn instance_from_jobject(jvm: &Jvm, obj: jobject) -> errors::Result<Instance> {
+    unsafe {
+        let instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jvm.jni_env,
+            cache::get_java2rust_utils_class()?,
+            cache::get_java2rust_utils_create_instance_method()?,
+            obj,
+        );
+        jni_utils::delete_java_local_ref(jvm.jni_env, obj);
+        Jvm::do_return(jvm.jni_env, ())?;
+        Instance::from_jobject_with_global_ref(instance)
+    }
+}
@@ -13,26 +13,35 @@
 // limitations under the License.
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
 use jni_sys::{
     self,
+    jarray,
     jboolean,
+    jbyte,
+    jbyteArray,
+    jchar,
     jclass,
     jdouble,
     jfloat,
     jint,
+    jlong,
     jmethodID,
     JNIEnv,
     jobject,
     jobjectArray,
+    jshort,
     jsize,
     jstring,
+    jthrowable,
+    jvalue,
 };
 use libc::c_char;
 
-use crate::{api_tweaks as tweaks, errors, jni_utils, utils};
+use crate::{api_tweaks as tweaks, errors, jni_utils, Jvm, utils};
 use crate::errors::opt_to_res;
 use crate::logger::debug;
 
@@ -41,6 +50,18 @@ pub(crate) const INVO_BASE_NAME: &'static str = "org/astonbitecode/j4rs/api/Inst
 pub(crate) const INVO_IFACE_NAME: &'static str = "org/astonbitecode/j4rs/api/Instance";
 pub(crate) const UNKNOWN_FOR_RUST: &'static str = "known_in_java_world";
 pub(crate) const J4RS_ARRAY: &'static str = "org.astonbitecode.j4rs.api.dtos.Array";
+// The JNI names of the classes that are looked up with `get_class` and `get_method_id`
+pub(crate) const JNI_CLASS_BOOLEAN: &str = "java/lang/Boolean";
+pub(crate) const JNI_CLASS_BYTE: &str = "java/lang/Byte";
+pub(crate) const JNI_CLASS_SHORT: &str = "java/lang/Short";
+pub(crate) const JNI_CLASS_INTEGER: &str = "java/lang/Integer";
+pub(crate) const JNI_CLASS_LONG: &str = "java/lang/Long";
+pub(crate) const JNI_CLASS_FLOAT: &str = "java/lang/Float";
+pub(crate) const JNI_CLASS_DOUBLE: &str = "java/lang/Double";
+pub(crate) const JNI_CLASS_CHARACTER: &str = "java/lang/Character";
+pub(crate) const JNI_CLASS_STRING: &str = "java/lang/String";
+pub(crate) const JNI_CLASS_BIG_INTEGER: &str = "java/math/BigInteger";
+pub(crate) const JNI_CLASS_BIG_DECIMAL: &str = "java/math/BigDecimal";
 
 pub(crate) type JniGetMethodId = unsafe extern "system" fn(*mut *const jni_sys::JNINativeInterface_, *mut jni_sys::_jobject, *const c_char, *const c_char) -> *mut jni_sys::_jmethodID;
 pub(crate) type JniGetStaticMethodId = unsafe extern "system" fn(*mut *const jni_sys::JNINativeInterface_, *mut jni_sys::_jobject, *const c_char, *const c_char) -> *mut jni_sys::_jmethodID;
@@ -65,11 +86,42 @@ pub(crate) type JniSetObjectArrayElement = unsafe extern "system" fn(*mut *const
 pub(crate) type JniExceptionCheck = unsafe extern "system" fn(_: *mut JNIEnv) -> jboolean;
 pub(crate) type JniExceptionDescribe = unsafe extern "system" fn(_: *mut JNIEnv);
 pub(crate) type JniExceptionClear = unsafe extern "system" fn(_: *mut JNIEnv);
+pub(crate) type JniExceptionOccurred = unsafe extern "system" fn(_: *mut JNIEnv) -> jthrowable;
 pub(crate) type JniDeleteLocalRef = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> ();
 pub(crate) type JniDeleteGlobalRef = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> ();
 pub(crate) type JniNewGlobalRef = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> jobject;
 pub(crate) type JniThrowNew = unsafe extern "system" fn(_: *mut JNIEnv, _: jclass, _: *const c_char) -> jint;
 pub(crate) type JniIsSameObject = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject, _: jobject) -> jboolean;
+pub(crate) type JniThrow = unsafe extern "system" fn(_: *mut JNIEnv, _: jthrowable) -> jint;
+pub(crate) type JniIsInstanceOf = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject, _: jclass) -> jboolean;
+pub(crate) type JniGetArrayLength = unsafe extern "system" fn(_: *mut JNIEnv, _: jarray) -> jsize;
+pub(crate) type JniNewByteArray = unsafe extern "system" fn(_: *mut JNIEnv, _: jsize) -> jbyteArray;
+pub(crate) type JniGetByteArrayRegion = unsafe extern "system" fn(_: *mut JNIEnv, _: jbyteArray, _: jsize, _: jsize, _: *mut jbyte);
+pub(crate) type JniSetByteArrayRegion = unsafe extern "system" fn(_: *mut JNIEnv, _: jbyteArray, _: jsize, _: jsize, _: *const jbyte);
+pub(crate) type JniGetObjectArrayElement = unsafe extern "system" fn(_: *mut JNIEnv, _: jobjectArray, _: jsize) -> jobject;
+pub(crate) type JniNewLocalRef = unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> jobject;
+#[allow(non_snake_case)]
+pub(crate) type JniCallObjectMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jobject;
+#[allow(non_snake_case)]
+pub(crate) type JniCallStaticObjectMethodA = unsafe extern "system" fn(env: *mut JNIEnv, clazz: jclass, methodID: jmethodID, args: *const jvalue) -> jobject;
+#[allow(non_snake_case)]
+pub(crate) type JniCallStaticVoidMethodA = unsafe extern "system" fn(env: *mut JNIEnv, clazz: jclass, methodID: jmethodID, args: *const jvalue);
+#[allow(non_snake_case)]
+pub(crate) type JniCallBooleanMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jboolean;
+#[allow(non_snake_case)]
+pub(crate) type JniCallByteMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jbyte;
+#[allow(non_snake_case)]
+pub(crate) type JniCallCharMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jchar;
+#[allow(non_snake_case)]
+pub(crate) type JniCallShortMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jshort;
+#[allow(non_snake_case)]
+pub(crate) type JniCallIntMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jint;
+#[allow(non_snake_case)]
+pub(crate) type JniCallLongMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jlong;
+#[allow(non_snake_case)]
+pub(crate) type JniCallFloatMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jfloat;
+#[allow(non_snake_case)]
+pub(crate) type JniCallDoubleMethodA = unsafe extern "system" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, args: *const jvalue) -> jdouble;
 
 const CLASS_CACHING_ENABLED: bool = !(cfg!(target_os = "android"));
 
@@ -83,6 +135,8 @@ lazy_static! {
 thread_local! {
     pub(crate) static JNI_ENV: RefCell<Option<*mut JNIEnv>> = RefCell::new(None);
     pub(crate) static ACTIVE_JVMS: RefCell<i32> = RefCell::new(0);
+    // The location of the last panic that occurred in this thread
+    pub(crate) static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
     pub(crate) static JNI_GET_METHOD_ID: RefCell<Option<JniGetMethodId>> = RefCell::new(None);
     pub(crate) static JNI_GET_STATIC_METHOD_ID: RefCell<Option<JniGetStaticMethodId>> = RefCell::new(None);
     pub(crate) static JNI_NEW_OBJECT: RefCell<Option<JniNewObject>> = RefCell::new(None);
@@ -99,11 +153,35 @@ thread_local! {
     pub(crate) static JNI_EXCEPTION_CHECK: RefCell<Option<JniExceptionCheck>> = RefCell::new(None);
     pub(crate) static JNI_EXCEPTION_DESCRIBE: RefCell<Option<JniExceptionDescribe>> = RefCell::new(None);
     pub(crate) static JNI_EXCEPTION_CLEAR: RefCell<Option<JniExceptionClear>> = RefCell::new(None);
+    pub(crate) static JNI_EXCEPTION_OCCURRED: RefCell<Option<JniExceptionOccurred>> = RefCell::new(None);
     pub(crate) static JNI_DELETE_LOCAL_REF: RefCell<Option<JniDeleteLocalRef>> = RefCell::new(None);
     pub(crate) static JNI_DELETE_GLOBAL_REF: RefCell<Option<JniDeleteGlobalRef>> = RefCell::new(None);
     pub(crate) static JNI_NEW_GLOBAL_REF: RefCell<Option<JniNewGlobalRef>> = RefCell::new(None);
     pub(crate) static JNI_THROW_NEW: RefCell<Option<JniThrowNew>> = RefCell::new(None);
     pub(crate) static JNI_IS_SAME_OBJECT: RefCell<Option<JniIsSameObject>> = RefCell::new(None);
+    pub(crate) static JNI_THROW: RefCell<Option<JniThrow>> = RefCell::new(None);
+    pub(crate) static JNI_IS_INSTANCE_OF: RefCell<Option<JniIsInstanceOf>> = RefCell::new(None);
+    pub(crate) static JNI_GET_ARRAY_LENGTH: RefCell<Option<JniGetArrayLength>> = RefCell::new(None);
+    pub(crate) static JNI_NEW_BYTE_ARRAY: RefCell<Option<JniNewByteArray>> = RefCell::new(None);
+    pub(crate) static JNI_GET_BYTE_ARRAY_REGION: RefCell<Option<JniGetByteArrayRegion>> = RefCell::new(None);
+    pub(crate) static JNI_SET_BYTE_ARRAY_REGION: RefCell<Option<JniSetByteArrayRegion>> = RefCell::new(None);
+    pub(crate) static JNI_GET_OBJECT_ARRAY_ELEMENT: RefCell<Option<JniGetObjectArrayElement>> = RefCell::new(None);
+    pub(crate) static JNI_NEW_LOCAL_REF: RefCell<Option<JniNewLocalRef>> = RefCell::new(None);
+    pub(crate) static JNI_CALL_OBJECT_METHOD_A: RefCell<Option<JniCallObjectMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_STATIC_OBJECT_METHOD_A: RefCell<Option<JniCallStaticObjectMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_STATIC_VOID_METHOD_A: RefCell<Option<JniCallStaticVoidMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_BOOLEAN_METHOD_A: RefCell<Option<JniCallBooleanMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_BYTE_METHOD_A: RefCell<Option<JniCallByteMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_CHAR_METHOD_A: RefCell<Option<JniCallCharMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_SHORT_METHOD_A: RefCell<Option<JniCallShortMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_INT_METHOD_A: RefCell<Option<JniCallIntMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_LONG_METHOD_A: RefCell<Option<JniCallLongMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_FLOAT_METHOD_A: RefCell<Option<JniCallFloatMethodA>> = const { RefCell::new(None) };
+    pub(crate) static JNI_CALL_DOUBLE_METHOD_A: RefCell<Option<JniCallDoubleMethodA>> = const { RefCell::new(None) };
+    // Classes that are looked up by their JNI name (eg. `[I` or `java/util/List`)
+    pub(crate) static CLASSES_BY_NAME: RefCell<HashMap<&'static str, jclass>> = RefCell::new(HashMap::new());
+    // Method IDs that are looked up by the JNI name of their class, their name and their signature
+    pub(crate) static METHOD_IDS: RefCell<HashMap<(&'static str, &'static str, &'static str), jmethodID>> = RefCell::new(HashMap::new());
     // This is the factory class. It creates instances using reflection. Currently the `NativeInstantiationImpl`.
     pub(crate) static FACTORY_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
     // The constructor method of the `NativeInstantiationImpl`.
@@ -151,6 +229,8 @@ thread_local! {
     pub(crate) static INV_ARG_RUST_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
     // The invstatic ocation argument constructor method for objects of Basic type created by Rust
     pub(crate) static INV_ARG_BASIC_RUST_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
+    // The invocation argument constructor method for objects that are encoded by Rust with a binary codec
+    pub(crate) static INV_ARG_ENCODED_RUST_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
     // Basic types definitions
     pub(crate) static INTEGER_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
     pub(crate) static INTEGER_TO_INT_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
@@ -171,6 +251,20 @@ thread_local! {
     pub(crate) static DOUBLE_TO_DOUBLE_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
     pub(crate) static DOUBLE_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
     pub(crate) static INVOCATION_EXCEPTION_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
+    // The `ThrowableDescription` class that describes Java Throwables
+    pub(crate) static THROWABLE_DESCRIPTION_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
+    // The describe static method of the `ThrowableDescription`
+    pub(crate) static THROWABLE_DESCRIPTION_DESCRIBE_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
+    // The `java.lang.Throwable` class
+    pub(crate) static THROWABLE_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
+    // The getCause method of the `java.lang.Throwable`
+    pub(crate) static THROWABLE_GET_CAUSE_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
+    // The `Java2RustUtils` class
+    pub(crate) static JAVA2RUST_UTILS_CLASS: RefCell<Option<jclass>> = RefCell::new(None);
+    // The createInstance static method of the `Java2RustUtils`
+    pub(crate) static JAVA2RUST_UTILS_CREATE_INSTANCE_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
+    // The encode static method of the `Java2RustUtils`
+    pub(crate) static JAVA2RUST_UTILS_ENCODE_METHOD: RefCell<Option<jmethodID>> = RefCell::new(None);
 }
 
 macro_rules! get_cached {
@@ -238,6 +332,18 @@ pub(crate) fn get_thread_local_env() -> errors::Result<*mut JNIEnv> {
     }
 }
 
+pub(crate) fn set_panic_location(location: Option<String>) {
+    PANIC_LOCATION.with(|opt| {
+        *opt.borrow_mut() = location;
+    });
+}
+
+pub(crate) fn take_panic_location() -> Option<String> {
+    PANIC_LOCATION.with(|opt| {
+        opt.borrow_mut().take()
+    })
+}
+
 pub(crate) fn set_jni_get_method_id(j: Option<JniGetMethodId>) -> Option<JniGetMethodId> {
     debug("Called set_jni_get_method_id");
     JNI_GET_METHOD_ID.with(|opt| {
@@ -465,6 +571,20 @@ pub(crate) fn get_jni_exception_clear() -> Option<JniExceptionClear> {
     })
 }
 
+pub(crate) fn set_jni_exception_occurred(j: Option<JniExceptionOccurred>) -> Option<JniExceptionOccurred> {
+    debug("Called set_jni_exception_occurred");
+    JNI_EXCEPTION_OCCURRED.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_exception_occurred()
+}
+
+pub(crate) fn get_jni_exception_occurred() -> Option<JniExceptionOccurred> {
+    JNI_EXCEPTION_OCCURRED.with(|opt| {
+        *opt.borrow()
+    })
+}
+
 pub(crate) fn set_jni_delete_local_ref(j: Option<JniDeleteLocalRef>) -> Option<JniDeleteLocalRef> {
     debug("Called set_jni_delete_local_ref");
     JNI_DELETE_LOCAL_REF.with(|opt| {
@@ -535,6 +655,321 @@ pub(crate) fn get_is_same_object() -> Option<JniIsSameObject> {
     })
 }
 
+pub(crate) fn set_jni_throw(j: Option<JniThrow>) -> Option<JniThrow> {
+    debug("Called set_jni_throw");
+    JNI_THROW.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_throw()
+}
+
+pub(crate) fn get_jni_throw() -> Option<JniThrow> {
+    JNI_THROW.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_is_instance_of(j: Option<JniIsInstanceOf>) -> Option<JniIsInstanceOf> {
+    debug("Called set_is_instance_of");
+    JNI_IS_INSTANCE_OF.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_is_instance_of()
+}
+
+pub(crate) fn get_is_instance_of() -> Option<JniIsInstanceOf> {
+    JNI_IS_INSTANCE_OF.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_get_array_length(j: Option<JniGetArrayLength>) -> Option<JniGetArrayLength> {
+    debug("Called set_jni_get_array_length");
+    JNI_GET_ARRAY_LENGTH.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_array_length()
+}
+
+pub(crate) fn get_jni_get_array_length() -> Option<JniGetArrayLength> {
+    JNI_GET_ARRAY_LENGTH.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_new_byte_array(j: Option<JniNewByteArray>) -> Option<JniNewByteArray> {
+    debug("Called set_jni_new_byte_array");
+    JNI_NEW_BYTE_ARRAY.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_new_byte_array()
+}
+
+pub(crate) fn get_jni_new_byte_array() -> Option<JniNewByteArray> {
+    JNI_NEW_BYTE_ARRAY.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_get_byte_array_region(j: Option<JniGetByteArrayRegion>) -> Option<JniGetByteArrayRegion> {
+    debug("Called set_jni_get_byte_array_region");
+    JNI_GET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_byte_array_region()
+}
+
+pub(crate) fn get_jni_get_byte_array_region() -> Option<JniGetByteArrayRegion> {
+    JNI_GET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_set_byte_array_region(j: Option<JniSetByteArrayRegion>) -> Option<JniSetByteArrayRegion> {
+    debug("Called set_jni_set_byte_array_region");
+    JNI_SET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_set_byte_array_region()
+}
+
+pub(crate) fn get_jni_set_byte_array_region() -> Option<JniSetByteArrayRegion> {
+    JNI_SET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_get_object_array_element(j: Option<JniGetObjectArrayElement>) -> Option<JniGetObjectArrayElement> {
+    debug("Called set_jni_get_object_array_element");
+    JNI_GET_OBJECT_ARRAY_ELEMENT.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_object_array_element()
+}
+
+pub(crate) fn get_jni_get_object_array_element() -> Option<JniGetObjectArrayElement> {
+    JNI_GET_OBJECT_ARRAY_ELEMENT.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_new_local_ref(j: Option<JniNewLocalRef>) -> Option<JniNewLocalRef> {
+    debug("Called set_jni_new_local_ref");
+    JNI_NEW_LOCAL_REF.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_new_local_ref()
+}
+
+pub(crate) fn get_jni_new_local_ref() -> Option<JniNewLocalRef> {
+    JNI_NEW_LOCAL_REF.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_object_method_a(j: Option<JniCallObjectMethodA>) -> Option<JniCallObjectMethodA> {
+    debug("Called set_jni_call_object_method_a");
+    JNI_CALL_OBJECT_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_object_method_a()
+}
+
+pub(crate) fn get_jni_call_object_method_a() -> Option<JniCallObjectMethodA> {
+    JNI_CALL_OBJECT_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_static_object_method_a(j: Option<JniCallStaticObjectMethodA>) -> Option<JniCallStaticObjectMethodA> {
+    debug("Called set_jni_call_static_object_method_a");
+    JNI_CALL_STATIC_OBJECT_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_static_object_method_a()
+}
+
+pub(crate) fn get_jni_call_static_object_method_a() -> Option<JniCallStaticObjectMethodA> {
+    JNI_CALL_STATIC_OBJECT_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_static_void_method_a(j: Option<JniCallStaticVoidMethodA>) -> Option<JniCallStaticVoidMethodA> {
+    debug("Called set_jni_call_static_void_method_a");
+    JNI_CALL_STATIC_VOID_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_static_void_method_a()
+}
+
+pub(crate) fn get_jni_call_static_void_method_a() -> Option<JniCallStaticVoidMethodA> {
+    JNI_CALL_STATIC_VOID_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_boolean_method_a(j: Option<JniCallBooleanMethodA>) -> Option<JniCallBooleanMethodA> {
+    debug("Called set_jni_call_boolean_method_a");
+    JNI_CALL_BOOLEAN_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_boolean_method_a()
+}
+
+pub(crate) fn get_jni_call_boolean_method_a() -> Option<JniCallBooleanMethodA> {
+    JNI_CALL_BOOLEAN_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_byte_method_a(j: Option<JniCallByteMethodA>) -> Option<JniCallByteMethodA> {
+    debug("Called set_jni_call_byte_method_a");
+    JNI_CALL_BYTE_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_byte_method_a()
+}
+
+pub(crate) fn get_jni_call_byte_method_a() -> Option<JniCallByteMethodA> {
+    JNI_CALL_BYTE_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_char_method_a(j: Option<JniCallCharMethodA>) -> Option<JniCallCharMethodA> {
+    debug("Called set_jni_call_char_method_a");
+    JNI_CALL_CHAR_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_char_method_a()
+}
+
+pub(crate) fn get_jni_call_char_method_a() -> Option<JniCallCharMethodA> {
+    JNI_CALL_CHAR_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_short_method_a(j: Option<JniCallShortMethodA>) -> Option<JniCallShortMethodA> {
+    debug("Called set_jni_call_short_method_a");
+    JNI_CALL_SHORT_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_short_method_a()
+}
+
+pub(crate) fn get_jni_call_short_method_a() -> Option<JniCallShortMethodA> {
+    JNI_CALL_SHORT_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_int_method_a(j: Option<JniCallIntMethodA>) -> Option<JniCallIntMethodA> {
+    debug("Called set_jni_call_int_method_a");
+    JNI_CALL_INT_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_int_method_a()
+}
+
+pub(crate) fn get_jni_call_int_method_a() -> Option<JniCallIntMethodA> {
+    JNI_CALL_INT_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_long_method_a(j: Option<JniCallLongMethodA>) -> Option<JniCallLongMethodA> {
+    debug("Called set_jni_call_long_method_a");
+    JNI_CALL_LONG_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_long_method_a()
+}
+
+pub(crate) fn get_jni_call_long_method_a() -> Option<JniCallLongMethodA> {
+    JNI_CALL_LONG_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_float_method_a(j: Option<JniCallFloatMethodA>) -> Option<JniCallFloatMethodA> {
+    debug("Called set_jni_call_float_method_a");
+    JNI_CALL_FLOAT_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_float_method_a()
+}
+
+pub(crate) fn get_jni_call_float_method_a() -> Option<JniCallFloatMethodA> {
+    JNI_CALL_FLOAT_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn set_jni_call_double_method_a(j: Option<JniCallDoubleMethodA>) -> Option<JniCallDoubleMethodA> {
+    debug("Called set_jni_call_double_method_a");
+    JNI_CALL_DOUBLE_METHOD_A.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_double_method_a()
+}
+
+pub(crate) fn get_jni_call_double_method_a() -> Option<JniCallDoubleMethodA> {
+    JNI_CALL_DOUBLE_METHOD_A.with(|opt| {
+        *opt.borrow()
+    })
+}
+
+pub(crate) fn get_class(class_name: &'static str) -> errors::Result<jclass> {
+    if !CLASS_CACHING_ENABLED {
+        // The find_class of the Android tweaks keeps a global ref for each class, which is never deleted.
+        // Creating another global ref per call would exhaust the global refs table.
+        return tweaks::find_class(get_thread_local_env()?, class_name);
+    }
+    match CLASSES_BY_NAME.with(|classes| classes.borrow().get(class_name).cloned()) {
+        Some(class) => Ok(class),
+        None => {
+            let env = get_thread_local_env()?;
+            let c = tweaks::find_class(env, class_name)?;
+            let class = jni_utils::create_global_ref_from_local_ref(c, env)?;
+            CLASSES_BY_NAME.with(|classes| classes.borrow_mut().insert(class_name, class));
+            Ok(class)
+        }
+    }
+}
+
+pub(crate) fn get_method_id(class_name: &'static str, method_name: &'static str, signature: &'static str) -> errors::Result<jmethodID> {
+    lookup_method_id(class_name, method_name, signature, false)
+}
+
+pub(crate) fn get_static_method_id(class_name: &'static str, method_name: &'static str, signature: &'static str) -> errors::Result<jmethodID> {
+    lookup_method_id(class_name, method_name, signature, true)
+}
+
+fn lookup_method_id(class_name: &'static str, method_name: &'static str, signature: &'static str, is_static: bool) -> errors::Result<jmethodID> {
+    let key = (class_name, method_name, signature);
+    if let Some(method_id) = METHOD_IDS.with(|ids| ids.borrow().get(&key).cloned()) {
+        return Ok(method_id);
+    }
+    let env = get_thread_local_env()?;
+    let class = get_class(class_name)?;
+    let cstr1 = utils::to_c_string(method_name);
+    let cstr2 = utils::to_c_string(signature);
+    let method_id = unsafe {
+        if is_static {
+            (opt_to_res(get_jni_get_static_method_id())?)(env, class, cstr1, cstr2)
+        } else {
+            (opt_to_res(get_jni_get_method_id())?)(env, class, cstr1, cstr2)
+        }
+    };
+    utils::drop_c_string(cstr1);
+    utils::drop_c_string(cstr2);
+    Jvm::do_return(env, ())?;
+    METHOD_IDS.with(|ids| ids.borrow_mut().insert(key, method_id));
+    Ok(method_id)
+}
+
 pub(crate) fn set_factory_class(j: jclass) {
     debug("Called set_factory_class");
     FACTORY_CLASS.with(|opt| {
@@ -1195,6 +1630,38 @@ pub(crate) fn get_inv_arg_rust_constructor_method() -> errors::Result<jmethodID>
         set_inv_arg_rust_constructor_method)
 }
 
+pub(crate) fn set_inv_arg_encoded_rust_constructor_method(j: jmethodID) {
+    debug("Called set_inv_arg_encoded_rust_constructor_method");
+    INV_ARG_ENCODED_RUST_CONSTRUCTOR_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_inv_arg_encoded_rust_constructor_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INV_ARG_ENCODED_RUST_CONSTRUCTOR_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let cstr1 = utils::to_c_string("<init>");
+            let cstr2 = utils::to_c_string("(Ljava/lang/String;[BLjava/lang/String;)V");
+            let j = unsafe {
+                let m = opt_to_res(get_jni_get_method_id())?;
+                let invargclass = get_invocation_arg_class()?;
+                (m)(
+                    env,
+                    invargclass,
+                    cstr1,
+                    cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_inv_arg_encoded_rust_constructor_method)
+}
+
 pub(crate) fn set_inv_arg_basic_rust_constructor_method(j: jmethodID) {
     debug("Called set_inv_arg_basic_rust_constructor_method");
     INV_ARG_BASIC_RUST_CONSTRUCTOR_METHOD.with(|opt| {
@@ -1360,7 +1827,7 @@ pub(crate) fn get_long_class() -> errors::Result<jclass> {
 
 pub(crate) fn set_invocation_exception_class(j: jclass) {
     debug("Called set_invocation_exception_class");
-    LONG_CLASS.with(|opt| {
+    INVOCATION_EXCEPTION_CLASS.with(|opt| {
         *opt.borrow_mut() = Some(j);
     });
 }
@@ -1788,4 +2255,250 @@ pub(crate) fn get_double_to_double_method() -> errors::Result<jmethodID> {
             j
         },
         set_double_to_double_method)
-}
\ No newline at end of file
+}
+
+pub(crate) fn set_throwable_description_class(j: jclass) {
+    debug("Called set_throwable_description_class");
+    THROWABLE_DESCRIPTION_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_throwable_description_class() -> errors::Result<jclass> {
+    get_cached!(
+        THROWABLE_DESCRIPTION_CLASS,
+        {
+            let env = get_thread_local_env()?;
+
+            let c = tweaks::find_class(
+                env,
+                "org/astonbitecode/j4rs/api/dtos/ThrowableDescription",
+            )?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_throwable_description_class)
+}
+
+pub(crate) fn set_throwable_description_describe_method(j: jmethodID) {
+    debug("Called set_throwable_description_describe_method");
+    THROWABLE_DESCRIPTION_DESCRIBE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_throwable_description_describe_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        THROWABLE_DESCRIPTION_DESCRIBE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let signature = "(Ljava/lang/Throwable;)Ljava/lang/String;";
+            let cstr1 = utils::to_c_string("describe");
+            let cstr2 = utils::to_c_string(signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_throwable_description_class()?,
+                    cstr1,
+                    cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_throwable_description_describe_method)
+}
+
+pub(crate) fn set_throwable_class(j: jclass) {
+    debug("Called set_throwable_class");
+    THROWABLE_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_throwable_class() -> errors::Result<jclass> {
+    get_cached!(
+        THROWABLE_CLASS,
+        {
+            let env = get_thread_local_env()?;
+
+            let c = tweaks::find_class(
+                env,
+                "java/lang/Throwable",
+            )?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_throwable_class)
+}
+
+pub(crate) fn set_throwable_get_cause_method(j: jmethodID) {
+    debug("Called set_throwable_get_cause_method");
+    THROWABLE_GET_CAUSE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_throwable_get_cause_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        THROWABLE_GET_CAUSE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let signature = "()Ljava/lang/Throwable;";
+            let cstr1 = utils::to_c_string("getCause");
+            let cstr2 = utils::to_c_string(signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_throwable_class()?,
+                    cstr1,
+                    cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_throwable_get_cause_method)
+}
+
+pub(crate) fn set_java2rust_utils_class(j: jclass) {
+    debug("Called set_java2rust_utils_class");
+    JAVA2RUST_UTILS_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_java2rust_utils_class() -> errors::Result<jclass> {
+    get_cached!(
+        JAVA2RUST_UTILS_CLASS,
+        {
+            let env = get_thread_local_env()?;
+
+            let c = tweaks::find_class(
+                env,
+                "org/astonbitecode/j4rs/api/java2rust/Java2RustUtils",
+            )?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_java2rust_utils_class)
+}
+
+pub(crate) fn set_java2rust_utils_create_instance_method(j: jmethodID) {
+    debug("Called set_java2rust_utils_create_instance_method");
+    JAVA2RUST_UTILS_CREATE_INSTANCE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_java2rust_utils_create_instance_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        JAVA2RUST_UTILS_CREATE_INSTANCE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let signature = format!("(Ljava/lang/Object;)L{};", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("createInstance");
+            let cstr2 = utils::to_c_string(&signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_java2rust_utils_class()?,
+                    cstr1,
+                    cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_java2rust_utils_create_instance_method)
+}
+
+pub(crate) fn set_java2rust_utils_encode_method(j: jmethodID) {
+    debug("Called set_java2rust_utils_encode_method");
+    JAVA2RUST_UTILS_ENCODE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_java2rust_utils_encode_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        JAVA2RUST_UTILS_ENCODE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let signature = format!("(L{};Ljava/lang/String;)[B", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("encode");
+            let cstr2 = utils::to_c_string(&signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_java2rust_utils_class()?,
+                    cstr1,
+                    cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_java2rust_utils_encode_method)
+}
+
+/// Retrieves and caches all the classes and method IDs that j4rs uses, for the current thread.
+///
+/// This is called while the class loader that loaded the library is available (eg. in `JNI_OnLoad`),
+/// so that the classes can be cached for all the threads. See `api_tweaks::set_class_caching`.
+pub(crate) fn load_classes_and_methods() -> errors::Result<()> {
+    get_factory_class()?;
+    get_invocation_arg_class()?;
+    get_factory_instantiate_method()?;
+    get_factory_create_for_static_method()?;
+    get_factory_create_java_array_method()?;
+    get_factory_create_java_list_method()?;
+    get_java_instance_class()?;
+    get_invoke_method()?;
+    get_invoke_static_method()?;
+    get_invoke_to_channel_method()?;
+    get_init_callback_channel_method()?;
+    get_field_method()?;
+    get_clone_static_method()?;
+    get_cast_static_method()?;
+    get_get_json_method()?;
+    get_get_object_class_method()?;
+    get_get_object_method()?;
+    get_inv_arg_java_constructor_method()?;
+    get_inv_arg_rust_constructor_method()?;
+    get_inv_arg_basic_rust_constructor_method()?;
+    get_inv_arg_encoded_rust_constructor_method()?;
+    get_class_to_invoke_clone_and_cast()?;
+    get_integer_class()?;
+    get_integer_constructor_method()?;
+    get_integer_to_int_method()?;
+    get_long_class()?;
+    get_invocation_exception_class()?;
+    get_long_constructor_method()?;
+    get_long_to_long_method()?;
+    get_short_class()?;
+    get_short_constructor_method()?;
+    get_short_to_short_method()?;
+    get_byte_class()?;
+    get_byte_constructor_method()?;
+    get_byte_to_byte_method()?;
+    get_float_class()?;
+    get_float_constructor_method()?;
+    get_float_to_float_method()?;
+    get_double_class()?;
+    get_double_constructor_method()?;
+    get_double_to_double_method()?;
+    get_throwable_description_class()?;
+    get_throwable_description_describe_method()?;
+    get_throwable_class()?;
+    get_throwable_get_cause_method()?;
+    get_java2rust_utils_class()?;
+    get_java2rust_utils_create_instance_method()?;
+    get_java2rust_utils_encode_method()?;
+    Ok(())
+}
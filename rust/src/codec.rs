@@ -0,0 +1,69 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors;
+
+/// The format that is used for serializing the values that are transferred between Rust and Java.
+///
+/// `Json` is the default. The binary formats result in smaller payloads. Their effect on the performance depends on the values;
+/// the `codecs` benchmark compares them.
+/// `Jni` does not encode the values at all: they are converted to and from Java objects directly, with JNI calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[non_exhaustive]
+pub enum Codec {
+    #[default]
+    Json,
+    /// [CBOR](https://tools.ietf.org/html/rfc7049)
+    Cbor,
+    /// [MessagePack](https://msgpack.org). Structs are serialized as maps, including the names of their fields.
+    MessagePack,
+    /// The Java objects are created and read directly, with JNI calls.
+    /// Structs are converted to and from the expected Java class, using its setters, getters or fields
+    /// instead of Jackson (ie. Jackson annotations are not taken into account).
+    Jni,
+}
+
+impl Codec {
+    /// The name of the respective `org.astonbitecode.j4rs.json.Codec.Format`
+    pub(crate) fn java_format(&self) -> errors::Result<&'static str> {
+        match self {
+            Codec::Json => Ok("JSON"),
+            Codec::Cbor => Ok("CBOR"),
+            Codec::MessagePack => Ok("MESSAGE_PACK"),
+            Codec::Jni => Err(errors::J4RsError::RustError("The Jni codec does not have a Java format".to_string())),
+        }
+    }
+
+    pub(crate) fn encode<T: Serialize>(&self, value: &T) -> errors::Result<Vec<u8>> {
+        match self {
+            Codec::Json => Ok(serde_json::to_vec(value)?),
+            Codec::Cbor => Ok(serde_cbor::to_vec(value)?),
+            // Java maps the structs by the names of their fields
+            Codec::MessagePack => Ok(rmp_serde::to_vec_named(value)?),
+            Codec::Jni => Err(errors::J4RsError::RustError("The Jni codec does not encode values".to_string())),
+        }
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> errors::Result<T> {
+        match self {
+            Codec::Json => Ok(serde_json::from_slice(payload)?),
+            Codec::Cbor => Ok(serde_cbor::from_slice(payload)?),
+            Codec::MessagePack => Ok(rmp_serde::from_slice(payload)?),
+            Codec::Jni => Err(errors::J4RsError::RustError("The Jni codec does not decode values".to_string())),
+        }
+    }
+}
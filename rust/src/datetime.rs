@@ -0,0 +1,278 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of the Rust dates and times to and from the `java.time` classes.
+//!
+//! | Rust                         | Java                                                 |
+//! |------------------------------|------------------------------------------------------|
+//! | `std::time::Duration`        | `java.time.Duration`                                 |
+//! | `std::time::SystemTime`      | `java.time.Instant`, or `java.util.Date`             |
+//! | `chrono::NaiveDate`          | `java.time.LocalDate`                                |
+//! | `chrono::NaiveDateTime`      | `java.time.LocalDateTime`                            |
+//! | `chrono::DateTime`           | `java.time.ZonedDateTime`, or `OffsetDateTime`       |
+//!
+//! The chrono types are converted only if the `chrono` feature is enabled.
+
+use std::any::{Any, TypeId};
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, DateTime, FixedOffset, NaiveDate, NaiveDateTime, Offset, Timelike, TimeZone, Utc};
+#[cfg(feature = "chrono")]
+use jni_sys::jint;
+use jni_sys::{jlong, jobject, jvalue, JNIEnv};
+
+use crate::{errors, Instance, jni_utils};
+
+const CLASS_DURATION: &str = "java/time/Duration";
+const CLASS_INSTANT: &str = "java/time/Instant";
+const CLASS_DATE: &str = "java/util/Date";
+#[cfg(feature = "chrono")]
+const CLASS_LOCAL_DATE: &str = "java/time/LocalDate";
+#[cfg(feature = "chrono")]
+const CLASS_LOCAL_DATE_TIME: &str = "java/time/LocalDateTime";
+#[cfg(feature = "chrono")]
+const CLASS_ZONED_DATE_TIME: &str = "java/time/ZonedDateTime";
+#[cfg(feature = "chrono")]
+const CLASS_OFFSET_DATE_TIME: &str = "java/time/OffsetDateTime";
+#[cfg(feature = "chrono")]
+const CLASS_ZONE_OFFSET: &str = "java/time/ZoneOffset";
+
+/// Creates a `java.time.Duration`. The Duration is a local reference.
+pub(crate) fn new_duration(duration: &Duration, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let seconds = jlong::try_from(duration.as_secs())
+        .map_err(|_| errors::J4RsError::RustError(format!("The duration {:?} does not fit in a java.time.Duration", duration)))?;
+    jni_utils::call_static_object_method(
+        CLASS_DURATION,
+        "ofSeconds",
+        "(JJ)Ljava/time/Duration;",
+        &[jvalue { j: seconds }, jvalue { j: duration.subsec_nanos() as jlong }],
+        jni_env,
+    )
+}
+
+/// Creates a `java.time.Instant`. The Instant is a local reference.
+pub(crate) fn new_instant(time: &SystemTime, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let (seconds, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => (jlong::try_from(after.as_secs()).ok(), after.subsec_nanos() as jlong),
+        Err(error) => (jlong::try_from(error.duration().as_secs()).ok().map(|s| -s), -(error.duration().subsec_nanos() as jlong)),
+    };
+    let seconds = seconds.ok_or_else(|| errors::J4RsError::RustError(format!("The time {:?} does not fit in a java.time.Instant", time)))?;
+    // The Instant adjusts negative nanoseconds
+    jni_utils::call_static_object_method(
+        CLASS_INSTANT,
+        "ofEpochSecond",
+        "(JJ)Ljava/time/Instant;",
+        &[jvalue { j: seconds }, jvalue { j: nanos }],
+        jni_env,
+    )
+}
+
+/// Creates a `java.util.Date`, which is truncated to milliseconds. The Date is a local reference.
+pub(crate) fn new_date(time: &SystemTime, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let instant = new_instant(time, jni_env)?;
+    let date = jni_utils::call_static_object_method(CLASS_DATE, "from", "(Ljava/time/Instant;)Ljava/util/Date;", &[jvalue { l: instant }], jni_env);
+    jni_utils::delete_java_local_ref(jni_env, instant);
+    date
+}
+
+/// Creates a `java.time.LocalDate`. The LocalDate is a local reference.
+#[cfg(feature = "chrono")]
+pub(crate) fn new_local_date(date: &NaiveDate, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    jni_utils::call_static_object_method(
+        CLASS_LOCAL_DATE,
+        "of",
+        "(III)Ljava/time/LocalDate;",
+        &[jvalue { i: date.year() }, jvalue { i: date.month() as jint }, jvalue { i: date.day() as jint }],
+        jni_env,
+    )
+}
+
+/// Creates a `java.time.LocalDateTime`. The LocalDateTime is a local reference.
+#[cfg(feature = "chrono")]
+pub(crate) fn new_local_date_time(date_time: &NaiveDateTime, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let args: Vec<jvalue> = [
+        date_time.year(),
+        date_time.month() as jint,
+        date_time.day() as jint,
+        date_time.hour() as jint,
+        date_time.minute() as jint,
+        date_time.second() as jint,
+        date_time.nanosecond() as jint,
+    ].iter().map(|&i| jvalue { i }).collect();
+    jni_utils::call_static_object_method(CLASS_LOCAL_DATE_TIME, "of", "(IIIIIII)Ljava/time/LocalDateTime;", &args, jni_env)
+}
+
+/// Creates a `java.time.ZonedDateTime` with the fixed offset of the `date_time`. The ZonedDateTime is a local reference.
+#[cfg(feature = "chrono")]
+pub(crate) fn new_zoned_date_time<Tz: TimeZone>(date_time: &DateTime<Tz>, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let instant = jni_utils::call_static_object_method(
+        CLASS_INSTANT,
+        "ofEpochSecond",
+        "(JJ)Ljava/time/Instant;",
+        &[jvalue { j: date_time.timestamp() }, jvalue { j: date_time.timestamp_subsec_nanos() as jlong }],
+        jni_env,
+    )?;
+    let offset = jni_utils::call_static_object_method(
+        CLASS_ZONE_OFFSET,
+        "ofTotalSeconds",
+        "(I)Ljava/time/ZoneOffset;",
+        &[jvalue { i: date_time.offset().fix().local_minus_utc() }],
+        jni_env,
+    );
+    let zoned_date_time = offset.and_then(|offset| {
+        let zoned_date_time = jni_utils::call_static_object_method(
+            CLASS_ZONED_DATE_TIME,
+            "ofInstant",
+            "(Ljava/time/Instant;Ljava/time/ZoneId;)Ljava/time/ZonedDateTime;",
+            &[jvalue { l: instant }, jvalue { l: offset }],
+            jni_env,
+        );
+        jni_utils::delete_java_local_ref(jni_env, offset);
+        zoned_date_time
+    });
+    jni_utils::delete_java_local_ref(jni_env, instant);
+    zoned_date_time
+}
+
+/// If `T` is a date or time type that the `instance` can be converted to, the Java object is converted to `T`.
+/// Otherwise, `None` is returned.
+pub(crate) fn time_from_instance<T: Any>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<Option<Box<T>>> {
+    macro_rules! try_time_of {
+        ($rust_type:ty, $from_jobject:expr) => {
+            if TypeId::of::<T>() == TypeId::of::<$rust_type>() {
+                let v: Option<$rust_type> = jni_utils::map_instance_object(instance, jni_env, $from_jobject)?;
+                return Ok(v.and_then(|v| (Box::new(v) as Box<dyn Any>).downcast::<T>().ok()));
+            }
+        };
+    }
+
+    try_time_of!(Duration, duration_from_jobject);
+    try_time_of!(SystemTime, system_time_from_jobject);
+    #[cfg(feature = "chrono")]
+    {
+        try_time_of!(NaiveDate, local_date_from_jobject);
+        try_time_of!(NaiveDateTime, local_date_time_from_jobject);
+        try_time_of!(DateTime<FixedOffset>, date_time_from_jobject);
+        try_time_of!(DateTime<Utc>, |object, jni_env| Ok(date_time_from_jobject(object, jni_env)?.map(|date_time| date_time.with_timezone(&Utc))));
+    }
+    Ok(None)
+}
+
+fn duration_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<Duration>> {
+    if !jni_utils::is_instance_of(object, CLASS_DURATION, jni_env)? {
+        return Ok(None);
+    }
+    let seconds = jni_utils::call_primitive_method!(object, CLASS_DURATION, "getSeconds", "()J", get_jni_call_long_method_a, jni_env);
+    let nanos = jni_utils::call_primitive_method!(object, CLASS_DURATION, "getNano", "()I", get_jni_call_int_method_a, jni_env);
+    match u64::try_from(seconds) {
+        Ok(seconds) => Ok(Some(Duration::new(seconds, nanos as u32))),
+        Err(_) => Err(errors::J4RsError::RustError(format!("The negative java.time.Duration of {} seconds does not fit in a Duration", seconds))),
+    }
+}
+
+fn system_time_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<SystemTime>> {
+    let (seconds, nanos) = match epoch_time_from_jobject(object, jni_env)? {
+        Some(epoch_time) => epoch_time,
+        None => return Ok(None),
+    };
+    // The nanoseconds of an Instant are always positive
+    let time = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(seconds as u64, nanos))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(seconds.unsigned_abs())).and_then(|time| time.checked_add(Duration::from_nanos(nanos as u64)))
+    };
+    time.map(Some).ok_or_else(|| errors::J4RsError::RustError(format!("The time of {} seconds since the epoch does not fit in a SystemTime", seconds)))
+}
+
+// The seconds and nanoseconds since the epoch, if the object is an Instant or a Date
+fn epoch_time_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<(i64, u32)>> {
+    if jni_utils::is_instance_of(object, CLASS_INSTANT, jni_env)? {
+        let seconds = jni_utils::call_primitive_method!(object, CLASS_INSTANT, "getEpochSecond", "()J", get_jni_call_long_method_a, jni_env);
+        let nanos = jni_utils::call_primitive_method!(object, CLASS_INSTANT, "getNano", "()I", get_jni_call_int_method_a, jni_env);
+        Ok(Some((seconds, nanos as u32)))
+    } else if jni_utils::is_instance_of(object, CLASS_DATE, jni_env)? {
+        let instant = jni_utils::call_object_method(object, CLASS_DATE, "toInstant", "()Ljava/time/Instant;", jni_env)?;
+        let epoch_time = epoch_time_from_jobject(instant, jni_env);
+        jni_utils::delete_java_local_ref(jni_env, instant);
+        epoch_time
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn local_date_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<NaiveDate>> {
+    if !jni_utils::is_instance_of(object, CLASS_LOCAL_DATE, jni_env)? {
+        return Ok(None);
+    }
+    let year = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE, "getYear", "()I", get_jni_call_int_method_a, jni_env);
+    let month = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE, "getMonthValue", "()I", get_jni_call_int_method_a, jni_env);
+    let day = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE, "getDayOfMonth", "()I", get_jni_call_int_method_a, jni_env);
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+        .map(Some)
+        .ok_or_else(|| errors::J4RsError::RustError(format!("The java.time.LocalDate {}-{}-{} does not fit in a NaiveDate", year, month, day)))
+}
+
+#[cfg(feature = "chrono")]
+fn local_date_time_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<NaiveDateTime>> {
+    if !jni_utils::is_instance_of(object, CLASS_LOCAL_DATE_TIME, jni_env)? {
+        return Ok(None);
+    }
+    let date = jni_utils::call_object_method(object, CLASS_LOCAL_DATE_TIME, "toLocalDate", "()Ljava/time/LocalDate;", jni_env)?;
+    let date_result = local_date_from_jobject(date, jni_env);
+    jni_utils::delete_java_local_ref(jni_env, date);
+    let hour = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE_TIME, "getHour", "()I", get_jni_call_int_method_a, jni_env);
+    let minute = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE_TIME, "getMinute", "()I", get_jni_call_int_method_a, jni_env);
+    let second = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE_TIME, "getSecond", "()I", get_jni_call_int_method_a, jni_env);
+    let nano = jni_utils::call_primitive_method!(object, CLASS_LOCAL_DATE_TIME, "getNano", "()I", get_jni_call_int_method_a, jni_env);
+    match date_result? {
+        Some(date) => date.and_hms_nano_opt(hour as u32, minute as u32, second as u32, nano as u32)
+            .map(Some)
+            .ok_or_else(|| errors::J4RsError::RustError(format!("The java.time.LocalDateTime {}T{}:{}:{}.{} does not fit in a NaiveDateTime", date, hour, minute, second, nano))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn date_time_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<DateTime<FixedOffset>>> {
+    let class_name = if jni_utils::is_instance_of(object, CLASS_ZONED_DATE_TIME, jni_env)? {
+        CLASS_ZONED_DATE_TIME
+    } else if jni_utils::is_instance_of(object, CLASS_OFFSET_DATE_TIME, jni_env)? {
+        CLASS_OFFSET_DATE_TIME
+    } else {
+        return Ok(None);
+    };
+    let instant = jni_utils::call_object_method(object, class_name, "toInstant", "()Ljava/time/Instant;", jni_env)?;
+    let epoch_time = epoch_time_from_jobject(instant, jni_env);
+    jni_utils::delete_java_local_ref(jni_env, instant);
+    let (seconds, nanos) = match epoch_time? {
+        Some(epoch_time) => epoch_time,
+        None => return Ok(None),
+    };
+    let offset = jni_utils::call_object_method(object, class_name, "getOffset", "()Ljava/time/ZoneOffset;", jni_env)?;
+    let offset_seconds = offset_seconds_from_jobject(offset, jni_env);
+    jni_utils::delete_java_local_ref(jni_env, offset);
+    FixedOffset::east_opt(offset_seconds?)
+        .and_then(|offset| offset.timestamp_opt(seconds, nanos).single())
+        .map(Some)
+        .ok_or_else(|| errors::J4RsError::RustError(format!("The time of {} seconds since the epoch does not fit in a DateTime", seconds)))
+}
+
+#[cfg(feature = "chrono")]
+fn offset_seconds_from_jobject(offset: jobject, jni_env: *mut JNIEnv) -> errors::Result<i32> {
+    Ok(jni_utils::call_primitive_method!(offset, CLASS_ZONE_OFFSET, "getTotalSeconds", "()I", get_jni_call_int_method_a, jni_env))
+}
+
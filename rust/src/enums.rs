@@ -0,0 +1,55 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor};
+
+/// A fieldless Rust enum that maps to a Java enum, variant by constant.
+///
+/// It is usually implemented with `#[derive(JavaEnum)]` of `j4rs_derive`, which also implements
+/// `TryFrom<E> for InvocationArg`, as well as `Serialize` and `Deserialize` using the names of the Java constants.
+/// This way, `Jvm::to_rust` returns the variant of a Java enum constant.
+pub trait JavaEnum: Sized {
+    /// The fully qualified name of the Java enum class
+    const JAVA_CLASS: &'static str;
+
+    /// The name of the Java constant of this variant, as returned by `Enum.name()`
+    fn java_name(&self) -> &'static str;
+
+    /// The variant of the Java constant with the provided name, if any
+    fn from_java_name(name: &str) -> Option<Self>;
+}
+
+/// Deserializes a `JavaEnum` from the name of a Java constant.
+#[doc(hidden)]
+pub fn deserialize_java_enum<'de, E: JavaEnum, D: Deserializer<'de>>(deserializer: D) -> Result<E, D::Error> {
+    deserializer.deserialize_str(JavaEnumVisitor(PhantomData))
+}
+
+struct JavaEnumVisitor<E>(PhantomData<E>);
+
+impl<'de, E: JavaEnum> Visitor<'de> for JavaEnumVisitor<E> {
+    type Value = E;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the name of a constant of the Java enum {}", E::JAVA_CLASS)
+    }
+
+    fn visit_str<Er: de::Error>(self, name: &str) -> Result<E, Er> {
+        E::from_java_name(name)
+            .ok_or_else(|| de::Error::custom(format!("Unknown constant {} of the Java enum {}", name, E::JAVA_CLASS)))
+    }
+}
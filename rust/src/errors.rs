@@ -17,11 +17,14 @@ use std::convert::Infallible;
 use std::error::Error;
 use std::ffi::NulError;
 use std::io;
-use std::sync::{PoisonError, TryLockError};
+use std::sync::{Arc, PoisonError, TryLockError};
 
 use fs_extra;
+use serde::Deserialize;
 use serde_json;
 
+use crate::api::Instance;
+
 pub type Result<T> = result::Result<T, J4RsError>;
 
 pub(crate) fn opt_to_res<T>(opt: Option<T>) -> Result<T> {
@@ -43,6 +46,7 @@ pub(crate) fn res_to_opt<T>(res: Result<T>) -> Option<T> {
 pub enum J4RsError {
     GeneralError(String),
     JavaError(String),
+    JavaException(JavaThrowable),
     JniError(String),
     RustError(String),
     ParseError(String),
@@ -54,6 +58,7 @@ impl fmt::Display for J4RsError {
         match self {
             &J4RsError::GeneralError(ref message) => write!(f, "{}", message),
             &J4RsError::JavaError(ref message) => write!(f, "{}", message),
+            &J4RsError::JavaException(ref throwable) => write!(f, "{}", throwable),
             &J4RsError::JniError(ref message) => write!(f, "{}", message),
             &J4RsError::RustError(ref message) => write!(f, "{}", message),
             &J4RsError::ParseError(ref message) => write!(f, "{}", message),
@@ -67,6 +72,7 @@ impl Error for J4RsError {
         match *self {
             J4RsError::GeneralError(_) => "A general error occured",
             J4RsError::JavaError(_) => "An error coming from Java occured",
+            J4RsError::JavaException(_) => "An exception was thrown by Java",
             J4RsError::JniError(_) => "A JNI error occured",
             J4RsError::RustError(_) => "An error coming from Rust occured",
             J4RsError::ParseError(_) => "A parsing error occured",
@@ -75,6 +81,108 @@ impl Error for J4RsError {
     }
 }
 
+/// The description of a Throwable that was thrown in the Java world.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaThrowable {
+    class_name: String,
+    message: Option<String>,
+    stack_trace: Vec<String>,
+    cause: Option<Box<JavaThrowable>>,
+    #[serde(skip)]
+    instance: Option<ThrowableInstance>,
+}
+
+impl JavaThrowable {
+    /// The fully qualified name of the class of the Throwable.
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// The message of the Throwable, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// The stack trace of the Throwable, one element per frame.
+    pub fn stack_trace(&self) -> &[String] {
+        &self.stack_trace
+    }
+
+    /// The cause of the Throwable, as returned by `getCause`.
+    pub fn cause(&self) -> Option<&JavaThrowable> {
+        self.cause.as_ref().map(|c| c.as_ref())
+    }
+
+    /// Returns an `Instance` of the actual Java Throwable, so that its methods can be invoked.
+    pub fn instance(&self) -> Result<Instance> {
+        match &self.instance {
+            Some(throwable_instance) => {
+                let instance = Instance::from_jobject_with_global_ref((throwable_instance.0).0.jinstance)?;
+                Instance::new(instance.java_object(), (throwable_instance.0).0.class_name())
+            }
+            None => Err(J4RsError::GeneralError(format!("No Instance is available for the Java Throwable {}", self.class_name))),
+        }
+    }
+
+    pub(crate) fn set_instance(&mut self, instance: Instance) {
+        self.instance = Some(ThrowableInstance(Arc::new(SharedInstance(instance))));
+    }
+
+    pub(crate) fn cause_mut(&mut self) -> Option<&mut JavaThrowable> {
+        self.cause.as_mut().map(|c| c.as_mut())
+    }
+
+    /// The last Throwable in the chain of causes.
+    ///
+    /// The exceptions thrown by invoked Java methods are wrapped by j4rs, so this is usually the exception that was originally thrown.
+    pub fn root_cause(&self) -> &JavaThrowable {
+        let mut current = self;
+        while let Some(cause) = current.cause() {
+            current = cause;
+        }
+        current
+    }
+}
+
+/// Holds the `Instance` of a Java Throwable.
+///
+/// It does not take part in the equality of `JavaThrowable`s.
+#[derive(Clone)]
+struct ThrowableInstance(Arc<SharedInstance>);
+
+/// The `Instance` contains a global reference, which may be used by any thread.
+struct SharedInstance(Instance);
+
+unsafe impl Sync for SharedInstance {}
+
+impl PartialEq for ThrowableInstance {
+    fn eq(&self, _other: &ThrowableInstance) -> bool {
+        true
+    }
+}
+
+impl Eq for ThrowableInstance {}
+
+impl fmt::Debug for ThrowableInstance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Instance of {}", (self.0).0.class_name())
+    }
+}
+
+impl fmt::Display for JavaThrowable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.class_name, message)?,
+            None => write!(f, "{}", self.class_name)?,
+        };
+        match &self.cause {
+            Some(cause) => write!(f, ". Caused by: {}", cause),
+            None => Ok(()),
+        }
+    }
+}
+
 impl From<NulError> for J4RsError {
     fn from(err: NulError) -> J4RsError {
         J4RsError::JniError(format!("{:?}", err))
@@ -93,6 +201,24 @@ impl From<serde_json::Error> for J4RsError {
     }
 }
 
+impl From<serde_cbor::Error> for J4RsError {
+    fn from(err: serde_cbor::Error) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
+impl From<rmp_serde::encode::Error> for J4RsError {
+    fn from(err: rmp_serde::encode::Error) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
+impl From<rmp_serde::decode::Error> for J4RsError {
+    fn from(err: rmp_serde::decode::Error) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
 impl From<fs_extra::error::Error> for J4RsError {
     fn from(err: fs_extra::error::Error) -> J4RsError {
         J4RsError::GeneralError(format!("{:?}", err))
@@ -12,16 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::os::raw::{c_char, c_double};
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
+use std::sync::Once;
 
-use jni_sys::{jint, JNI_TRUE, JNIEnv, jobject, jobjectRefType, jstring};
+use jni_sys::{jbyte, jbyteArray, jclass, jint, jsize, JNI_TRUE, JNIEnv, jobject, jobjectArray, jobjectRefType, jstring, jthrowable, jvalue};
 
-use crate::{InvocationArg, Jvm};
+use crate::{Instance, InvocationArg, Jvm};
+use crate::api_tweaks as tweaks;
+use crate::arrays::JavaPrimitive;
 use crate::cache;
 use crate::errors;
-use crate::errors::opt_to_res;
-use crate::logger::{debug, error};
+use crate::errors::{JavaThrowable, opt_to_res};
+use crate::logger::{debug, error, warn};
 use crate::utils;
 
 pub(crate) fn invocation_arg_jobject_from_rust_serialized(ia: &InvocationArg, jni_env: *mut JNIEnv, create_global: bool) -> errors::Result<jobject> {
@@ -30,6 +35,9 @@ pub(crate) fn invocation_arg_jobject_from_rust_serialized(ia: &InvocationArg, jn
             _s @ &InvocationArg::Java { .. } | _s @ &InvocationArg::RustBasic { .. } => {
                 panic!("Called invocation_arg_jobject_from_rust_serialized for an InvocationArg that contains an object. Please consider opening a bug to the developers.")
             }
+            _s @ &InvocationArg::RustEncoded { .. } => {
+                panic!("Called invocation_arg_jobject_from_rust_serialized for an InvocationArg that is not serialized as json. Please consider opening a bug to the developers.")
+            }
             &InvocationArg::Rust { ref class_name, ref json, .. } => {
                 debug(&format!("Creating jobject from Rust with serialized representation for class {}", class_name));
                 (class_name.to_owned(), json.to_owned())
@@ -63,13 +71,56 @@ pub(crate) fn invocation_arg_jobject_from_rust_serialized(ia: &InvocationArg, jn
     }
 }
 
+pub(crate) fn invocation_arg_jobject_from_rust_encoded(ia: &InvocationArg, jni_env: *mut JNIEnv, create_global: bool) -> errors::Result<jobject> {
+    unsafe {
+        let (class_name, payload, codec) = match ia {
+            &InvocationArg::RustEncoded { ref class_name, ref payload, ref codec, .. } => {
+                debug(&format!("Creating jobject from Rust with {:?} representation for class {}", codec, class_name));
+                (class_name, payload, codec)
+            }
+            _ => {
+                panic!("Called invocation_arg_jobject_from_rust_encoded for an InvocationArg that is not serialized with a binary codec. Please consider opening a bug to the developers.")
+            }
+        };
+
+        let class_name_jstring = global_jobject_from_str(class_name, jni_env)?;
+        let payload_jbyte_array = u8::new_java_array(payload, jni_env)?;
+        let format_jstring = global_jobject_from_str(codec.java_format()?, jni_env)?;
+
+        debug(&format!("Calling the InvocationArg constructor with '{}'", class_name));
+        let inv_arg_instance = (opt_to_res(cache::get_jni_new_object())?)(
+            jni_env,
+            cache::get_invocation_arg_class()?,
+            cache::get_inv_arg_encoded_rust_constructor_method()?,
+            // First argument: class_name
+            class_name_jstring,
+            // Second argument: payload
+            payload_jbyte_array,
+            // Third argument: format
+            format_jstring,
+        );
+
+        // Check for exceptions
+        Jvm::do_return(jni_env, ())?;
+        delete_java_ref(jni_env, class_name_jstring);
+        delete_java_local_ref(jni_env, payload_jbyte_array);
+        delete_java_ref(jni_env, format_jstring);
+
+        if create_global {
+            Ok(create_global_ref_from_local_ref(inv_arg_instance, jni_env)?)
+        } else {
+            Ok(inv_arg_instance)
+        }
+    }
+}
+
 pub(crate) fn invocation_arg_jobject_from_rust_basic(ia: &InvocationArg, jni_env: *mut JNIEnv, create_global: bool) -> errors::Result<jobject> {
     unsafe {
         let (class_name, jinstance) = match ia {
             _s @ &InvocationArg::Java { .. } => {
                 panic!("Called invocation_arg_jobject_from_rust_basic for an InvocationArg that contains an object from Java. Please consider opening a bug to the developers.")
             }
-            _s @ &InvocationArg::Rust { .. } => {
+            _s @ &InvocationArg::Rust { .. } | _s @ &InvocationArg::RustEncoded { .. } => {
                 panic!("Called invocation_arg_jobject_from_rust_basic for an InvocationArg that contains a serialized object. Please consider opening a bug to the developers.")
             }
             &InvocationArg::RustBasic { ref class_name, ref instance, .. } => {
@@ -103,7 +154,7 @@ pub(crate) fn invocation_arg_jobject_from_rust_basic(ia: &InvocationArg, jni_env
 pub(crate) fn invocation_arg_jobject_from_java(ia: &InvocationArg, jni_env: *mut JNIEnv, create_global: bool) -> errors::Result<jobject> {
     unsafe {
         let (class_name, jinstance) = match ia {
-            _s @ &InvocationArg::Rust { .. } => panic!("Called invocation_arg_jobject_from_java for an InvocationArg that is created by Rust. Please consider opening a bug to the developers."),
+            _s @ &InvocationArg::Rust { .. } | _s @ &InvocationArg::RustEncoded { .. } => panic!("Called invocation_arg_jobject_from_java for an InvocationArg that is created by Rust. Please consider opening a bug to the developers."),
             &InvocationArg::Java { ref class_name, ref instance, .. } | &InvocationArg::RustBasic { ref class_name, ref instance, .. } => {
                 debug(&format!("Creating jobject from Java for class {}", class_name));
                 (class_name.to_owned(), instance.jinstance)
@@ -198,24 +249,18 @@ pub(crate) fn _create_weak_global_ref_from_global_ref(global_ref: jobject, jni_e
 }
 
 /// Deletes the java ref from the memory
+///
+/// Deleting a reference is allowed while an exception is pending, so any pending exception is kept.
 pub fn delete_java_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
     unsafe {
-        match ((**jni_env).DeleteGlobalRef,
-               (**jni_env).ExceptionCheck,
-               (**jni_env).ExceptionDescribe,
-               (**jni_env).ExceptionClear) {
-            (Some(dgr), Some(exc), Some(exd), Some(exclear)) => {
+        match (**jni_env).DeleteGlobalRef {
+            Some(dgr) => {
                 dgr(
                     jni_env,
                     jinstance,
                 );
-                if (exc)(jni_env) == JNI_TRUE {
-                    (exd)(jni_env);
-                    (exclear)(jni_env);
-                    error("An Exception was thrown by Java... Please check the logs or the console.");
-                }
             }
-            (_, _, _, _) => {
+            None => {
                 error("Could retrieve the native functions to drop the Java ref. This may lead to memory leaks");
             }
         }
@@ -223,42 +268,37 @@ pub fn delete_java_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
 }
 
 /// Deletes the java ref from the memory
+///
+/// Deleting a reference is allowed while an exception is pending, so any pending exception is kept.
 pub(crate) fn delete_java_local_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
     unsafe {
-        match ((**jni_env).DeleteLocalRef,
-               (**jni_env).ExceptionCheck,
-               (**jni_env).ExceptionDescribe,
-               (**jni_env).ExceptionClear) {
-            (Some(dlr), Some(exc), Some(exd), Some(exclear)) => {
+        match (**jni_env).DeleteLocalRef {
+            Some(dlr) => {
                 dlr(
                     jni_env,
                     jinstance,
                 );
-                if (exc)(jni_env) == JNI_TRUE {
-                    (exd)(jni_env);
-                    (exclear)(jni_env);
-                    error("An Exception was thrown by Java... Please check the logs or the console.");
-                }
             }
-            (_, _, _, _) => {
+            None => {
                 error("Could retrieve the native functions to drop the Java ref. This may lead to memory leaks");
             }
         }
     }
 }
 
-pub(crate) fn global_jobject_from_str(string: &str, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+/// Creates a new local ref of the passed java object, for example in order to return it from a native method.
+pub(crate) fn create_local_ref(jinstance: jobject, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
     unsafe {
-        let tmp = utils::to_c_string_struct(string);
-        let obj = (opt_to_res(cache::get_jni_new_string_utf())?)(
-            jni_env,
-            tmp.as_ptr(),
-        );
-        let gr = create_global_ref_from_local_ref(obj, jni_env)?;
-        Ok(gr)
+        Ok((opt_to_res(cache::get_jni_new_local_ref())?)(jni_env, jinstance))
     }
 }
 
+pub(crate) fn global_jobject_from_str(string: &str, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let obj = jstring_from_str(string, jni_env)?;
+    let gr = create_global_ref_from_local_ref(obj, jni_env)?;
+    Ok(gr)
+}
+
 pub(crate) fn global_jobject_from_i8(a: &i8, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
     unsafe {
         let tmp = a.clone() as *const i8;
@@ -416,34 +456,401 @@ pub(crate) unsafe fn f64_from_jobject(obj: jobject, jni_env: *mut JNIEnv) -> err
 }
 
 pub fn jstring_to_rust_string(jvm: &Jvm, java_string: jstring) -> errors::Result<String> {
+    let rust_string = string_from_jstring(java_string, jvm.jni_env)?;
+    Jvm::do_return(jvm.jni_env, rust_string)
+}
+
+/// Creates a new Java String from the passed Rust `&str`.
+///
+/// The returned `jstring` is a local reference and may be returned to Java by functions that are called from Java.
+pub fn rust_string_to_jstring(jvm: &Jvm, string: &str) -> errors::Result<jstring> {
+    let java_string = jstring_from_str(string, jvm.jni_env)?;
+    Jvm::do_return(jvm.jni_env, java_string)
+}
+
+/// Copies the contents of the passed Java `byte[]` into a Rust `Vec<u8>`.
+pub fn jbyte_array_to_rust_vec(jvm: &Jvm, java_array: jbyteArray) -> errors::Result<Vec<u8>> {
+    let bytes = bytes_from_jbyte_array(java_array, jvm.jni_env)?;
+    Jvm::do_return(jvm.jni_env, bytes)
+}
+
+/// Creates a new Java `byte[]` that contains the passed bytes.
+///
+/// The returned `jbyteArray` is a local reference and may be returned to Java by functions that are called from Java.
+pub fn rust_bytes_to_jbyte_array(jvm: &Jvm, bytes: &[u8]) -> errors::Result<jbyteArray> {
+    let java_array = jbyte_array_from_bytes(bytes, jvm.jni_env)?;
+    Jvm::do_return(jvm.jni_env, java_array)
+}
+
+pub(crate) fn jstring_from_str(string: &str, jni_env: *mut JNIEnv) -> errors::Result<jstring> {
+    unsafe {
+        let tmp = utils::to_c_string_struct(string);
+        let java_string = (opt_to_res(cache::get_jni_new_string_utf())?)(
+            jni_env,
+            tmp.as_ptr(),
+        );
+        Ok(java_string)
+    }
+}
+
+pub(crate) fn bytes_from_jbyte_array(java_array: jbyteArray, jni_env: *mut JNIEnv) -> errors::Result<Vec<u8>> {
+    if java_array.is_null() {
+        return Err(errors::J4RsError::RustError("Cannot create a Rust Vec from a null Java array".to_string()));
+    }
+    unsafe {
+        let length = (opt_to_res(cache::get_jni_get_array_length())?)(
+            jni_env,
+            java_array,
+        );
+        let mut bytes = vec![0u8; length as usize];
+        (opt_to_res(cache::get_jni_get_byte_array_region())?)(
+            jni_env,
+            java_array,
+            0,
+            length,
+            bytes.as_mut_ptr() as *mut jbyte,
+        );
+        Ok(bytes)
+    }
+}
+
+pub(crate) fn jbyte_array_from_bytes(bytes: &[u8], jni_env: *mut JNIEnv) -> errors::Result<jbyteArray> {
+    unsafe {
+        let length = bytes.len() as jsize;
+        let java_array = (opt_to_res(cache::get_jni_new_byte_array())?)(
+            jni_env,
+            length,
+        );
+        if java_array.is_null() {
+            return Err(errors::J4RsError::JavaError(format!("Could not create a Java byte array of length {}", length)));
+        }
+        (opt_to_res(cache::get_jni_set_byte_array_region())?)(
+            jni_env,
+            java_array,
+            0,
+            length,
+            bytes.as_ptr() as *const jbyte,
+        );
+        Ok(java_array)
+    }
+}
+
+/// Creates Rust Instances from the elements of a Java `Instance[]`.
+pub(crate) fn instances_from_jobject_array(java_array: jobjectArray, jni_env: *mut JNIEnv) -> errors::Result<Vec<Instance>> {
+    if java_array.is_null() {
+        return Ok(Vec::new());
+    }
+    unsafe {
+        let length = (opt_to_res(cache::get_jni_get_array_length())?)(jni_env, java_array);
+        let mut instances = Vec::with_capacity(length as usize);
+        for i in 0..length {
+            let java_instance = (opt_to_res(cache::get_jni_get_object_array_element())?)(jni_env, java_array, i);
+            instances.push(Instance::from_jobject_with_global_ref(java_instance)?);
+        }
+        Ok(instances)
+    }
+}
+
+pub(crate) fn string_from_jstring(java_string: jstring, jni_env: *mut JNIEnv) -> errors::Result<String> {
+    if java_string.is_null() {
+        return Err(errors::J4RsError::RustError("Cannot create a Rust String from a null Java String".to_string()));
+    }
     unsafe {
         let s = (opt_to_res(cache::get_jni_get_string_utf_chars())?)(
-            jvm.jni_env,
+            jni_env,
             java_string,
             ptr::null_mut(),
         ) as *mut c_char;
         let rust_string = utils::to_rust_string(s);
         (opt_to_res(cache::get_jni_release_string_utf_chars())?)(
-            jvm.jni_env,
+            jni_env,
             java_string,
             s,
         );
-        Jvm::do_return(jvm.jni_env, rust_string)
+        Ok(rust_string)
+    }
+}
+
+/// Describes the passed `throwable`, along with its causes.
+///
+/// This is called while handling a thrown Java Exception, so it must not call `Jvm::do_return`.
+pub(crate) fn describe_throwable(throwable: jthrowable, jni_env: *mut JNIEnv) -> errors::Result<JavaThrowable> {
+    unsafe {
+        let json_jstring = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jni_env,
+            cache::get_throwable_description_class()?,
+            cache::get_throwable_description_describe_method()?,
+            throwable,
+        );
+        if clear_pending_exception(jni_env)? {
+            return Err(errors::J4RsError::JavaError("An Exception was thrown by Java while describing a Throwable".to_string()));
+        }
+        let json = string_from_jstring(json_jstring, jni_env);
+        delete_java_local_ref(jni_env, json_jstring);
+        let mut java_throwable: JavaThrowable = serde_json::from_str(&json?)?;
+        // The description is useful even without the Instances
+        if let Err(error) = attach_throwable_instances(&mut java_throwable, throwable, jni_env) {
+            warn(&format!("Could not create Instances for the Java Throwable {}: {}", java_throwable.class_name(), error));
+        }
+        Ok(java_throwable)
+    }
+}
+
+/// Attaches an `Instance` to the description of the passed `throwable`, as well as to the descriptions of its causes.
+unsafe fn attach_throwable_instances(java_throwable: &mut JavaThrowable, throwable: jthrowable, jni_env: *mut JNIEnv) -> errors::Result<()> {
+    let mut current_description = Some(java_throwable);
+    let mut current_throwable = throwable;
+    while let Some(description) = current_description {
+        let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jni_env,
+            cache::get_java2rust_utils_class()?,
+            cache::get_java2rust_utils_create_instance_method()?,
+            current_throwable,
+        );
+        if clear_pending_exception(jni_env)? {
+            return Err(errors::J4RsError::JavaError("An Exception was thrown by Java while creating an Instance of a Throwable".to_string()));
+        }
+        let next_throwable = if description.cause().is_some() {
+            (opt_to_res(cache::get_jni_call_object_method())?)(
+                jni_env,
+                current_throwable,
+                cache::get_throwable_get_cause_method()?,
+            )
+        } else {
+            ptr::null_mut()
+        };
+        // The passed throwable is owned by the caller
+        if current_throwable != throwable {
+            delete_java_local_ref(jni_env, current_throwable);
+        }
+        if clear_pending_exception(jni_env)? {
+            return Err(errors::J4RsError::JavaError("An Exception was thrown by Java while retrieving the cause of a Throwable".to_string()));
+        }
+
+        let global = create_global_ref_from_local_ref(java_instance, jni_env)?;
+        let instance = Instance::new(global, description.class_name())?;
+        description.set_instance(instance);
+
+        if next_throwable.is_null() {
+            break;
+        }
+        current_description = description.cause_mut();
+        current_throwable = next_throwable;
+    }
+    Ok(())
+}
+
+/// Clears any pending exception, returning true if there was one.
+unsafe fn clear_pending_exception(jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    if (opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE {
+        (opt_to_res(cache::get_jni_exception_clear())?)(jni_env);
+        Ok(true)
+    } else {
+        Ok(false)
     }
 }
 
 pub(crate) fn throw_exception(message: &str, jni_env: *mut JNIEnv) -> errors::Result<i32> {
+    throw_exception_of_class(cache::get_invocation_exception_class()?, message, jni_env)
+}
+
+pub(crate) fn throw_exception_of_class(class: jclass, message: &str, jni_env: *mut JNIEnv) -> errors::Result<i32> {
     unsafe {
         let message_jstring = utils::to_c_string_struct(message);
         let i = (opt_to_res(cache::get_jni_throw_new())?)(
             jni_env,
-            cache::get_invocation_exception_class()?,
+            class,
             message_jstring.as_ptr(),
         );
         Ok(i)
     }
 }
 
+pub(crate) fn throw_throwable(throwable: jthrowable, jni_env: *mut JNIEnv) -> errors::Result<i32> {
+    unsafe {
+        let i = (opt_to_res(cache::get_jni_throw())?)(
+            jni_env,
+            throwable,
+        );
+        Ok(i)
+    }
+}
+
+pub(crate) fn is_exception_pending(jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    unsafe {
+        Ok((opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE)
+    }
+}
+
+pub(crate) fn is_instance_of_throwable(obj: jobject, jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    unsafe {
+        let b = (opt_to_res(cache::get_is_instance_of())?)(
+            jni_env,
+            obj,
+            cache::get_throwable_class()?,
+        );
+        Ok(b == JNI_TRUE)
+    }
+}
+
+pub(crate) fn is_instance_of(obj: jobject, class_name: &'static str, jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    unsafe {
+        Ok((opt_to_res(cache::get_is_instance_of())?)(jni_env, obj, cache::get_class(class_name)?) == JNI_TRUE)
+    }
+}
+
+/// Applies the `f` to the Java object that the `instance` contains, if it is not null.
+/// The object is a local reference that is deleted afterwards.
+pub(crate) fn map_instance_object<R>(
+    instance: &Instance,
+    jni_env: *mut JNIEnv,
+    f: fn(jobject, *mut JNIEnv) -> errors::Result<Option<R>>,
+) -> errors::Result<Option<R>> {
+    unsafe {
+        // Call the getObjectMethod. This returns a localref
+        let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        Jvm::do_return(jni_env, ())?;
+        if object.is_null() {
+            return Ok(None);
+        }
+        let mapped = f(object, jni_env);
+        delete_java_local_ref(jni_env, object);
+        mapped
+    }
+}
+
+/// The name of the runtime class of the Java object that the `instance` contains.
+/// If the object is null, the class that is known to the Java `Instance` is used instead.
+pub(crate) fn runtime_class_name(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<String> {
+    unsafe {
+        // Call the getObjectMethod. This returns a localref
+        let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        Jvm::do_return(jni_env, ())?;
+        if object.is_null() {
+            return known_class_name(instance.jinstance, jni_env);
+        }
+        let class = (opt_to_res((**jni_env).GetObjectClass)?)(jni_env, object);
+        delete_java_local_ref(jni_env, object);
+        class_name_and_delete(class, jni_env)
+    }
+}
+
+/// The name of the class that the Java `Instance` knows for its object, eg. the declared type of a retrieved field.
+pub(crate) fn known_class_name(java_instance: jobject, jni_env: *mut JNIEnv) -> errors::Result<String> {
+    unsafe {
+        // Call the getObjectClass method. This returns a localref
+        let class = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            java_instance,
+            cache::get_get_object_class_method()?,
+        );
+        let class = Jvm::do_return(jni_env, class)?;
+        class_name_and_delete(class, jni_env)
+    }
+}
+
+// The name of a class. The local reference of the class is deleted.
+fn class_name_and_delete(class: jobject, jni_env: *mut JNIEnv) -> errors::Result<String> {
+    let name = call_object_method(class, "java/lang/Class", "getName", "()Ljava/lang/String;", jni_env);
+    delete_java_local_ref(jni_env, class);
+    let name = name?;
+    let rust_name = string_from_jstring(name, jni_env);
+    delete_java_local_ref(jni_env, name);
+    rust_name
+}
+
+/// Whether the Java object that the `instance` contains is an instance of the class with the provided name.
+/// A null object is not an instance of any class.
+pub(crate) fn is_instance_of_class_name(instance: &Instance, class_name: &str, jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    let is_instance_of = opt_to_res(cache::get_is_instance_of())?;
+    unsafe {
+        // Find the class. This returns a localref
+        let cstr = utils::to_c_string(&class_name.replace('.', "/"));
+        let class = (opt_to_res((**jni_env).FindClass)?)(jni_env, cstr);
+        utils::drop_c_string(cstr);
+        let class = Jvm::do_return(jni_env, class)?;
+        // Call the getObjectMethod. This returns a localref
+        let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        let object = Jvm::do_return(jni_env, object);
+        let is_instance = match object {
+            Ok(object) if !object.is_null() => {
+                let is_instance = (is_instance_of)(jni_env, object, class) == JNI_TRUE;
+                delete_java_local_ref(jni_env, object);
+                Ok(is_instance)
+            }
+            Ok(_) => Ok(false),
+            Err(error) => Err(error),
+        };
+        delete_java_local_ref(jni_env, class);
+        is_instance
+    }
+}
+
+/// Calls a method without arguments that returns an object. The returned object is a local reference.
+pub(crate) fn call_object_method(obj: jobject, class_name: &'static str, method: &'static str, signature: &'static str, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    unsafe {
+        let result = (opt_to_res(cache::get_jni_call_object_method_a())?)(
+            jni_env,
+            obj,
+            cache::get_method_id(class_name, method, signature)?,
+            ptr::null(),
+        );
+        Jvm::do_return(jni_env, result)
+    }
+}
+
+/// Calls a static method that returns an object. The returned object is a local reference.
+pub(crate) fn call_static_object_method(class_name: &'static str, method: &'static str, signature: &'static str, args: &[jvalue], jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    unsafe {
+        let result = (opt_to_res(cache::get_jni_call_static_object_method_a())?)(
+            jni_env,
+            cache::get_class(class_name)?,
+            cache::get_static_method_id(class_name, method, signature)?,
+            args.as_ptr(),
+        );
+        Jvm::do_return(jni_env, result)
+    }
+}
+
+/// Calls a method that returns a Java primitive, using the cached JNI function of `$jni_call` (e.g. `get_jni_call_int_method_a`).
+///
+/// Evaluates to the primitive and returns the error from the enclosing function if the call fails.
+macro_rules! call_primitive_method {
+    ($obj:expr, $class_name:expr, $method:expr, $signature:expr, $jni_call:ident, $jni_env:expr) => {
+        $crate::jni_utils::call_primitive_method!($obj, $class_name, $method, $signature, $jni_call, [], $jni_env)
+    };
+    ($obj:expr, $class_name:expr, $method:expr, $signature:expr, $jni_call:ident, $args:expr, $jni_env:expr) => {{
+        let jni_env: *mut jni_sys::JNIEnv = $jni_env;
+        let method_id = $crate::cache::get_method_id($class_name, $method, $signature)?;
+        let args: &[jni_sys::jvalue] = &$args;
+        let result = unsafe {
+            ($crate::errors::opt_to_res($crate::cache::$jni_call())?)(jni_env, $obj, method_id, args.as_ptr())
+        };
+        $crate::Jvm::do_return(jni_env, result)?
+    }};
+}
+
+pub(crate) use call_primitive_method;
+
+pub(crate) fn is_assignable_to_throwable(class: jclass, jni_env: *mut JNIEnv) -> errors::Result<bool> {
+    unsafe {
+        let ias = opt_to_res((**jni_env).IsAssignableFrom)?;
+        Ok((ias)(jni_env, class, cache::get_throwable_class()?) == JNI_TRUE)
+    }
+}
+
 pub(crate) fn is_same_object(obj1: jobject, obj2: jobject, jni_env: *mut JNIEnv) -> errors::Result<bool> {
     unsafe {
         let b = (opt_to_res(cache::get_is_same_object())?)(
@@ -453,4 +860,71 @@ pub(crate) fn is_same_object(obj1: jobject, obj2: jobject, jni_env: *mut JNIEnv)
         );
         Ok(b == JNI_TRUE)
     }
-}
\ No newline at end of file
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Executes the function `f`, catching any panic that may occur.
+///
+/// A panic is thrown in the Java world as an `InvocationException` that contains the panic message and location, and `None` is returned.
+///
+/// Rust code that is called by Java should be executed using this function, because unwinding into the JVM is undefined behaviour.
+pub fn catch_unwind_into_java_exception<F, R>(jni_env: *mut JNIEnv, f: F) -> Option<R> where F: FnOnce() -> R {
+    install_panic_hook();
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(r) => Some(r),
+        Err(payload) => {
+            let message = match cache::take_panic_location() {
+                Some(location) => format!("Rust panicked at {}: {}", location, panic_message(payload.as_ref())),
+                None => format!("Rust panicked: {}", panic_message(payload.as_ref())),
+            };
+            error(&message);
+            if let Err(error_while_throwing) = throw_invocation_exception_without_cache(&message, jni_env) {
+                error(&format!("Could not throw the panic to Java: {}", error_while_throwing));
+            }
+            None
+        }
+    }
+}
+
+// Records the location of panics, before delegating to the previously defined hook.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            cache::set_panic_location(info.location().map(|location| location.to_string()));
+            previous_hook(info);
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    }
+}
+
+// Throws an `InvocationException` using only the JNIEnv, because a panic may occur before the cache is initialized.
+// If an exception is already pending, it is kept.
+fn throw_invocation_exception_without_cache(message: &str, jni_env: *mut JNIEnv) -> errors::Result<()> {
+    unsafe {
+        if (opt_to_res((**jni_env).ExceptionCheck)?)(jni_env) == JNI_TRUE {
+            return Ok(());
+        }
+        let class = tweaks::find_class(jni_env, "org/astonbitecode/j4rs/errors/InvocationException")?;
+        if class.is_null() {
+            return Err(errors::J4RsError::JavaError("Could not find the InvocationException class".to_string()));
+        }
+        let message_jstring = utils::to_c_string_struct(message);
+        (opt_to_res((**jni_env).ThrowNew)?)(jni_env, class, message_jstring.as_ptr());
+        // The class may be a cached global ref. Only a local ref is deleted.
+        if (opt_to_res((**jni_env).GetObjectRefType)?)(jni_env, class) as jint == jobjectRefType::JNILocalRefType as jint {
+            delete_java_local_ref(jni_env, class);
+        }
+        Ok(())
+    }
+}
@@ -17,14 +17,19 @@ extern crate lazy_static;
 extern crate libc;
 #[macro_use]
 extern crate log;
-extern crate serde;
+// Re-exported in the prelude, for the code that is generated by the derive macros of j4rs_derive
+#[doc(hidden)]
+pub extern crate serde;
 extern crate serde_json;
+// Lets the tests compile the `j4rs::` paths of the generated bindings
+#[cfg(test)]
+extern crate self as j4rs;
 
 use std::mem;
 use std::os::raw::c_void;
 use std::sync::mpsc::Sender;
 
-use jni_sys::{jlong, JNIEnv, jobject};
+use jni_sys::{jlong, JNIEnv, jobject, jobjectArray, jstring};
 pub use jni_sys as jni_sys;
 
 pub use self::api::Callback as Callback;
@@ -37,18 +42,52 @@ pub use self::api::Jvm as Jvm;
 pub use self::api::JvmBuilder as JvmBuilder;
 pub use self::api::Null as Null;
 pub use self::api_tweaks::{get_created_java_vms, set_java_vm};
+pub use self::arrays::JavaPrimitive as JavaPrimitive;
+pub use self::bindings::BindingsGenerator as BindingsGenerator;
+pub use self::buffers::DirectBufferMemory as DirectBufferMemory;
+pub use self::buffers::DirectByteBuffer as DirectByteBuffer;
+pub use self::codec::Codec as Codec;
+pub use self::enums::JavaEnum as JavaEnum;
+pub use self::jni_utils::catch_unwind_into_java_exception as catch_unwind_into_java_exception;
+pub use self::jni_utils::jbyte_array_to_rust_vec as jbyte_array_to_rust_vec;
 pub use self::jni_utils::jstring_to_rust_string as jstring_to_rust_string;
+pub use self::jni_utils::rust_bytes_to_jbyte_array as rust_bytes_to_jbyte_array;
+pub use self::jni_utils::rust_string_to_jstring as rust_string_to_jstring;
+pub use self::natives::JavaNativeMethod as JavaNativeMethod;
+pub use self::numbers::BigDecimal as BigDecimal;
+pub use self::numbers::BigInteger as BigInteger;
+pub use self::objects::JavaObject as JavaObject;
+pub use self::objects::JavaObjectReader as JavaObjectReader;
+pub use self::objects::JavaObjectWriter as JavaObjectWriter;
+pub use self::onload::JniOnLoadHook as JniOnLoadHook;
+pub use self::onload::on_jni_load as on_jni_load;
 pub use self::provisioning::LocalJarArtifact as LocalJarArtifact;
 pub use self::provisioning::MavenArtifact as MavenArtifact;
 pub use self::provisioning::MavenArtifactRepo as MavenArtifactRepo;
 pub use self::provisioning::MavenSettings as MavenSettings;
+pub use self::reflection::ClassDescription as ClassDescription;
+pub use self::reflection::FieldDescription as FieldDescription;
+pub use self::reflection::MethodDescription as MethodDescription;
 
 mod api;
+mod arrays;
+mod bindings;
+mod buffers;
+mod codec;
+mod datetime;
+mod enums;
 pub(crate) mod api_tweaks;
 pub mod errors;
 mod jni_utils;
 mod logger;
+mod natives;
+mod numbers;
+mod objects;
+mod onload;
 mod provisioning;
+mod proxy;
+mod reflection;
+mod serde_jni;
 mod utils;
 mod cache;
 pub mod prelude;
@@ -63,39 +102,79 @@ pub fn new_jvm(classpath_entries: Vec<ClasspathEntry>, java_opts: Vec<JavaOpt>)
 }
 
 #[no_mangle]
-pub extern fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRustChannelSupport_docallbacktochannel(_jni_env: *mut JNIEnv, _class: *const c_void, ptr_address: jlong, java_instance: jobject) {
-    let mut jvm = Jvm::attach_thread().expect("Could not create a j4rs Jvm while invoking callback to channel.");
-    jvm.detach_thread_on_drop(false);
-    let instance_res = Instance::from_jobject_with_global_ref(java_instance);
-    if let Ok(instance) = instance_res {
-        let p = ptr_address as *mut Sender<Instance>;
-        let tx = unsafe { Box::from_raw(p) };
-
-        let result = tx.send(instance);
-        mem::forget(tx);
-        if let Err(error) = result {
-            panic!("Could not send to the defined callback channel: {:?}", error);
-        }
-    } else {
-        panic!("Could not create Rust Instance from the Java Instance object...");
-    }
+pub extern "system" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRustChannelSupport_docallbacktochannel(jni_env: *mut JNIEnv, _class: *const c_void, ptr_address: jlong, java_instance: jobject) {
+    // Panics are thrown to Java instead of unwinding into the JVM
+    let _ = catch_unwind_into_java_exception(jni_env, || {
+        let mut jvm = Jvm::attach_thread().expect("Could not create a j4rs Jvm while invoking callback to channel.");
+        jvm.detach_thread_on_drop(false);
+        let instance_res = Instance::from_jobject_with_global_ref(java_instance);
+        if let Ok(instance) = instance_res {
+            let p = ptr_address as *mut Sender<Instance>;
+            let tx = unsafe { Box::from_raw(p) };
+
+            let result = tx.send(instance);
+            mem::forget(tx);
+            if let Err(error) = result {
+                panic!("Could not send to the defined callback channel: {:?}", error);
+            }
+        } else {
+            panic!("Could not create Rust Instance from the Java Instance object...");
+        }
+    });
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_astonbitecode_j4rs_api_invocation_RustInvocationHandler_invokeRust(jni_env: *mut JNIEnv, _class: *const c_void, invoke_fn_address: jlong, closure_address: jlong, method_name: jstring, args: jobjectArray) -> jobject {
+    proxy::invoke(jni_env, invoke_fn_address, closure_address, method_name, args)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_astonbitecode_j4rs_api_invocation_RustInvocationHandler_releaseRust(jni_env: *mut JNIEnv, _class: *const c_void, release_fn_address: jlong, closure_address: jlong) {
+    proxy::release(jni_env, release_fn_address, closure_address)
+}
+
+/// Initializes j4rs when the library is loaded by Java (`System.loadLibrary`). Enabled with the `jni_onload` feature.
+///
+/// See `on_jni_load`.
+#[cfg(feature = "jni_onload")]
+#[no_mangle]
+pub extern "system" fn JNI_OnLoad(java_vm: *mut jni_sys::JavaVM, _reserved: *mut c_void) -> jni_sys::jint {
+    on_jni_load(java_vm)
 }
 
 #[cfg(test)]
 mod lib_unit_tests {
     use std::{thread, time};
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
     use std::convert::TryFrom;
+    use std::os::raw::c_void;
     use std::path::MAIN_SEPARATOR;
+    use std::ptr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::thread::JoinHandle;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     use fs_extra::remove_items;
-
-    use crate::{LocalJarArtifact, MavenArtifactRepo, MavenSettings, Null};
+    use serde::{Deserialize, Serialize};
+    use serde::de::DeserializeOwned;
+    use jni_sys::{jint, JNI_VERSION_1_6, JNIEnv, jstring};
+    use j4rs_derive::{call_from_java, java_class, JavaEnum, JavaObject};
+
+    use crate::{BigDecimal, BigInteger, BindingsGenerator, cache, Codec, errors, Instance, JavaEnum, JavaNativeMethod, JavaObject, JavaObjectReader, JavaObjectWriter, JavaPrimitive, JniOnLoadHook, LocalJarArtifact, MavenArtifactRepo, MavenSettings, Null};
+    use crate::errors::J4RsError;
+    // The code that is generated by the macros of j4rs_derive uses the prelude
+    use crate::prelude::*;
     use crate::provisioning::JavaArtifact;
 
     use super::{ClasspathEntry, InvocationArg, Jvm, JvmBuilder, MavenArtifact};
     use super::utils::jassets_path;
 
+    /// The bindings that `BindingsGenerator` generates for `MyTest` and `DummyBean`
+    mod test_bindings {
+        include!("test_bindings.rs");
+    }
+
     #[test]
     fn create_instance_and_invoke() {
         let jvm: Jvm = JvmBuilder::new()
@@ -807,4 +886,1215 @@ mod lib_unit_tests {
         let i = jvm.invoke(&test_instance, "echo", &vec![InvocationArg::try_from(33_i64).unwrap()]).unwrap();
         let _: Box<i64> = jvm.to_rust_boxed(i).unwrap();
     }
+
+    #[test]
+    fn java_exception_details() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        let res = jvm.invoke(&test_instance, "throwFileNotFound", &[InvocationArg::try_from("/a/path").unwrap()]);
+        match res {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.class_name(), "org.astonbitecode.j4rs.errors.InvocationException");
+                assert!(!throwable.stack_trace().is_empty());
+                let root_cause = throwable.root_cause();
+                assert_eq!(root_cause.class_name(), "java.io.FileNotFoundException");
+                assert_eq!(root_cause.message(), Some("/a/path"));
+                assert!(root_cause.stack_trace()[0].contains("MyTest.throwFileNotFound"));
+            }
+            other => panic!("Expected a JavaException, found {:?}", other.map(|_| ())),
+        }
+
+        let res = jvm.invoke_static("java.lang.Integer", "parseInt", &[InvocationArg::try_from("NaN").unwrap()]);
+        match res {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.root_cause().class_name(), "java.lang.NumberFormatException");
+            }
+            other => panic!("Expected a JavaException, found {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn java_exception_instance() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<J4RsError>();
+
+        let jvm = JvmBuilder::new().build().unwrap();
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        let res = jvm.invoke(&test_instance, "throwMyTestException", &[InvocationArg::try_from(33_i32).unwrap()]);
+        match res {
+            Err(J4RsError::JavaException(throwable)) => {
+                let root_cause = throwable.root_cause();
+                assert_eq!(root_cause.class_name(), "org.astonbitecode.j4rs.tests.MyTestException");
+                let exception_instance = root_cause.instance().unwrap();
+                assert_eq!(exception_instance.class_name(), "org.astonbitecode.j4rs.tests.MyTestException");
+                let error_code_instance = jvm.invoke(&exception_instance, "getErrorCode", &[]).unwrap();
+                let error_code: i32 = jvm.to_rust(error_code_instance).unwrap();
+                assert_eq!(error_code, 33);
+
+                let top_instance = throwable.instance().unwrap();
+                let message_instance = jvm.invoke(&top_instance, "getMessage", &[]).unwrap();
+                let message: String = jvm.to_rust(message_instance).unwrap();
+                assert_eq!(Some(message.as_str()), throwable.message());
+            }
+            other => panic!("Expected a JavaException, found {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn pending_exceptions_survive_dropping_instances() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        let instance = jvm.create_instance("java.lang.String", &[]).unwrap();
+
+        jvm.throw_invocation_exception("Pending").unwrap();
+        drop(instance);
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_err());
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_ok());
+    }
+
+    #[test]
+    fn throw_java_exceptions() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        assert!(jvm.throw_exception("java.lang.IllegalArgumentException", "Illegal!").is_ok());
+        // An already pending exception is not replaced
+        assert!(jvm.throw_invocation_exception("Another one").is_ok());
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.class_name(), "java.lang.IllegalArgumentException");
+                assert_eq!(throwable.message(), Some("Illegal!"));
+            }
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        let cause = jvm.create_instance("java.io.FileNotFoundException", &[InvocationArg::try_from("/a/path").unwrap()]).unwrap();
+        assert!(jvm.throw_exception_with_cause("java.lang.IllegalStateException", "Illegal state!", &cause).is_ok());
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.class_name(), "java.lang.IllegalStateException");
+                assert_eq!(throwable.cause().unwrap().class_name(), "java.io.FileNotFoundException");
+            }
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        assert!(jvm.throw(&cause).is_ok());
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => assert_eq!(throwable.message(), Some("/a/path")),
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        let not_a_throwable = jvm.create_instance("java.lang.String", &[]).unwrap();
+        assert!(jvm.throw(&not_a_throwable).is_err());
+        assert!(jvm.throw_exception("java.lang.String", "Not a Throwable").is_err());
+        assert!(jvm.throw_exception("not.existing.Exception", "Not existing").is_err());
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_ok());
+    }
+
+    #[test]
+    fn panic_into_java_exception() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let res: Option<i32> = super::catch_unwind_into_java_exception(jvm.jni_env, || panic!("Panic from {}", "Rust"));
+        assert!(res.is_none());
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.class_name(), "org.astonbitecode.j4rs.errors.InvocationException");
+                let message = throwable.message().unwrap();
+                assert!(message.contains("Panic from Rust"));
+                assert!(message.contains("lib.rs"));
+            }
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        let res = super::catch_unwind_into_java_exception(jvm.jni_env, || 33);
+        assert_eq!(res, Some(33));
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_ok());
+    }
+
+    #[test]
+    fn jni_conversions() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let java_string = super::rust_string_to_jstring(&jvm, "A String with ü").unwrap();
+        assert_eq!(super::jstring_to_rust_string(&jvm, java_string).unwrap(), "A String with ü");
+        assert!(super::jstring_to_rust_string(&jvm, std::ptr::null_mut()).is_err());
+
+        let bytes = vec![0u8, 1, 127, 128, 255];
+        let java_array = super::rust_bytes_to_jbyte_array(&jvm, &bytes).unwrap();
+        assert_eq!(super::jbyte_array_to_rust_vec(&jvm, java_array).unwrap(), bytes);
+        let empty = super::rust_bytes_to_jbyte_array(&jvm, &[]).unwrap();
+        assert!(super::jbyte_array_to_rust_vec(&jvm, empty).unwrap().is_empty());
+        assert!(super::jbyte_array_to_rust_vec(&jvm, std::ptr::null_mut()).is_err());
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.checkedAdd")]
+    fn checked_add(a: i32, b: i32) -> Result<i32, String> {
+        a.checked_add(b).ok_or_else(|| "Overflow".to_string())
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.describe")]
+    fn describe(name: String, flag: bool, count: i64, ratio: f64) -> String {
+        format!("{} {} {} {}", name, flag, count, ratio)
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.reverse_bytes")]
+    fn reverse_bytes(mut bytes: Vec<u8>) -> Vec<u8> {
+        bytes.reverse();
+        bytes
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.checkPositive")]
+    fn check_positive(value: i32) -> Result<(), String> {
+        if value > 0 { Ok(()) } else { Err(format!("{} is not positive", value)) }
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.echoInstance")]
+    fn echo_instance(instance: Instance) -> Instance {
+        instance
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.panicking")]
+    fn panicking() {
+        panic!("Panic from a call_from_java function");
+    }
+
+    #[test]
+    fn call_from_java_functions() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        let class = ptr::null();
+
+        assert_eq!(Java_org_astonbitecode_j4rs_tests_MyTest_checkedAdd(jvm.jni_env, class, 30, 3), 33);
+        // Errors are thrown to Java
+        assert_eq!(Java_org_astonbitecode_j4rs_tests_MyTest_checkedAdd(jvm.jni_env, class, i32::MAX, 1), 0);
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => assert_eq!(throwable.message(), Some("Overflow")),
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        let name = super::rust_string_to_jstring(&jvm, "j4rs").unwrap();
+        let described = Java_org_astonbitecode_j4rs_tests_MyTest_describe(jvm.jni_env, class, name, JNI_TRUE, 3, 0.5);
+        assert_eq!(super::jstring_to_rust_string(&jvm, described).unwrap(), "j4rs true 3 0.5");
+        // Null Strings and arrays are thrown to Java as NullPointerExceptions
+        let described = Java_org_astonbitecode_j4rs_tests_MyTest_describe(jvm.jni_env, class, ptr::null_mut(), JNI_TRUE, 3, 0.5);
+        assert!(described.is_null());
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => {
+                assert_eq!(throwable.class_name(), "java.lang.NullPointerException");
+                assert_eq!(throwable.message(), Some("The argument name of org.astonbitecode.j4rs.tests.MyTest.describe is null"));
+            }
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+        assert!(Java_org_astonbitecode_j4rs_tests_MyTest_reverse_1bytes(jvm.jni_env, class, ptr::null_mut()).is_null());
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_err());
+
+        // Results without a value are returned as void
+        Java_org_astonbitecode_j4rs_tests_MyTest_checkPositive(jvm.jni_env, class, 1);
+        assert!(Jvm::do_return(jvm.jni_env, ()).is_ok());
+        Java_org_astonbitecode_j4rs_tests_MyTest_checkPositive(jvm.jni_env, class, -1);
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => assert_eq!(throwable.message(), Some("-1 is not positive")),
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+
+        // Underscores are escaped in the names of the jni functions
+        let bytes = super::rust_bytes_to_jbyte_array(&jvm, &[1, 2, 255]).unwrap();
+        let reversed = Java_org_astonbitecode_j4rs_tests_MyTest_reverse_1bytes(jvm.jni_env, class, bytes);
+        assert_eq!(super::jbyte_array_to_rust_vec(&jvm, reversed).unwrap(), vec![255, 2, 1]);
+
+        let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("echo").unwrap()]).unwrap();
+        let echoed = Java_org_astonbitecode_j4rs_tests_MyTest_echoInstance(jvm.jni_env, class, instance.jinstance);
+        let echoed = Instance::from_jobject(echoed).unwrap();
+        assert_eq!(jvm.to_rust::<String>(echoed).unwrap(), "echo");
+
+        Java_org_astonbitecode_j4rs_tests_MyTest_panicking(jvm.jni_env, class);
+        match Jvm::do_return(jvm.jni_env, ()) {
+            Err(J4RsError::JavaException(throwable)) => assert!(throwable.message().unwrap().contains("Panic from a call_from_java function")),
+            other => panic!("Expected a JavaException, found {:?}", other),
+        }
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.nativeAdd", register_natives)]
+    fn native_add_ints(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.nativeAdd", register_natives)]
+    fn native_add_strings(a: String, b: String) -> String {
+        format!("{}{}", a, b)
+    }
+
+    #[test]
+    fn register_natives() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        jvm.register_natives("org.astonbitecode.j4rs.tests.MyTest").unwrap();
+
+        let sum = jvm.invoke_static(
+            "org.astonbitecode.j4rs.tests.MyTest",
+            "addNatively",
+            &[InvocationArg::try_from(30).unwrap(), InvocationArg::try_from(3).unwrap()]).unwrap();
+        let sum: i32 = jvm.to_rust(sum).unwrap();
+        assert_eq!(sum, 33);
+
+        let concatenated = jvm.invoke_static(
+            "org.astonbitecode.j4rs.tests.MyTest",
+            "addNatively",
+            &[InvocationArg::try_from("j4").unwrap(), InvocationArg::try_from("rs").unwrap()]).unwrap();
+        let concatenated: String = jvm.to_rust(concatenated).unwrap();
+        assert_eq!(concatenated, "j4rs");
+
+        assert!(jvm.register_natives("java.lang.String").is_err());
+        assert!(jvm.register_all_natives().is_ok());
+    }
+
+    static JNI_ONLOAD_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+    fn jni_onload_hook(jvm: &Jvm) -> errors::Result<()> {
+        jvm.create_instance("java.lang.String", &[])?;
+        JNI_ONLOAD_HOOK_CALLED.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    inventory::submit! {
+        JniOnLoadHook::new(jni_onload_hook)
+    }
+
+    #[test]
+    fn on_jni_load() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        let mut java_vm = ptr::null_mut();
+        unsafe {
+            ((**jvm.jni_env).GetJavaVM.unwrap())(jvm.jni_env, &mut java_vm);
+        }
+
+        assert_eq!(super::on_jni_load(java_vm), JNI_VERSION_1_6);
+        assert!(JNI_ONLOAD_HOOK_CALLED.load(Ordering::SeqCst));
+        // The classes that are found from now on are cached for all the threads
+        assert!(crate::api_tweaks::class_caching());
+        // The native methods are registered
+        let sum = jvm.invoke_static(
+            "org.astonbitecode.j4rs.tests.MyTest",
+            "addNatively",
+            &[InvocationArg::try_from(1).unwrap(), InvocationArg::try_from(2).unwrap()]).unwrap();
+        let sum: i32 = jvm.to_rust(sum).unwrap();
+        assert_eq!(sum, 3);
+        // The JavaVM is retrieved for new threads
+        let handle = thread::spawn(|| {
+            let jvm = Jvm::attach_thread().unwrap();
+            let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("from thread").unwrap()]).unwrap();
+            jvm.to_rust::<String>(instance).unwrap()
+        });
+        assert_eq!(handle.join().unwrap(), "from thread");
+    }
+
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn proxy() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let function = jvm.proxy("java.util.function.Function", |method_name, args| {
+            assert_eq!(method_name, "apply");
+            let jvm = Jvm::attach_thread()?;
+            let arg: String = jvm.to_rust(args.into_iter().next().unwrap())?;
+            Instance::try_from(InvocationArg::try_from(format!("{} from Rust", arg))?)
+        }).unwrap();
+        let result = jvm.invoke(&function, "apply", &[InvocationArg::try_from("Hello").unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(result).unwrap(), "Hello from Rust");
+        // The Object methods are implemented in Java
+        let to_string = jvm.invoke(&jvm.cast(&function, "java.lang.Object").unwrap(), "toString", &[]).unwrap();
+        assert!(jvm.to_rust::<String>(to_string).unwrap().starts_with("RustProxy(java.util.function.Function)"));
+
+        // Primitive return values and calls from Java
+        let comparator = jvm.proxy("java.util.Comparator", |_, args| {
+            let jvm = Jvm::attach_thread()?;
+            let args: Vec<i32> = args.into_iter().map(|arg| jvm.to_rust(arg)).collect::<errors::Result<_>>()?;
+            Instance::try_from(InvocationArg::try_from(args[1] - args[0])?)
+        }).unwrap();
+        let list = jvm.create_java_list("java.lang.Integer", &[
+            InvocationArg::try_from(2).unwrap(),
+            InvocationArg::try_from(3).unwrap(),
+            InvocationArg::try_from(1).unwrap()]).unwrap();
+        let list = jvm.create_instance("java.util.ArrayList", &[InvocationArg::from(list)]).unwrap();
+        let _ = jvm.invoke(&list, "sort", &[InvocationArg::from(comparator)]).unwrap();
+        let sorted: Vec<i32> = jvm.to_rust(list).unwrap();
+        assert_eq!(sorted, vec![3, 2, 1]);
+
+        // Errors are thrown to Java
+        let failing = jvm.proxy("java.util.function.Supplier", |_, _| {
+            Err(J4RsError::GeneralError("Supplier failure".to_string()))
+        }).unwrap();
+        let error = jvm.invoke(&failing, "get", &[]).err().unwrap();
+        assert!(format!("{}", error).contains("Supplier failure"));
+
+        // Only interfaces can be proxied
+        assert!(jvm.proxy("java.lang.String", |_, _| Err(J4RsError::GeneralError("".to_string()))).is_err());
+
+        // The closure is dropped after the proxy is garbage collected
+        let dropped = Arc::new(AtomicBool::new(false));
+        let drop_flag = DropFlag(dropped.clone());
+        let runnable = jvm.proxy("java.lang.Runnable", move |_, _| {
+            let _ = &drop_flag;
+            Instance::try_from(InvocationArg::try_from(Null::Of("java.lang.Object"))?)
+        }).unwrap();
+        let _ = jvm.invoke(&runnable, "run", &[]).unwrap();
+        drop(runnable);
+        for _ in 0..50 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+            let _ = jvm.invoke_static("java.lang.System", "gc", &[]).unwrap();
+            thread::sleep(time::Duration::from_millis(100));
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Greeting {
+        name: String,
+        times: i32,
+    }
+
+    #[test]
+    fn java_functional_interfaces() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        // Streams
+        let list = jvm.create_java_list("java.lang.Integer", &[
+            InvocationArg::try_from(1).unwrap(),
+            InvocationArg::try_from(2).unwrap(),
+            InvocationArg::try_from(3).unwrap()]).unwrap();
+        let function = jvm.java_function(|i: i32| i * 10).unwrap();
+        let predicate = jvm.java_predicate(|i: i32| i > 10).unwrap();
+        let stream = jvm.invoke(&list, "stream", &[]).unwrap();
+        let stream = jvm.invoke(&stream, "map", &[InvocationArg::from(function)]).unwrap();
+        let stream = jvm.invoke(&stream, "filter", &[InvocationArg::from(predicate)]).unwrap();
+        let collector = jvm.invoke_static("java.util.stream.Collectors", "toList", &[]).unwrap();
+        let collected = jvm.invoke(&stream, "collect", &[InvocationArg::from(collector)]).unwrap();
+        let collected: Vec<i32> = jvm.to_rust(collected).unwrap();
+        assert_eq!(collected, vec![20, 30]);
+
+        // CompletableFutures, that call the closures from Java threads
+        let (tx, rx) = std::sync::mpsc::channel();
+        let supplier = jvm.java_supplier(|| "j4rs".to_string()).unwrap();
+        let function = jvm.java_function(|s: String| s.len() as i32).unwrap();
+        let consumer = jvm.java_consumer(move |length: i32| tx.send(length).unwrap()).unwrap();
+        let future = jvm.invoke_static("java.util.concurrent.CompletableFuture", "supplyAsync", &[InvocationArg::from(supplier)]).unwrap();
+        let future = jvm.invoke(&future, "thenApplyAsync", &[InvocationArg::from(function)]).unwrap();
+        let future = jvm.invoke(&future, "thenAccept", &[InvocationArg::from(consumer)]).unwrap();
+        let future = jvm.cast(&future, "java.util.concurrent.CompletableFuture").unwrap();
+        let _ = jvm.invoke(&future, "get", &[]).unwrap();
+        assert_eq!(rx.recv_timeout(time::Duration::from_secs(10)).unwrap(), 4);
+
+        // Serializable structs
+        let bi_function = jvm.java_bi_function(|name: String, times: i32| Greeting { name, times }).unwrap();
+        let greeting = jvm.invoke(&bi_function, "apply", &[
+            InvocationArg::try_from("j4rs").unwrap(),
+            InvocationArg::try_from(3).unwrap()]).unwrap();
+        let greeting: Greeting = jvm.to_rust(greeting).unwrap();
+        assert_eq!(greeting, Greeting { name: "j4rs".to_string(), times: 3 });
+    }
+
+    fn copy_of_primitive_array<T>(jvm: &Jvm, values: &[T]) -> Vec<T> where T: JavaPrimitive + DeserializeOwned {
+        let copy = jvm.invoke_static("java.util.Arrays", "copyOf", &[
+            InvocationArg::from_primitive_array(values).unwrap(),
+            InvocationArg::try_from(values.len() as i32).unwrap().into_primitive().unwrap()]).unwrap();
+        jvm.to_rust(copy).unwrap()
+    }
+
+    #[test]
+    fn primitive_arrays() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        assert_eq!(copy_of_primitive_array(&jvm, &[true, false, true]), vec![true, false, true]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[-1i8, 0, 1]), vec![-1i8, 0, 1]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[0u8, 128, 255]), vec![0u8, 128, 255]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[97u16, 0xfc]), vec![97u16, 0xfc]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[i16::MIN, i16::MAX]), vec![i16::MIN, i16::MAX]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[i32::MIN, 0, i32::MAX]), vec![i32::MIN, 0, i32::MAX]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[i64::MIN, i64::MAX]), vec![i64::MIN, i64::MAX]);
+        assert_eq!(copy_of_primitive_array(&jvm, &[1.5f32, -2.25]), vec![1.5f32, -2.25]);
+        assert_eq!(copy_of_primitive_array::<f64>(&jvm, &[]), Vec::<f64>::new());
+        let samples: Vec<f64> = (0..1_000_000).map(|i| i as f64 / 3.0).collect();
+        assert_eq!(copy_of_primitive_array(&jvm, &samples), samples);
+
+        // The array is passed to Java as a primitive array
+        let sum = jvm.invoke_static("java.util.stream.IntStream", "of", &[InvocationArg::from_primitive_array(&[1, 2, 3]).unwrap()]).unwrap();
+        let sum = jvm.invoke(&sum, "sum", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<i32>(sum).unwrap(), 6);
+
+        // Other types are still deserialized
+        let array = jvm.invoke_static("java.util.Arrays", "copyOf", &[
+            InvocationArg::from_primitive_array(&[1, 2]).unwrap(),
+            InvocationArg::try_from(2).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<Vec<i64>>(array).unwrap(), vec![1i64, 2]);
+    }
+
+    #[test]
+    fn direct_byte_buffers() {
+        let jvm = JvmBuilder::new().build().unwrap();
+        let index = |i: i32| InvocationArg::try_from(i).unwrap().into_primitive().unwrap();
+
+        // Borrowed memory
+        let mut frame = vec![1u8, 2, 3, 4];
+        {
+            // Java does not keep the ByteBuffer
+            let byte_buffer = unsafe { jvm.direct_byte_buffer(&mut frame[..]) }.unwrap();
+            let capacity = jvm.invoke(byte_buffer.instance(), "capacity", &[]).unwrap();
+            assert_eq!(jvm.to_rust::<i32>(capacity).unwrap(), 4);
+            let byte = jvm.invoke(byte_buffer.instance(), "get", &[index(3)]).unwrap();
+            assert_eq!(jvm.to_rust::<i8>(byte).unwrap(), 4);
+            let _ = jvm.invoke(byte_buffer.instance(), "put", &[index(0), InvocationArg::try_from(42i8).unwrap().into_primitive().unwrap()]).unwrap();
+        }
+        assert_eq!(frame, vec![42, 2, 3, 4]);
+
+        // Owned memory
+        let mut byte_buffer = unsafe { jvm.direct_byte_buffer(vec![0u8; 8]) }.unwrap();
+        byte_buffer.as_mut_slice()[1] = 7;
+        let java_buffer = jvm.invoke_static("java.nio.ByteBuffer", "allocateDirect", &[index(8)]).unwrap();
+        let _ = jvm.invoke(&java_buffer, "put", &[InvocationArg::try_from(&byte_buffer).unwrap()]).unwrap();
+        assert_eq!(byte_buffer.into_inner(), vec![0, 7, 0, 0, 0, 0, 0, 0]);
+
+        // Memory owned by Java
+        let mut java_buffer = java_buffer;
+        unsafe {
+            assert_eq!(jvm.direct_buffer_slice(&java_buffer).unwrap(), &[0, 7, 0, 0, 0, 0, 0, 0]);
+            jvm.direct_buffer_slice_mut(&mut java_buffer).unwrap()[7] = 9;
+        }
+        let byte = jvm.invoke(&java_buffer, "get", &[index(7)]).unwrap();
+        assert_eq!(jvm.to_rust::<i8>(byte).unwrap(), 9);
+
+        // Heap buffers do not expose their memory
+        let heap_buffer = jvm.invoke_static("java.nio.ByteBuffer", "allocate", &[index(8)]).unwrap();
+        assert!(unsafe { jvm.direct_buffer_slice(&heap_buffer) }.is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Telemetry {
+        id: u64,
+        label: String,
+        ratio: f32,
+        readings: Vec<f64>,
+        tags: Option<Vec<String>>,
+        greeting: Greeting,
+    }
+
+    #[test]
+    fn codecs() {
+        let mut jvm = JvmBuilder::new().with_codec(Codec::Cbor).build().unwrap();
+        assert_eq!(jvm.codec(), Codec::Cbor);
+
+        let telemetry = Telemetry {
+            id: u64::MAX,
+            label: "sensor \u{1F321}".to_string(),
+            ratio: 0.5,
+            readings: vec![0.1, -273.15, 1e300],
+            tags: None,
+            greeting: Greeting { name: "j4rs".to_string(), times: -70000 },
+        };
+        for codec in &[Codec::Json, Codec::Cbor, Codec::MessagePack] {
+            jvm.set_codec(*codec);
+            let arg = jvm.create_invocation_arg(&telemetry, "java.util.Map").unwrap();
+            let map = jvm.create_instance("java.util.HashMap", &[arg]).unwrap();
+            let label = jvm.invoke(&map, "get", &[InvocationArg::try_from("label").unwrap()]).unwrap();
+            assert_eq!(jvm.to_rust::<String>(label).unwrap(), telemetry.label);
+            let telemetry_from_java: Telemetry = jvm.to_rust(map).unwrap();
+            assert_eq!(telemetry_from_java, telemetry);
+        }
+
+        // The codec of an argument does not depend on the Jvm
+        jvm.set_codec(Codec::Json);
+        let arg = InvocationArg::new_with_codec(&vec![1, 2, 3], "java.util.List", Codec::MessagePack).unwrap();
+        let list = jvm.create_instance("java.util.ArrayList", &[arg]).unwrap();
+        let size = jvm.invoke(&list, "size", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<i32>(size).unwrap(), 3);
+
+        // Basic types are not serialized
+        let arg = InvocationArg::new_with_codec(&"j4rs".to_string(), "java.lang.String", Codec::Cbor).unwrap();
+        assert!(matches!(arg, InvocationArg::RustBasic { .. }));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct DummyBean {
+        first_name: String,
+        count: i32,
+        tags: Vec<String>,
+        weights: Vec<f64>,
+        unit: Option<String>,
+        child: Option<Box<DummyBean>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rectangle { width: i32, height: i32 },
+    }
+
+    #[test]
+    fn jni_codec_primitives() {
+        let jvm = JvmBuilder::new().with_codec(Codec::Jni).build().unwrap();
+        let my_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        let arg = jvm.create_invocation_arg(&-7_i8, "java.lang.Byte").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust::<i8>(echoed).unwrap(), -7);
+        let arg = jvm.create_invocation_arg(&i64::MIN, "java.lang.Long").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust::<i64>(echoed).unwrap(), i64::MIN);
+        let arg = jvm.create_invocation_arg(&-273.15_f64, "java.lang.Double").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust::<f64>(echoed).unwrap(), -273.15);
+        let arg = jvm.create_invocation_arg(&true, "java.lang.Boolean").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert!(jvm.to_rust::<bool>(echoed).unwrap());
+        let arg = jvm.create_invocation_arg(&'\u{1F321}', "java.lang.String").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust::<char>(echoed).unwrap(), '\u{1F321}');
+        let arg = jvm.create_invocation_arg(&"j4rs".to_string(), "java.lang.String").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(echoed).unwrap(), "j4rs");
+    }
+
+    #[test]
+    fn jni_codec_nested_structs() {
+        let jvm = JvmBuilder::new().with_codec(Codec::Jni).build().unwrap();
+        let my_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        // Structs are created as the expected class, using its setters or fields
+        let bean = DummyBean {
+            first_name: "j4rs".to_string(),
+            count: 3,
+            tags: vec!["a".to_string(), "b".to_string()],
+            weights: vec![0.5, -1.25],
+            unit: Some("SECONDS".to_string()),
+            child: Some(Box::new(DummyBean {
+                first_name: "child".to_string(),
+                count: -1,
+                tags: vec![],
+                weights: vec![],
+                unit: None,
+                child: None,
+            })),
+        };
+        let arg = jvm.create_invocation_arg(&bean, "org.astonbitecode.j4rs.tests.DummyBean").unwrap();
+        assert!(matches!(arg, InvocationArg::RustBasic { .. }));
+        let bean_instance = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        let bean_instance = jvm.cast(&bean_instance, "org.astonbitecode.j4rs.tests.DummyBean").unwrap();
+        let first_name = jvm.invoke(&bean_instance, "getFirstName", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(first_name).unwrap(), "j4rs");
+        let child = jvm.invoke(&bean_instance, "getChild", &[]).unwrap();
+        let child_first_name = jvm.invoke(&child, "getFirstName", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(child_first_name).unwrap(), "child");
+        let bean_from_java: DummyBean = jvm.to_rust(bean_instance).unwrap();
+        assert_eq!(bean_from_java, bean);
+
+        // Without an expected class, structs become Maps
+        let telemetry = Telemetry {
+            id: 42,
+            label: "sensor \u{1F321}".to_string(),
+            ratio: 0.5,
+            readings: vec![0.1, -273.15, 1e300],
+            tags: Some(vec!["outdoor".to_string()]),
+            greeting: Greeting { name: "j4rs".to_string(), times: -70000 },
+        };
+        let arg = jvm.create_invocation_arg(&telemetry, "java.util.Map").unwrap();
+        let map = jvm.create_instance("java.util.HashMap", &[arg]).unwrap();
+        let label = jvm.invoke(&map, "get", &[InvocationArg::try_from("label").unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(label).unwrap(), telemetry.label);
+        let telemetry_from_java: Telemetry = jvm.to_rust(map).unwrap();
+        assert_eq!(telemetry_from_java, telemetry);
+    }
+
+    #[test]
+    fn jni_codec_collections() {
+        let jvm = JvmBuilder::new().with_codec(Codec::Jni).build().unwrap();
+        let my_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        let shapes = vec![Shape::Point, Shape::Circle(1.5), Shape::Rectangle { width: 2, height: 3 }];
+        let arg = jvm.create_invocation_arg(&shapes, "java.util.List").unwrap();
+        let list = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        let shapes_from_java: Vec<Shape> = jvm.to_rust(list).unwrap();
+        assert_eq!(shapes_from_java, shapes);
+
+        let mut counts = HashMap::new();
+        counts.insert("one".to_string(), vec![1_i32]);
+        counts.insert("none".to_string(), vec![]);
+        let arg = jvm.create_invocation_arg(&counts, "java.util.Map").unwrap();
+        let map = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        let counts_from_java: HashMap<String, Vec<i32>> = jvm.to_rust(map).unwrap();
+        assert_eq!(counts_from_java, counts);
+
+        // Java objects that were not created by Rust
+        let map = jvm.invoke(&my_test, "getMap", &[]).unwrap();
+        let map_from_java: HashMap<String, i64> = jvm.to_rust(map).unwrap();
+        assert_eq!(map_from_java.get("two"), Some(&2));
+    }
+
+    #[test]
+    fn jni_codec_null_and_option() {
+        let jvm = JvmBuilder::new().with_codec(Codec::Jni).build().unwrap();
+        let my_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        let null_integer = jvm.invoke(&my_test, "getNullInteger", &[]).unwrap();
+        assert_eq!(jvm.to_rust_deserialized::<Option<i32>>(null_integer).unwrap(), None);
+
+        let arg = jvm.create_invocation_arg(&Some(3_i32), "java.lang.Integer").unwrap();
+        let echoed = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        assert_eq!(jvm.to_rust_deserialized::<Option<i32>>(echoed).unwrap(), Some(3));
+
+        let tags: Vec<Option<String>> = vec![Some("a".to_string()), None];
+        let arg = jvm.create_invocation_arg(&tags, "java.util.List").unwrap();
+        let list = jvm.invoke(&my_test, "echo", &[arg]).unwrap();
+        let tags_from_java: Vec<Option<String>> = jvm.to_rust(list).unwrap();
+        assert_eq!(tags_from_java, tags);
+    }
+
+    #[test]
+    fn jni_codec_errors() {
+        let jvm = JvmBuilder::new().with_codec(Codec::Jni).build().unwrap();
+        let my_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+
+        // Integers that do not fit in the expected class are not truncated
+        assert!(jvm.create_invocation_arg(&u64::MAX, "java.lang.Long").is_err());
+        assert!(jvm.create_invocation_arg(&vec![1_i32, 300], "[B").is_err());
+        assert!(jvm.create_invocation_arg(&vec![2.5_f64], "[J").is_err());
+
+        let bean = DummyBean {
+            first_name: "j4rs".to_string(),
+            count: 3,
+            tags: vec![],
+            weights: vec![],
+            unit: None,
+            child: None,
+        };
+        assert!(jvm.create_invocation_arg(&bean, "org.astonbitecode.j4rs.tests.NoSuchBean").is_err());
+
+        let string = jvm.invoke(&my_test, "getMyString", &[]).unwrap();
+        assert!(jvm.to_rust::<i32>(string).is_err());
+        let null_integer = jvm.invoke(&my_test, "getNullInteger", &[]).unwrap();
+        assert!(jvm.to_rust::<i32>(null_integer).is_err());
+    }
+
+    #[test]
+    fn unsigned_and_128_bit_integers() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        // The unsigned integers are widened to the next Java type
+        let short = jvm.invoke_static("java.lang.Short", "valueOf", &[InvocationArg::try_from(u8::MAX).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u8>(short).unwrap(), u8::MAX);
+        let integer = jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from(u16::MAX).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u16>(integer).unwrap(), u16::MAX);
+        let long = jvm.invoke_static("java.lang.Long", "valueOf", &[InvocationArg::try_from(&u32::MAX).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u32>(long).unwrap(), u32::MAX);
+
+        // like the elements of the slices
+        let shorts = Instance::try_from(InvocationArg::try_from(&[u8::MAX, 0][..]).unwrap()).unwrap();
+        let short = jvm.invoke(&jvm.cast(&shorts, "java.util.List").unwrap(), "get", &[InvocationArg::try_from(0).unwrap().into_primitive().unwrap()]).unwrap();
+        let short_class = jvm.invoke(&jvm.invoke(&short, "getClass", &[]).unwrap(), "getName", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(short_class).unwrap(), "java.lang.Short");
+        assert_eq!(jvm.to_rust::<Vec<u8>>(shorts).unwrap(), vec![u8::MAX, 0]);
+        let integers = Instance::try_from(InvocationArg::try_from(&[u16::MAX, 0][..]).unwrap()).unwrap();
+        let integer = jvm.invoke(&jvm.cast(&integers, "java.util.List").unwrap(), "get", &[InvocationArg::try_from(0).unwrap().into_primitive().unwrap()]).unwrap();
+        let integer_class = jvm.invoke(&jvm.invoke(&integer, "getClass", &[]).unwrap(), "getName", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(integer_class).unwrap(), "java.lang.Integer");
+        assert_eq!(jvm.to_rust::<Vec<u16>>(integers).unwrap(), vec![u16::MAX, 0]);
+
+        // The byte[] and char[] arrays are created with from_primitive_array
+        let chars = jvm.create_instance("java.lang.String", &[InvocationArg::from_primitive_array(&[0x6a_u16, 0x34, 0xd83c, 0xdf21]).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(chars).unwrap(), "j4\u{1F321}");
+        let bytes = jvm.create_instance("java.lang.String", &[InvocationArg::from_primitive_array(&[0x6a_u8, 0x34, 0xf0, 0x9f, 0x8c, 0xa1]).unwrap(), InvocationArg::try_from("UTF-8").unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(bytes).unwrap(), "j4\u{1F321}");
+
+        // or to BigInteger
+        let big_integer = jvm.create_instance("java.math.BigInteger", &[InvocationArg::try_from("1").unwrap()]).unwrap();
+        let sum = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(u64::MAX).unwrap()]).unwrap();
+        let sum_str = jvm.invoke(&sum, "toString", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(sum_str).unwrap(), "18446744073709551616");
+        let sum = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(u64::MAX).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u128>(sum).unwrap(), u64::MAX as u128 + 1);
+        let negated = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(i128::MIN + 1).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<i128>(negated).unwrap(), i128::MIN + 2);
+        let max = jvm.invoke(&big_integer, "max", &[InvocationArg::try_from(u128::MAX).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u128>(max).unwrap(), u128::MAX);
+        let usize_arg = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(41_usize).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<usize>(usize_arg).unwrap(), 42);
+
+        // Overflows result in errors instead of truncation
+        let sum = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(u64::MAX).unwrap()]).unwrap();
+        assert!(jvm.to_rust::<u64>(sum).is_err());
+        let negative = jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from(-1).unwrap().into_primitive().unwrap()]).unwrap();
+        assert!(jvm.to_rust::<u32>(negative).is_err());
+        let long = jvm.invoke_static("java.lang.Long", "valueOf", &[InvocationArg::try_from(i64::MAX).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<u64>(long).unwrap(), i64::MAX as u64);
+    }
+
+    #[test]
+    fn big_numbers_display_and_parse() {
+        for s in ["0", "-1", "255", "256", "-340282366920938463463374607431768211456", "1606938044258990275541962092341162602522202993782792835301376"] {
+            assert_eq!(s.parse::<BigInteger>().unwrap().to_string(), s);
+        }
+        assert_eq!(BigInteger::from(-256).magnitude(), &[1, 0]);
+        assert_eq!(BigInteger::new(1, &[0, 0]).unwrap(), BigInteger::from(0));
+        assert!(BigInteger::new(0, &[1]).is_err());
+        assert_eq!(i128::try_from(&BigInteger::from(i128::MIN)).unwrap(), i128::MIN);
+        assert!(u8::try_from(&BigInteger::from(-1)).is_err());
+        assert!("1.5".parse::<BigInteger>().is_err());
+
+        // The same notation as BigDecimal.toString
+        for s in ["0", "0.00", "-12.50", "0.000001", "1E-7", "1.23E+5", "-1E+3"] {
+            assert_eq!(s.parse::<BigDecimal>().unwrap().to_string(), s);
+        }
+        let decimal = "123.4500".parse::<BigDecimal>().unwrap();
+        assert_eq!((decimal.unscaled_value().to_string(), decimal.scale()), ("1234500".to_string(), 4));
+        assert_eq!("1.2e2".parse::<BigDecimal>().unwrap().to_string(), "1.2E+2");
+        assert_ne!("1.0".parse::<BigDecimal>().unwrap(), "1.00".parse::<BigDecimal>().unwrap());
+        assert!(".".parse::<BigDecimal>().is_err());
+        assert!("1.2.3".parse::<BigDecimal>().is_err());
+        assert!("1E+-3".parse::<BigDecimal>().is_err());
+        assert!("1E++3".parse::<BigDecimal>().is_err());
+        assert!("1E-9223372036854775808".parse::<BigDecimal>().is_err());
+
+        // Floats are rejected, because they are not exact
+        assert_eq!(serde_json::from_str::<BigDecimal>("\"0.10\"").unwrap().to_string(), "0.10");
+        assert_eq!(serde_json::from_str::<BigInteger>("42").unwrap(), BigInteger::from(42));
+        assert!(serde_json::from_str::<BigDecimal>("0.1").is_err());
+    }
+
+    #[test]
+    fn big_numbers() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let a = "12345678901234567890.12345678901234567890".parse::<BigDecimal>().unwrap();
+        let b = "-0.00000000000000000001".parse::<BigDecimal>().unwrap();
+        let big_decimal = jvm.create_instance("java.math.BigDecimal", &[
+            InvocationArg::try_from(a.unscaled_value()).unwrap(),
+            InvocationArg::try_from(a.scale()).unwrap().into_primitive().unwrap(),
+        ]).unwrap();
+        let sum = jvm.invoke(&big_decimal, "add", &[InvocationArg::try_from(b).unwrap()]).unwrap();
+        let sum_str = jvm.invoke(&sum, "toString", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(sum_str).unwrap(), "12345678901234567890.12345678901234567889");
+        let sum = jvm.invoke(&big_decimal, "add", &[InvocationArg::try_from(&a).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<BigDecimal>(sum).unwrap().to_string(), "24691357802469135780.24691357802469135780");
+        let scaled = jvm.invoke(&big_decimal, "scaleByPowerOfTen", &[InvocationArg::try_from(30).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<BigDecimal>(scaled).unwrap().to_string(), "1.234567890123456789012345678901234567890E+49");
+        // The JSON codec transfers the BigDecimals as numbers
+        let tenth = jvm.create_instance("java.math.BigDecimal", &[InvocationArg::try_from("0.10").unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<f64>(tenth).unwrap(), 0.1);
+
+        let power = "-1606938044258990275541962092341162602522202993782792835301376".parse::<BigInteger>().unwrap();
+        let big_integer = jvm.create_instance("java.math.BigInteger", &[InvocationArg::try_from("1").unwrap()]).unwrap();
+        let sum = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(&power).unwrap()]).unwrap();
+        let sum = jvm.to_rust::<BigInteger>(sum).unwrap();
+        assert_eq!(sum.to_string(), "-1606938044258990275541962092341162602522202993782792835301375");
+        assert_eq!(sum.signum(), -1);
+        let zero = jvm.invoke(&big_integer, "subtract", &[InvocationArg::try_from(BigInteger::from(1)).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<BigInteger>(zero).unwrap(), BigInteger::from(0));
+
+        // Java integers are converted as well
+        let long = jvm.invoke_static("java.lang.Long", "valueOf", &[InvocationArg::try_from(i64::MIN).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<BigDecimal>(long).unwrap(), BigDecimal::from(BigInteger::from(i64::MIN)));
+    }
+
+    #[test]
+    fn java_time() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let duration = Duration::new(90061, 5);
+        let java_duration = jvm.invoke_static("java.time.Duration", "from", &[InvocationArg::try_from(duration).unwrap()]).unwrap();
+        let days = jvm.invoke(&java_duration, "toDays", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<i64>(days).unwrap(), 1);
+        assert_eq!(jvm.to_rust::<Duration>(java_duration).unwrap(), duration);
+        let negative = jvm.invoke_static("java.time.Duration", "ofSeconds", &[InvocationArg::try_from(-1_i64).unwrap().into_primitive().unwrap()]).unwrap();
+        assert!(jvm.to_rust::<Duration>(negative).is_err());
+
+        let time = UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789);
+        let instant = jvm.invoke_static("java.time.Instant", "from", &[InvocationArg::try_from(&time).unwrap()]).unwrap();
+        let instant_str = jvm.invoke(&instant, "toString", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(instant_str).unwrap(), "2020-09-13T12:26:40.123456789Z");
+        assert_eq!(jvm.to_rust::<SystemTime>(instant).unwrap(), time);
+        let before_epoch = UNIX_EPOCH - Duration::new(1, 250_000_000);
+        let instant = jvm.invoke_static("java.time.Instant", "from", &[InvocationArg::try_from(before_epoch).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<SystemTime>(instant).unwrap(), before_epoch);
+
+        // java.util.Dates have millisecond precision
+        let date = jvm.create_instance("java.util.Date", &[InvocationArg::try_from(1_600_000_000_123_i64).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<SystemTime>(date).unwrap(), UNIX_EPOCH + Duration::from_millis(1_600_000_000_123));
+        let date = jvm.create_instance("java.util.Date", &[]).unwrap();
+        let is_after = jvm.invoke(&date, "after", &[InvocationArg::from_system_time_as_date(&time).unwrap()]).unwrap();
+        assert!(jvm.to_rust::<bool>(is_after).unwrap());
+    }
+
+    #[test]
+    fn java_maps_sets_and_optionals() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let java_map = jvm.create_java_map(&[
+            (InvocationArg::try_from("one").unwrap(), InvocationArg::try_from(1).unwrap()),
+            (InvocationArg::try_from("two").unwrap(), InvocationArg::from(jvm.create_instance("java.lang.Integer", &[InvocationArg::try_from("2").unwrap()]).unwrap())),
+        ]).unwrap();
+        let size = jvm.invoke(&java_map, "size", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<i32>(size).unwrap(), 2);
+        let map: HashMap<String, i32> = jvm.to_rust(java_map).unwrap();
+        assert_eq!(map, vec![("one".to_string(), 1), ("two".to_string(), 2)].into_iter().collect());
+        let java_map = jvm.invoke_static("java.util.Collections", "unmodifiableMap", &[InvocationArg::try_from(&map).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<HashMap<String, i32>>(java_map).unwrap(), map);
+
+        let tree: BTreeMap<i64, Vec<String>> = vec![(3, vec!["c".to_string()]), (1, vec![])].into_iter().collect();
+        let java_map = jvm.invoke_static("java.util.Collections", "unmodifiableMap", &[InvocationArg::try_from(&tree).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<BTreeMap<i64, Vec<String>>>(java_map).unwrap(), tree);
+
+        let java_set = jvm.create_java_set(&[InvocationArg::try_from("a").unwrap(), InvocationArg::try_from("a").unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<HashSet<String>>(java_set).unwrap(), vec!["a".to_string()].into_iter().collect());
+        let set: BTreeSet<i32> = vec![2, 1].into_iter().collect();
+        let java_set = jvm.invoke_static("java.util.Collections", "unmodifiableSet", &[InvocationArg::try_from(&set).unwrap()]).unwrap();
+        let contains = jvm.invoke(&java_set, "contains", &[InvocationArg::try_from(2).unwrap()]).unwrap();
+        assert!(jvm.to_rust::<bool>(contains).unwrap());
+        assert_eq!(jvm.to_rust::<BTreeSet<i32>>(java_set).unwrap(), set);
+
+        let present = jvm.create_java_optional(Some(InvocationArg::try_from("value").unwrap())).unwrap();
+        let is_present = jvm.invoke(&present, "isPresent", &[]).unwrap();
+        assert!(jvm.to_rust::<bool>(is_present).unwrap());
+        assert_eq!(jvm.to_rust::<Option<String>>(present).unwrap(), Some("value".to_string()));
+        let empty = jvm.invoke_static("java.util.Optional", "empty", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<Option<String>>(empty).unwrap(), None);
+        let optional = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(Some(3_i64)).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<Option<i64>>(optional).unwrap(), Some(3));
+        let optional = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(None::<i64>).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<Option<i64>>(optional).unwrap(), None);
+
+        let greeting = Greeting { name: "j4rs".to_string(), times: 2 };
+        let optional = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(Some(greeting.clone())).unwrap()]).unwrap();
+        let is_present = jvm.invoke(&jvm.cast(&optional, "java.util.Optional").unwrap(), "isPresent", &[]).unwrap();
+        assert!(jvm.to_rust::<bool>(is_present).unwrap());
+        assert_eq!(jvm.to_rust::<Option<Greeting>>(optional).unwrap(), Some(greeting));
+        let optional = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(None::<Greeting>).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<Option<Greeting>>(optional).unwrap(), None);
+    }
+
+    #[derive(JavaEnum, Debug, PartialEq)]
+    #[java(class = "java.util.concurrent.TimeUnit")]
+    enum Unit {
+        Seconds,
+        #[java(rename = "MILLISECONDS")]
+        Millis,
+    }
+
+    #[test]
+    fn java_enums() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let seconds = jvm.create_java_enum("java.util.concurrent.TimeUnit", "SECONDS").unwrap();
+        let converted = jvm.invoke(&seconds, "convert", &[
+            InvocationArg::try_from(5000_i64).unwrap().into_primitive().unwrap(),
+            InvocationArg::try_from(Unit::Millis).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<i64>(converted).unwrap(), 5);
+        assert_eq!(jvm.to_rust::<Unit>(seconds).unwrap(), Unit::Seconds);
+        assert_eq!(Unit::JAVA_CLASS, "java.util.concurrent.TimeUnit");
+        assert_eq!(Unit::Seconds.java_name(), "SECONDS");
+        assert_eq!(Unit::from_java_name("MILLISECONDS"), Some(Unit::Millis));
+
+        // The derived conversions round-trip through Java
+        let millis = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(&Unit::Millis).unwrap()]).unwrap();
+        let name = jvm.invoke(&jvm.cast(&millis, "java.lang.Enum").unwrap(), "name", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(name).unwrap(), "MILLISECONDS");
+        assert_eq!(jvm.to_rust::<Unit>(millis).unwrap(), Unit::Millis);
+        assert_eq!(serde_json::to_string(&Unit::Millis).unwrap(), "\"MILLISECONDS\"");
+
+        let days = jvm.create_java_enum("java.util.concurrent.TimeUnit", "DAYS").unwrap();
+        assert!(jvm.to_rust::<Unit>(days).is_err());
+        assert!(jvm.create_java_enum("java.util.concurrent.TimeUnit", "FORTNIGHTS").is_err());
+        assert!(jvm.create_java_enum("java.lang.String", "SECONDS").is_err());
+    }
+
+    #[derive(JavaObject, Debug, PartialEq)]
+    #[java(class = "org.astonbitecode.j4rs.tests.DummyBean")]
+    struct Order {
+        #[java(rename = "firstName")]
+        name: String,
+        count: i64,
+        unit: Option<Unit>,
+        tags: Vec<String>,
+    }
+
+    #[derive(JavaObject, Debug)]
+    #[java(class = "org.astonbitecode.j4rs.tests.DummyBean")]
+    struct MisspelledOrder {
+        #[java(rename = "firstNam")]
+        name: Option<String>,
+    }
+
+    #[test]
+    fn java_objects() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        assert_eq!(Order::JAVA_CLASS, "org.astonbitecode.j4rs.tests.DummyBean");
+        assert_eq!(Order::JAVA_FIELDS, &["firstName", "count", "unit", "tags"]);
+
+        let order = Order { name: "j4rs".to_string(), count: 3, unit: Some(Unit::Millis), tags: vec!["a".to_string()] };
+        let bean = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(&order).unwrap()]).unwrap();
+        let bean = jvm.cast(&bean, "org.astonbitecode.j4rs.tests.DummyBean").unwrap();
+        let first_name = jvm.invoke(&bean, "getFirstName", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(first_name).unwrap(), "j4rs");
+        let count = jvm.field(&bean, "count").unwrap();
+        assert_eq!(jvm.to_rust::<i64>(count).unwrap(), 3);
+        // The derived conversions round-trip through Java
+        assert_eq!(Order::try_from(&bean).unwrap(), order);
+        let without_unit = Order { unit: None, tags: vec![], ..order };
+        let bean = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(&without_unit).unwrap()]).unwrap();
+        assert_eq!(Order::from_instance(&bean).unwrap(), without_unit);
+
+        // Fields that are null cannot be read as non-optional values
+        let empty = jvm.create_instance("org.astonbitecode.j4rs.tests.DummyBean", &[]).unwrap();
+        assert!(Order::from_instance(&empty).is_err());
+        let unit = jvm.create_java_enum("java.util.concurrent.TimeUnit", "SECONDS").unwrap();
+        assert!(Order::from_instance(&unit).is_err());
+
+        // Fields that do not exist cannot be read, even as optional values
+        let error = MisspelledOrder::from_instance(&empty).unwrap_err();
+        assert!(error.to_string().contains("Cannot read the field firstNam"), "{}", error);
+    }
+
+    java_class! {
+        ArrayList = "java.util.ArrayList" {
+            fn new() -> Self;
+            fn size(&self) -> i32;
+            fn add(&self, e: &str) -> bool;
+            #[java(name = "add")]
+            fn insert(&self, index: i32, e: &str);
+            fn get(&self, index: i32) -> String;
+            fn index_of(&self, #[java(boxed)] o: i32) -> i32;
+        }
+
+        Collections = "java.util.Collections" {
+            static fn max(coll: &ArrayList) -> String;
+            static fn unmodifiable_list(list: &ArrayList) -> Instance;
+        }
+    }
+
+    #[test]
+    fn java_classes() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        assert_eq!(ArrayList::CLASS_NAME, "java.util.ArrayList");
+        let list = ArrayList::new(&jvm).unwrap();
+        assert_eq!(list.instance().class_name(), "java.util.ArrayList");
+        assert!(list.add(&jvm, "b").unwrap());
+        list.insert(&jvm, 0, "a").unwrap();
+        assert_eq!(list.size(&jvm).unwrap(), 2);
+        assert_eq!(list.get(&jvm, 0).unwrap(), "a");
+        assert_eq!(list.get(&jvm, 1).unwrap(), "b");
+        assert_eq!(list.index_of(&jvm, 1).unwrap(), -1);
+        assert!(list.get(&jvm, 2).is_err());
+
+        assert_eq!(Collections::max(&jvm, &list).unwrap(), "b");
+        let unmodifiable = Collections::unmodifiable_list(&jvm, &list).unwrap();
+        assert!(jvm.invoke(&unmodifiable, "clear", &[]).is_err());
+
+        // The wrappers convert from and to Instances
+        let list = ArrayList::from(list.into_instance());
+        let instance: Instance = list.into();
+        let size = jvm.invoke(&instance, "size", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<i32>(size).unwrap(), 2);
+    }
+
+    #[test]
+    fn runtime_class_names_and_instanceof() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let mut list = jvm.invoke_static("java.util.Collections", "singletonList", &[InvocationArg::try_from("a").unwrap()]).unwrap();
+        assert_eq!(list.class_name(), cache::UNKNOWN_FOR_RUST);
+        assert_eq!(list.runtime_class_name().unwrap(), "java.util.Collections$SingletonList");
+        assert_eq!(list.class_name(), "java.util.Collections$SingletonList");
+        assert!(jvm.is_instance_of(&list, "java.util.List").unwrap());
+        assert!(jvm.is_instance_of(&list, "java.util.Collection").unwrap());
+        assert!(!jvm.is_instance_of(&list, "java.util.Map").unwrap());
+        assert!(jvm.is_instance_of(&list, "org.astonbitecode.Missing").is_err());
+
+        // A known class name is not replaced
+        let mut set = jvm.create_java_set(&[InvocationArg::try_from("a").unwrap()]).unwrap();
+        assert_eq!(set.runtime_class_name().unwrap(), "java.util.LinkedHashSet");
+        assert_eq!(set.class_name(), "java.util.Set");
+
+        let test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+        let mut null_integer = jvm.invoke(&test, "getNullInteger", &[]).unwrap();
+        assert!(!jvm.is_instance_of(&null_integer, "java.lang.Integer").unwrap());
+        // Null objects have the class that is known to Java
+        assert_eq!(null_integer.runtime_class_name().unwrap(), "java.lang.Integer");
+    }
+
+    #[test]
+    fn set_fields_and_static_fields() {
+        let mut jvm = JvmBuilder::new().build().unwrap();
+
+        let max_value = jvm.static_field("java.lang.Integer", "MAX_VALUE").unwrap();
+        // The retrieved fields have their declared types
+        assert_eq!(max_value.class_name(), "int");
+        assert_eq!(jvm.to_rust::<i32>(max_value).unwrap(), i32::MAX);
+
+        let bean = jvm.create_instance("org.astonbitecode.j4rs.tests.DummyBean", &[]).unwrap();
+        // The Integer is converted to the long of the field
+        jvm.set_field(&bean, "count", InvocationArg::try_from(5).unwrap()).unwrap();
+        let count = jvm.field(&bean, "count").unwrap();
+        assert_eq!(jvm.to_rust::<i64>(count).unwrap(), 5);
+        let count = jvm.chain(&bean).unwrap()
+            .set_field("count", InvocationArg::try_from(6_i64).unwrap()).unwrap()
+            .field("count").unwrap()
+            .to_rust::<i64>().unwrap();
+        assert_eq!(count, 6);
+        // The values are not narrowed with a loss of precision
+        assert!(jvm.set_field(&bean, "count", InvocationArg::try_from(2.5).unwrap()).is_err());
+        assert!(jvm.set_field(&bean, "count", InvocationArg::try_from(u64::MAX).unwrap()).is_err());
+        let count = jvm.field(&bean, "count").unwrap();
+        assert_eq!(count.class_name(), "long");
+        assert_eq!(jvm.to_rust::<i64>(count).unwrap(), 6);
+
+        // Private fields are accessible only if forced
+        assert!(jvm.field(&bean, "firstName").is_err());
+        assert!(jvm.set_field(&bean, "firstName", InvocationArg::try_from("j4rs").unwrap()).is_err());
+        jvm.set_force_accessible(true);
+        jvm.set_field(&bean, "firstName", InvocationArg::try_from("j4rs").unwrap()).unwrap();
+        let first_name = jvm.field(&bean, "firstName").unwrap();
+        assert_eq!(first_name.class_name(), "java.lang.String");
+        assert_eq!(jvm.to_rust::<String>(first_name).unwrap(), "j4rs");
+        jvm.set_force_accessible(false);
+
+        let static_string = "This is a static String from Java";
+        jvm.set_static_field("org.astonbitecode.j4rs.tests.MyTest", "StaticString", InvocationArg::try_from("changed").unwrap()).unwrap();
+        let test = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", &[]).unwrap();
+        let changed = jvm.chain(&test).unwrap()
+            .static_field("StaticString").unwrap()
+            .to_rust::<String>().unwrap();
+        assert_eq!(changed, "changed");
+        let restored = jvm.chain(&test).unwrap()
+            .set_static_field("StaticString", InvocationArg::try_from(static_string).unwrap()).unwrap()
+            .static_field("StaticString").unwrap()
+            .to_rust::<String>().unwrap();
+        assert_eq!(restored, static_string);
+
+        assert!(jvm.static_field("java.lang.Integer", "MISSING").is_err());
+        assert!(jvm.static_field("org.astonbitecode.j4rs.tests.DummyBean", "count").is_err());
+    }
+
+    #[test]
+    fn describe_class() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let description = jvm.describe_class("java.util.ArrayList").unwrap();
+        assert_eq!(description.name(), "java.util.ArrayList");
+        assert!(!description.is_interface());
+        assert_eq!(description.superclass(), Some("java.util.AbstractList"));
+        assert!(description.interfaces().contains(&"java.util.List".to_string()));
+        assert_eq!(description.modifiers(), &["public".to_string()]);
+        let constructor_params: Vec<&[String]> = description.constructors().iter().map(|constructor| constructor.parameter_types()).collect();
+        assert_eq!(constructor_params, vec![&[][..], &["int".to_string()][..], &["java.util.Collection".to_string()][..]]);
+        let size = description.methods().iter().find(|method| method.name() == "size").unwrap();
+        assert_eq!(size.return_type(), "int");
+        assert!(!size.is_static() && !size.is_var_args());
+
+        let as_list = jvm.describe_class("java.util.Arrays").unwrap().methods().iter()
+            .find(|method| method.name() == "asList")
+            .cloned()
+            .unwrap();
+        assert!(as_list.is_static() && as_list.is_var_args());
+        assert_eq!(as_list.parameter_types(), &["java.lang.Object[]".to_string()]);
+
+        let runnable = jvm.describe_class("java.lang.Runnable").unwrap();
+        assert!(runnable.is_interface());
+        assert_eq!(runnable.superclass(), None);
+        assert_eq!(runnable.annotations(), &["java.lang.FunctionalInterface".to_string()]);
+
+        // The members are described whatever their visibility
+        let fields = jvm.describe_class("org.astonbitecode.j4rs.tests.DummyBean").unwrap().fields().to_vec();
+        let field_names: Vec<&str> = fields.iter().map(|field| field.name()).collect();
+        assert_eq!(field_names, vec!["child", "count", "firstName", "tags", "unit", "weights"]);
+        assert_eq!((fields[1].field_type(), fields[1].modifiers()), ("long", &["public".to_string()][..]));
+        assert!(!fields[1].is_static() && !fields[1].is_final());
+        assert_eq!(fields[2].modifiers(), &["private".to_string()]);
+
+        // and primitives are described as well
+        let int = jvm.describe_class("int").unwrap();
+        assert_eq!(int.name(), "int");
+        assert!(int.constructors().is_empty() && int.methods().is_empty() && int.fields().is_empty());
+
+        assert!(jvm.describe_class("org.astonbitecode.Missing").is_err());
+    }
+
+    #[test]
+    fn generate_bindings() {
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let source = BindingsGenerator::new("j4rs-0.12.1-SNAPSHOT-jar-with-dependencies.jar")
+            .class("org.astonbitecode.j4rs.tests.MyTest")
+            .class("org.astonbitecode.j4rs.tests.DummyBean")
+            .generate(&jvm)
+            .unwrap();
+        // The checked-in output is compiled in the `test_bindings` module
+        assert_eq!(source, include_str!("test_bindings.rs"));
+
+        assert!(BindingsGenerator::new("missing.jar").generate(&jvm).is_err());
+        assert!(BindingsGenerator::new("j4rs-0.12.1-SNAPSHOT-jar-with-dependencies.jar").class("org.astonbitecode.Missing").generate(&jvm).is_err());
+    }
+
+    #[test]
+    fn call_generated_bindings() {
+        use test_bindings::{DummyBean, MyTest};
+
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let my_test = MyTest::new_string(&jvm, "a string").unwrap();
+        assert_eq!(my_test.get_my_string(&jvm).unwrap(), "a string");
+        // Overloads are named after their parameters
+        assert_eq!(my_test.add_ints_int_int(&jvm, 1, 2).unwrap(), Some(3));
+        // Boxed types are mapped to Option
+        assert_eq!(my_test.get_null_integer(&jvm).unwrap(), None);
+        assert!(MyTest::use_long_primitives_array(&jvm, &[1, 2, 3]).is_ok());
+        assert_eq!(MyTest::static_string(&jvm).unwrap(), "This is a static String from Java");
+        assert!(MyTest::new_my_test(&jvm, &my_test).is_ok());
+
+        let bean = DummyBean::new(&jvm).unwrap();
+        bean.set_first_name(&jvm, "Jane").unwrap();
+        assert_eq!(bean.get_first_name(&jvm).unwrap(), "Jane");
+        bean.set_weights(&jvm, &[1.5, 2.5]).unwrap();
+        assert_eq!(bean.get_weights(&jvm).unwrap(), vec![1.5, 2.5]);
+        assert_eq!(bean.count(&jvm).unwrap(), 0);
+        let child = DummyBean::new(&jvm).unwrap();
+        child.set_first_name(&jvm, "Child").unwrap();
+        bean.set_child(&jvm, &child).unwrap();
+        assert_eq!(bean.get_child(&jvm).unwrap().get_first_name(&jvm).unwrap(), "Child");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_java_time() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+        let jvm = JvmBuilder::new().build().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+        let local_date = jvm.invoke_static("java.time.LocalDate", "from", &[InvocationArg::try_from(date).unwrap()]).unwrap();
+        let next_day = jvm.invoke(&local_date, "plusDays", &[InvocationArg::try_from(1_i64).unwrap().into_primitive().unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<NaiveDate>(next_day).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 1).unwrap());
+
+        let date_time = date.and_hms_nano_opt(23, 59, 58, 999_999_999).unwrap();
+        let local_date_time = jvm.invoke_static("java.time.LocalDateTime", "from", &[InvocationArg::try_from(&date_time).unwrap()]).unwrap();
+        let local_date_time_str = jvm.invoke(&local_date_time, "toString", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(local_date_time_str).unwrap(), "2020-02-29T23:59:58.999999999");
+        assert_eq!(jvm.to_rust::<chrono::NaiveDateTime>(local_date_time).unwrap(), date_time);
+
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let zoned = offset.with_ymd_and_hms(2020, 9, 13, 14, 26, 40).unwrap();
+        let zoned_date_time = jvm.invoke_static("java.time.ZonedDateTime", "from", &[InvocationArg::try_from(zoned).unwrap()]).unwrap();
+        let zoned_str = jvm.invoke(&zoned_date_time, "toString", &[]).unwrap();
+        assert_eq!(jvm.to_rust::<String>(zoned_str).unwrap(), "2020-09-13T14:26:40+02:00");
+        assert_eq!(jvm.to_rust::<DateTime<FixedOffset>>(zoned_date_time).unwrap(), zoned);
+        let zoned_date_time = jvm.invoke_static("java.time.ZonedDateTime", "from", &[InvocationArg::try_from(zoned).unwrap()]).unwrap();
+        assert_eq!(jvm.to_rust::<DateTime<Utc>>(zoned_date_time).unwrap(), Utc.with_ymd_and_hms(2020, 9, 13, 12, 26, 40).unwrap());
+    }
 }
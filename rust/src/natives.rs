@@ -0,0 +1,114 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::os::raw::c_void;
+
+use jni_sys::{jint, JNIEnv, JNINativeMethod};
+
+use crate::api_tweaks as tweaks;
+use crate::errors;
+use crate::errors::opt_to_res;
+use crate::jni_utils;
+use crate::logger::debug;
+
+/// A Rust function that implements a Java `native` method and is registered using the JNI `RegisterNatives`.
+///
+/// These are submitted by the functions that are annotated with `#[call_from_java("...", register_natives)]`.
+pub struct JavaNativeMethod {
+    class_name: &'static str,
+    method_name: &'static str,
+    signature: &'static str,
+    fn_ptr: fn() -> *mut c_void,
+}
+
+impl JavaNativeMethod {
+    /// Creates a new `JavaNativeMethod`.
+    ///
+    /// `class_name` is the fully qualified name of the class that declares the native method,
+    /// `signature` is the JNI signature of the method (eg. `(ILjava/lang/String;)V`)
+    /// and `fn_ptr` returns a pointer to the `extern "system"` function that implements it.
+    pub const fn new(class_name: &'static str, method_name: &'static str, signature: &'static str, fn_ptr: fn() -> *mut c_void) -> JavaNativeMethod {
+        JavaNativeMethod { class_name, method_name, signature, fn_ptr }
+    }
+
+    pub fn class_name(&self) -> &str {
+        self.class_name
+    }
+
+    pub fn method_name(&self) -> &str {
+        self.method_name
+    }
+
+    pub fn signature(&self) -> &str {
+        self.signature
+    }
+}
+
+inventory::collect!(JavaNativeMethod);
+
+/// Returns the native methods that are declared by the class with the provided name.
+pub(crate) fn native_methods_of(class_name: &str) -> Vec<&'static JavaNativeMethod> {
+    inventory::iter::<JavaNativeMethod>()
+        .filter(|native_method| native_method.class_name == class_name)
+        .collect()
+}
+
+/// Returns all the native methods, grouped by the name of the class that declares them.
+pub(crate) fn native_methods_by_class() -> BTreeMap<&'static str, Vec<&'static JavaNativeMethod>> {
+    let mut by_class: BTreeMap<&'static str, Vec<&'static JavaNativeMethod>> = BTreeMap::new();
+    for native_method in inventory::iter::<JavaNativeMethod>() {
+        by_class.entry(native_method.class_name).or_default().push(native_method);
+    }
+    by_class
+}
+
+/// Registers the `native_methods` for the class with the provided name.
+///
+/// A Java exception may be pending after this call, for example if the class is not found.
+pub(crate) fn register_natives(jni_env: *mut JNIEnv, class_name: &str, native_methods: &[&JavaNativeMethod]) -> errors::Result<()> {
+    debug(&format!("Registering {} native methods for {}", native_methods.len(), class_name));
+    // The CStrings must outlive the call to RegisterNatives
+    let names_and_signatures = native_methods.iter()
+        .map(|native_method| Ok((CString::new(native_method.method_name)?, CString::new(native_method.signature)?)))
+        .collect::<errors::Result<Vec<(CString, CString)>>>()?;
+    let class = tweaks::find_class(jni_env, &class_name.replace(".", "/"))?;
+    if class.is_null() {
+        return Err(errors::J4RsError::JavaError(format!("Could not find the class {} to register its native methods", class_name)));
+    }
+    let class = jni_utils::create_global_ref_from_local_ref(class, jni_env)?;
+    let jni_native_methods: Vec<JNINativeMethod> = native_methods.iter()
+        .zip(names_and_signatures.iter())
+        .map(|(native_method, (name, signature))| JNINativeMethod {
+            name: name.as_ptr() as *mut _,
+            signature: signature.as_ptr() as *mut _,
+            fnPtr: (native_method.fn_ptr)(),
+        })
+        .collect();
+    unsafe {
+        let result = (opt_to_res((**jni_env).RegisterNatives)?)(
+            jni_env,
+            class,
+            jni_native_methods.as_ptr(),
+            jni_native_methods.len() as jint,
+        );
+        jni_utils::delete_java_ref(jni_env, class);
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(errors::J4RsError::JniError(format!("Could not register the native methods of {}: {}", class_name, result)))
+        }
+    }
+}
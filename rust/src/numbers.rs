@@ -0,0 +1,456 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::{Any, TypeId};
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ptr;
+use std::str::FromStr;
+
+use jni_sys::{jint, jobject, jvalue, JNIEnv};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Visitor;
+
+use crate::{cache, errors, Instance, jni_utils, Jvm};
+use crate::errors::opt_to_res;
+
+// The Java classes whose values are converted to the unsigned and 128-bit Rust integers
+const INTEGER_CLASSES: [&str; 5] = [cache::JNI_CLASS_BYTE, cache::JNI_CLASS_SHORT, cache::JNI_CLASS_INTEGER, cache::JNI_CLASS_LONG, cache::JNI_CLASS_BIG_INTEGER];
+
+/// An arbitrary-precision integer that is exchanged with Java as a `java.math.BigInteger`, without losing precision.
+///
+/// Like in Java, it consists of a signum (`-1`, `0` or `1`) and a big-endian magnitude.
+/// It is displayed, parsed and serialized in its decimal representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInteger {
+    signum: i8,
+    // Big-endian, without leading zeros. It is empty for zero.
+    magnitude: Vec<u8>,
+}
+
+impl BigInteger {
+    /// Creates a BigInteger from a signum and a big-endian magnitude, like the `BigInteger(int, byte[])` Java constructor does.
+    ///
+    /// A zero magnitude results in zero, whatever the signum is.
+    pub fn new(signum: i8, magnitude: &[u8]) -> errors::Result<BigInteger> {
+        let magnitude = strip_leading_zeros(magnitude);
+        match signum {
+            _ if magnitude.is_empty() && (-1..=1).contains(&signum) => Ok(BigInteger { signum: 0, magnitude: Vec::new() }),
+            -1 | 1 => Ok(BigInteger { signum, magnitude: magnitude.to_vec() }),
+            0 => Err(errors::J4RsError::ParseError("A BigInteger with a zero signum must have a zero magnitude".to_string())),
+            _ => Err(errors::J4RsError::ParseError(format!("Invalid BigInteger signum {}", signum))),
+        }
+    }
+
+    /// Returns `-1`, `0` or `1`, for negative, zero and positive integers.
+    pub fn signum(&self) -> i8 {
+        self.signum
+    }
+
+    /// Returns the big-endian magnitude, without leading zeros.
+    pub fn magnitude(&self) -> &[u8] {
+        &self.magnitude
+    }
+}
+
+macro_rules! big_integer_from_unsigned {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for BigInteger {
+                fn from(value: $int) -> BigInteger {
+                    BigInteger { signum: (value > 0) as i8, magnitude: strip_leading_zeros(&(value as u128).to_be_bytes()).to_vec() }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! big_integer_from_signed {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for BigInteger {
+                fn from(value: $int) -> BigInteger {
+                    let magnitude = (value as i128).unsigned_abs().to_be_bytes();
+                    BigInteger { signum: value.signum() as i8, magnitude: strip_leading_zeros(&magnitude).to_vec() }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! integer_from_big_integer {
+    ($($int:ty),*) => {
+        $(
+            impl<'a> TryFrom<&'a BigInteger> for $int {
+                type Error = errors::J4RsError;
+                fn try_from(value: &'a BigInteger) -> errors::Result<$int> {
+                    let overflow = || errors::J4RsError::RustError(format!("The BigInteger {} does not fit in a {}", value, stringify!($int)));
+                    if value.magnitude.len() > 16 {
+                        return Err(overflow());
+                    }
+                    let magnitude = value.magnitude.iter().fold(0u128, |acc, &byte| acc << 8 | byte as u128);
+                    if value.signum >= 0 {
+                        <$int>::try_from(magnitude).map_err(|_| overflow())
+                    } else if magnitude <= 1u128 << 127 {
+                        // The magnitude of i128::MIN does not fit in an i128, but wraps to it
+                        <$int>::try_from((magnitude as i128).wrapping_neg()).map_err(|_| overflow())
+                    } else {
+                        Err(overflow())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+big_integer_from_unsigned!(u8, u16, u32, u64, u128, usize);
+big_integer_from_signed!(i8, i16, i32, i64, i128, isize);
+integer_from_big_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            digits.push(b'0' + div_rem(&mut magnitude, 10));
+        }
+        if digits.is_empty() {
+            digits.push(b'0');
+        }
+        if self.signum < 0 {
+            digits.push(b'-');
+        }
+        digits.reverse();
+        f.write_str(&String::from_utf8_lossy(&digits))
+    }
+}
+
+impl FromStr for BigInteger {
+    type Err = errors::J4RsError;
+
+    /// Parses a decimal integer, with an optional sign.
+    fn from_str(s: &str) -> errors::Result<BigInteger> {
+        let (signum, digits) = match s.as_bytes().first() {
+            Some(b'-') => (-1, &s[1..]),
+            Some(b'+') => (1, &s[1..]),
+            _ => (1, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(errors::J4RsError::ParseError(format!("Invalid BigInteger {}", s)));
+        }
+        let mut magnitude = Vec::new();
+        for digit in digits.bytes() {
+            mul_add(&mut magnitude, 10, digit - b'0');
+        }
+        BigInteger::new(signum, &magnitude)
+    }
+}
+
+impl Serialize for BigInteger {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInteger {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigInteger, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor(PhantomData))
+    }
+}
+
+/// An arbitrary-precision decimal number that is exchanged with Java as a `java.math.BigDecimal`, without losing precision.
+///
+/// Like in Java, its value is `unscaled_value × 10^-scale`. Numbers with different scales are not equal (eg. `1.0` and `1.00`).
+/// It is displayed, parsed and serialized like `BigDecimal.toString` does (eg. `123.45` or `1.5E+10`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigDecimal {
+    unscaled_value: BigInteger,
+    scale: i32,
+}
+
+impl BigDecimal {
+    pub fn new(unscaled_value: BigInteger, scale: i32) -> BigDecimal {
+        BigDecimal { unscaled_value, scale }
+    }
+
+    pub fn unscaled_value(&self) -> &BigInteger {
+        &self.unscaled_value
+    }
+
+    pub fn scale(&self) -> i32 {
+        self.scale
+    }
+}
+
+impl From<BigInteger> for BigDecimal {
+    fn from(unscaled_value: BigInteger) -> BigDecimal {
+        BigDecimal::new(unscaled_value, 0)
+    }
+}
+
+impl fmt::Display for BigDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unscaled = self.unscaled_value.to_string();
+        let (sign, coefficient) = match unscaled.strip_prefix('-') {
+            Some(coefficient) => ("-", coefficient),
+            None => ("", unscaled.as_str()),
+        };
+        // The same notation as BigDecimal.toString
+        let adjusted_exponent = coefficient.len() as i64 - 1 - self.scale as i64;
+        if self.scale == 0 {
+            write!(f, "{}{}", sign, coefficient)
+        } else if self.scale > 0 && adjusted_exponent >= -6 {
+            let scale = self.scale as usize;
+            if coefficient.len() > scale {
+                let (integer, fraction) = coefficient.split_at(coefficient.len() - scale);
+                write!(f, "{}{}.{}", sign, integer, fraction)
+            } else {
+                write!(f, "{}0.{}{}", sign, "0".repeat(scale - coefficient.len()), coefficient)
+            }
+        } else {
+            let (first, rest) = coefficient.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            let exponent_sign = if adjusted_exponent >= 0 { "+" } else { "" };
+            write!(f, "{}{}{}{}E{}{}", sign, first, point, rest, exponent_sign, adjusted_exponent)
+        }
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = errors::J4RsError;
+
+    /// Parses a decimal number, with an optional sign, fraction and exponent (eg. `-12.50` or `1.5E+10`).
+    fn from_str(s: &str) -> errors::Result<BigDecimal> {
+        let invalid = || errors::J4RsError::ParseError(format!("Invalid BigDecimal {}", s));
+        let (number, exponent) = match s.find(['e', 'E']) {
+            // The parsing of i64 accepts a single sign
+            Some(index) => (&s[..index], s[index + 1..].parse::<i64>().map_err(|_| invalid())?),
+            None => (s, 0),
+        };
+        let (integer, fraction) = match number.find('.') {
+            Some(index) => (&number[..index], &number[index + 1..]),
+            None => (number, ""),
+        };
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) || (integer.trim_start_matches(['-', '+']).is_empty() && fraction.is_empty()) {
+            return Err(invalid());
+        }
+        let unscaled_value = format!("{}{}", integer, fraction).parse::<BigInteger>().map_err(|_| invalid())?;
+        let scale = (fraction.len() as i64).checked_sub(exponent).ok_or_else(invalid)?;
+        let scale = i32::try_from(scale).map_err(|_| invalid())?;
+        Ok(BigDecimal::new(unscaled_value, scale))
+    }
+}
+
+impl Serialize for BigDecimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigDecimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigDecimal, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor(PhantomData))
+    }
+}
+
+// Deserializes big numbers from integers and decimal strings. Floats are rejected, because they are not exact.
+struct DecimalVisitor<T>(PhantomData<T>);
+
+impl<'de, T: FromStr<Err=errors::J4RsError> + From<BigInteger>> Visitor<'de> for DecimalVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer or a decimal string")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        Ok(T::from(BigInteger::from(v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        Ok(T::from(BigInteger::from(v)))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<T, E> {
+        Ok(T::from(BigInteger::from(v)))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<T, E> {
+        Ok(T::from(BigInteger::from(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let first = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    &bytes[first..]
+}
+
+// magnitude = magnitude * factor + addend
+fn mul_add(magnitude: &mut Vec<u8>, factor: u8, addend: u8) {
+    let mut carry = addend as u32;
+    for byte in magnitude.iter_mut().rev() {
+        let v = *byte as u32 * factor as u32 + carry;
+        *byte = v as u8;
+        carry = v >> 8;
+    }
+    if carry > 0 {
+        magnitude.insert(0, carry as u8);
+    }
+}
+
+// magnitude = magnitude / divisor, returning the remainder
+fn div_rem(magnitude: &mut Vec<u8>, divisor: u8) -> u8 {
+    let mut remainder = 0u32;
+    for byte in magnitude.iter_mut() {
+        let v = remainder << 8 | *byte as u32;
+        *byte = (v / divisor as u32) as u8;
+        remainder = v % divisor as u32;
+    }
+    let stripped = magnitude.len() - strip_leading_zeros(magnitude).len();
+    magnitude.drain(..stripped);
+    remainder as u8
+}
+
+/// Creates a `java.math.BigInteger`. The BigInteger is a local reference.
+pub(crate) fn new_big_integer(value: &BigInteger, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let magnitude = jni_utils::jbyte_array_from_bytes(&value.magnitude, jni_env)?;
+    let big_integer = unsafe {
+        (opt_to_res((**jni_env).NewObjectA)?)(
+            jni_env,
+            cache::get_class(cache::JNI_CLASS_BIG_INTEGER)?,
+            cache::get_method_id(cache::JNI_CLASS_BIG_INTEGER, "<init>", "(I[B)V")?,
+            [jvalue { i: value.signum as jint }, jvalue { l: magnitude }].as_ptr(),
+        )
+    };
+    jni_utils::delete_java_local_ref(jni_env, magnitude);
+    Jvm::do_return(jni_env, big_integer)
+}
+
+/// Creates a `java.math.BigDecimal`. The BigDecimal is a local reference.
+pub(crate) fn new_big_decimal(value: &BigDecimal, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let unscaled_value = new_big_integer(&value.unscaled_value, jni_env)?;
+    let big_decimal = unsafe {
+        (opt_to_res((**jni_env).NewObjectA)?)(
+            jni_env,
+            cache::get_class(cache::JNI_CLASS_BIG_DECIMAL)?,
+            cache::get_method_id(cache::JNI_CLASS_BIG_DECIMAL, "<init>", "(Ljava/math/BigInteger;I)V")?,
+            [jvalue { l: unscaled_value }, jvalue { i: value.scale }].as_ptr(),
+        )
+    };
+    jni_utils::delete_java_local_ref(jni_env, unscaled_value);
+    Jvm::do_return(jni_env, big_decimal)
+}
+
+/// Converts the Java number that the `instance` contains to `T`, if `T` is a Rust type that needs a conversion
+/// that the JSON deserialization cannot do exactly. Otherwise, `None` is returned.
+///
+/// * The unsigned and 128-bit integers are created from Java integers (`Byte`, `Short`, `Integer`, `Long` or `BigInteger`).
+///   Java integers that do not fit in `T` result in errors, instead of being truncated.
+/// * The `BigInteger`s are created from Java integers and the `BigDecimal`s from Java integers and `BigDecimal`s.
+pub(crate) fn number_from_instance<T: Any>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<Option<Box<T>>> {
+    macro_rules! try_integer_of {
+        ($($rust_type:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<$rust_type>() {
+                    return match jni_utils::map_instance_object(instance, jni_env, integer_string)? {
+                        Some(s) => {
+                            let v = s.parse::<$rust_type>().map_err(|_| errors::J4RsError::RustError(
+                                format!("The Java integer {} does not fit in a {}", s, stringify!($rust_type))))?;
+                            Ok((Box::new(v) as Box<dyn Any>).downcast::<T>().ok())
+                        }
+                        None => Ok(None),
+                    };
+                }
+            )*
+        };
+    }
+
+    if TypeId::of::<T>() == TypeId::of::<BigInteger>() {
+        let v = jni_utils::map_instance_object(instance, jni_env, big_integer_from_jobject)?;
+        return Ok(v.and_then(|v| (Box::new(v) as Box<dyn Any>).downcast::<T>().ok()));
+    }
+    if TypeId::of::<T>() == TypeId::of::<BigDecimal>() {
+        let v = jni_utils::map_instance_object(instance, jni_env, big_decimal_from_jobject)?;
+        return Ok(v.and_then(|v| (Box::new(v) as Box<dyn Any>).downcast::<T>().ok()));
+    }
+    try_integer_of!(u8, u16, u32, u64, usize, i128, u128);
+    Ok(None)
+}
+
+// The decimal representation of the object, if it is a Java integer
+fn integer_string(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<String>> {
+    for class_name in INTEGER_CLASSES {
+        if jni_utils::is_instance_of(object, class_name, jni_env)? {
+            return to_string(object, jni_env).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn big_integer_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<BigInteger>> {
+    if !jni_utils::is_instance_of(object, cache::JNI_CLASS_BIG_INTEGER, jni_env)? {
+        return match integer_string(object, jni_env)? {
+            Some(s) => s.parse().map(Some),
+            None => Ok(None),
+        };
+    }
+    unsafe {
+        let signum = (opt_to_res(cache::get_jni_call_int_method_a())?)(
+            jni_env,
+            object,
+            cache::get_method_id(cache::JNI_CLASS_BIG_INTEGER, "signum", "()I")?,
+            ptr::null(),
+        );
+        let signum = Jvm::do_return(jni_env, signum)?;
+        let abs = jni_utils::call_object_method(object, cache::JNI_CLASS_BIG_INTEGER, "abs", "()Ljava/math/BigInteger;", jni_env)?;
+        let bytes = jni_utils::call_object_method(abs, cache::JNI_CLASS_BIG_INTEGER, "toByteArray", "()[B", jni_env);
+        jni_utils::delete_java_local_ref(jni_env, abs);
+        let bytes = bytes?;
+        let magnitude = jni_utils::bytes_from_jbyte_array(bytes, jni_env);
+        jni_utils::delete_java_local_ref(jni_env, bytes);
+        // The two's-complement bytes of the absolute value are its magnitude, with a possible leading zero
+        BigInteger::new(signum as i8, &magnitude?).map(Some)
+    }
+}
+
+fn big_decimal_from_jobject(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<Option<BigDecimal>> {
+    if !jni_utils::is_instance_of(object, cache::JNI_CLASS_BIG_DECIMAL, jni_env)? {
+        return Ok(big_integer_from_jobject(object, jni_env)?.map(BigDecimal::from));
+    }
+    unsafe {
+        let scale = (opt_to_res(cache::get_jni_call_int_method_a())?)(
+            jni_env,
+            object,
+            cache::get_method_id(cache::JNI_CLASS_BIG_DECIMAL, "scale", "()I")?,
+            ptr::null(),
+        );
+        let scale = Jvm::do_return(jni_env, scale)?;
+        let unscaled_value = jni_utils::call_object_method(object, cache::JNI_CLASS_BIG_DECIMAL, "unscaledValue", "()Ljava/math/BigInteger;", jni_env)?;
+        let big_integer = big_integer_from_jobject(unscaled_value, jni_env);
+        jni_utils::delete_java_local_ref(jni_env, unscaled_value);
+        Ok(big_integer?.map(|unscaled_value| BigDecimal::new(unscaled_value, scale)))
+    }
+}
+
+fn to_string(object: jobject, jni_env: *mut JNIEnv) -> errors::Result<String> {
+    let string = jni_utils::call_object_method(object, "java/lang/Object", "toString", "()Ljava/lang/String;", jni_env)?;
+    let s = jni_utils::string_from_jstring(string, jni_env);
+    jni_utils::delete_java_local_ref(jni_env, string);
+    s
+}
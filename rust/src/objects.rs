@@ -0,0 +1,41 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{cache, errors, Instance, serde_jni};
+pub use crate::serde_jni::{JavaObjectReader, JavaObjectWriter};
+
+/// A Rust struct that maps to a Java class, field by field.
+///
+/// It is usually implemented with `#[derive(JavaObject)]` of `j4rs_derive`, which also implements
+/// `TryFrom<&S> for InvocationArg` and `TryFrom<&Instance> for S`.
+/// The Java fields are set and read through their setters and getters, or directly if there are no accessors.
+/// Their values are converted like the `Codec::Jni` does, so the Jackson annotations are not needed.
+pub trait JavaObject: Sized {
+    /// The fully qualified name of the Java class
+    const JAVA_CLASS: &'static str;
+
+    /// The names of the Java fields that are mapped
+    const JAVA_FIELDS: &'static [&'static str];
+
+    /// Sets the Java fields of a newly created object
+    fn write_fields(&self, writer: &mut JavaObjectWriter) -> errors::Result<()>;
+
+    /// Creates the struct from the Java fields of an object
+    fn read_fields(reader: &mut JavaObjectReader) -> errors::Result<Self>;
+
+    /// Reads the struct from the Java object of the `instance`.
+    fn from_instance(instance: &Instance) -> errors::Result<Self> {
+        serde_jni::object_from_instance(instance, cache::get_thread_local_env()?)
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::raw::c_void;
+use std::panic;
+use std::ptr;
+
+use jni_sys::{JavaVM, jint, JNI_ERR, JNI_OK, JNI_VERSION_1_6, JNIEnv};
+
+use crate::{api_tweaks as tweaks, cache, errors, Jvm};
+use crate::errors::opt_to_res;
+use crate::logger::{debug, error};
+
+/// A function that is executed when the library is loaded by Java (`System.loadLibrary`). See `on_jni_load`.
+///
+/// These are submitted by the functions that are annotated with `#[jni_onload]`.
+pub struct JniOnLoadHook {
+    hook: fn(&Jvm) -> errors::Result<()>,
+}
+
+impl JniOnLoadHook {
+    pub const fn new(hook: fn(&Jvm) -> errors::Result<()>) -> JniOnLoadHook {
+        JniOnLoadHook { hook }
+    }
+}
+
+inventory::collect!(JniOnLoadHook);
+
+/// Initializes j4rs for the `java_vm` that loaded the library and returns the JNI version that j4rs needs,
+/// or `JNI_ERR` if the initialization failed.
+///
+/// The `java_vm` is recorded, the classes and methods that j4rs uses are cached using the class loader that loaded the library,
+/// the native methods of the functions annotated with `#[call_from_java("...", register_natives)]` are registered
+/// and the functions annotated with `#[jni_onload]` are executed.
+/// Class caching stays enabled afterwards. If any of these steps fails, `JNI_ERR` is returned and Java fails to load the library.
+///
+/// This is called by the `JNI_OnLoad` that j4rs defines when the `jni_onload` feature is enabled.
+/// Libraries that define their own `JNI_OnLoad` may call it from there instead.
+pub fn on_jni_load(java_vm: *mut JavaVM) -> jint {
+    debug("Initializing j4rs in JNI_OnLoad");
+    // Panics must not unwind into the JVM
+    match panic::catch_unwind(|| initialize(java_vm)) {
+        Ok(Ok(())) => JNI_VERSION_1_6,
+        Ok(Err(err)) => {
+            error(&format!("Could not initialize j4rs in JNI_OnLoad: {}", err));
+            JNI_ERR
+        }
+        Err(_) => {
+            error("Could not initialize j4rs in JNI_OnLoad: A panic occurred");
+            JNI_ERR
+        }
+    }
+}
+
+fn initialize(java_vm: *mut JavaVM) -> errors::Result<()> {
+    tweaks::set_java_vm(java_vm);
+    let jni_env = get_env(java_vm)?;
+    let mut jvm = Jvm::try_from(jni_env)?;
+    // The thread belongs to Java
+    jvm.detach_thread_on_drop(false);
+
+    // Caching stays enabled, so that the classes that are found later with the class loader of the library
+    // are available to the threads that are spawned in Rust as well
+    tweaks::set_class_caching(true);
+    let loaded = cache::load_classes_and_methods();
+    Jvm::do_return(jni_env, loaded)??;
+
+    // Any failure fails the loading of the library
+    jvm.register_all_natives()?;
+
+    for on_load_hook in inventory::iter::<JniOnLoadHook>() {
+        (on_load_hook.hook)(&jvm)?;
+    }
+    Ok(())
+}
+
+fn get_env(java_vm: *mut JavaVM) -> errors::Result<*mut JNIEnv> {
+    unsafe {
+        let mut jni_env: *mut JNIEnv = ptr::null_mut();
+        let res = (opt_to_res((**java_vm).GetEnv)?)(
+            java_vm,
+            (&mut jni_env as *mut *mut JNIEnv) as *mut *mut c_void,
+            JNI_VERSION_1_6,
+        );
+        if res == JNI_OK {
+            Ok(jni_env)
+        } else {
+            Err(errors::J4RsError::JniError(format!("Could not get the JNIEnv of the JavaVM: {}", res)))
+        }
+    }
+}
@@ -1,4 +1,11 @@
 pub use core::ptr;
 pub use std::os::raw::c_void;
-pub use crate::{Instance, Jvm};
-pub use crate::jni_sys::{jlong, JNIEnv, jobject};
\ No newline at end of file
+pub use crate::{catch_unwind_into_java_exception, Instance, InvocationArg, JavaEnum, JavaNativeMethod, JavaObject, JavaObjectReader, JavaObjectWriter, jbyte_array_to_rust_vec, JniOnLoadHook, jstring_to_rust_string, Jvm, rust_bytes_to_jbyte_array, rust_string_to_jstring};
+pub use crate::jni_sys::{jboolean, jbyteArray, jdouble, jint, jlong, JNI_FALSE, JNI_TRUE, JNIEnv, jobject, jstring};
+pub use crate::errors::J4RsError;
+#[doc(hidden)]
+pub use crate::enums::deserialize_java_enum;
+#[doc(hidden)]
+pub use inventory;
+#[doc(hidden)]
+pub use crate::serde as j4rs_serde;
\ No newline at end of file
@@ -0,0 +1,172 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::ptr;
+use std::sync::Mutex;
+
+use jni_sys::{jlong, JNIEnv, jobject, jobjectArray, jstring};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{errors, Instance, InvocationArg, jni_utils, Jvm, Null};
+use crate::logger::{debug, error};
+
+const RUST_INVOCATION_HANDLER_CLASS: &str = "org.astonbitecode.j4rs.api.invocation.RustInvocationHandler";
+
+/// The closure that implements the methods of a Java proxy.
+pub(crate) type ProxyFn = Box<dyn FnMut(&str, Vec<Instance>) -> errors::Result<Instance> + Send>;
+
+// The functions that are called by the natives of the `RustInvocationHandler`.
+// Their addresses are passed to Java along with the closure, so that the closure is always handled by the j4rs
+// that created it, even if another library that contains j4rs is the one that implements the native methods.
+type InvokeFn = extern "C" fn(*mut JNIEnv, jlong, jstring, jobjectArray) -> jobject;
+type ReleaseFn = extern "C" fn(*mut JNIEnv, jlong);
+
+thread_local! {
+    // The addresses of the closures that are being invoked by this thread
+    static INVOKING: RefCell<Vec<jlong>> = RefCell::new(Vec::new());
+}
+
+/// Creates a Java proxy that implements the interface `interface_name` by calling `f`.
+///
+/// The closure is owned by the `InvocationHandler` of the proxy and it is dropped after the proxy is garbage collected.
+pub(crate) fn new_proxy(jvm: &Jvm, interface_name: &str, f: ProxyFn) -> errors::Result<Instance> {
+    debug(&format!("Creating a proxy for {}", interface_name));
+    let interface_arg = InvocationArg::try_from(interface_name)?;
+    let invoke_fn_arg = InvocationArg::try_from(invoke_closure as InvokeFn as usize as i64)?;
+    let release_fn_arg = InvocationArg::try_from(release_closure as ReleaseFn as usize as i64)?;
+    let closure_address = Box::into_raw(Box::new(Mutex::new(f))) as jlong;
+    let closure_arg = InvocationArg::try_from(closure_address as i64);
+
+    let proxy = closure_arg.and_then(|closure_arg| jvm.invoke_static(
+        RUST_INVOCATION_HANDLER_CLASS,
+        "newProxy",
+        &[interface_arg, invoke_fn_arg, release_fn_arg, closure_arg]));
+    match proxy {
+        // The proxy is returned as an Object
+        Ok(proxy) => jvm.cast(&proxy, interface_name),
+        Err(error) => {
+            // Java did not take the ownership of the closure
+            unsafe { drop(Box::from_raw(closure_address as *mut Mutex<ProxyFn>)) };
+            Err(error)
+        }
+    }
+}
+
+/// Creates a proxy for the functional interface `interface_name`, whose abstract method `method_name` is implemented by `f`.
+///
+/// Calling any other method of the interface results in an error.
+pub(crate) fn new_functional_proxy<F>(jvm: &Jvm, interface_name: &'static str, method_name: &'static str, mut f: F) -> errors::Result<Instance>
+    where F: FnMut(&Jvm, Vec<Instance>) -> errors::Result<Instance> + Send + 'static {
+    new_proxy(jvm, interface_name, Box::new(move |invoked_method_name, args| {
+        if invoked_method_name != method_name {
+            return Err(errors::J4RsError::GeneralError(format!("The method {} of {} is not implemented by Rust", invoked_method_name, interface_name)));
+        }
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        f(&jvm, args)
+    }))
+}
+
+/// Deserializes the next one of the `args` of a proxy method.
+pub(crate) fn next_arg<T>(jvm: &Jvm, args: &mut impl Iterator<Item=Instance>) -> errors::Result<T> where T: DeserializeOwned + Any {
+    let arg = args.next().ok_or_else(|| errors::J4RsError::RustError("A proxy method was called with fewer arguments than expected".to_string()))?;
+    jvm.to_rust(arg)
+}
+
+/// Creates the Instance that is returned by a proxy method, by serializing the `value`.
+pub(crate) fn return_value<R>(jvm: &Jvm, value: &R) -> errors::Result<Instance> where R: Serialize + Any {
+    Instance::try_from(InvocationArg::new_2(value, "java.lang.Object", jvm.jni_env)?)
+}
+
+/// Creates the Instance that is returned by a `void` proxy method.
+pub(crate) fn return_void() -> errors::Result<Instance> {
+    Instance::try_from(InvocationArg::try_from(Null::Of("java.lang.Object"))?)
+}
+
+/// Calls the `InvokeFn` with the address `invoke_fn_address`. Called by the `RustInvocationHandler` natives.
+pub(crate) fn invoke(jni_env: *mut JNIEnv, invoke_fn_address: jlong, closure_address: jlong, method_name: jstring, args: jobjectArray) -> jobject {
+    let invoke_fn: InvokeFn = unsafe { std::mem::transmute(invoke_fn_address as usize) };
+    invoke_fn(jni_env, closure_address, method_name, args)
+}
+
+/// Calls the `ReleaseFn` with the address `release_fn_address`. Called by the `RustInvocationHandler` natives.
+pub(crate) fn release(jni_env: *mut JNIEnv, release_fn_address: jlong, closure_address: jlong) {
+    let release_fn: ReleaseFn = unsafe { std::mem::transmute(release_fn_address as usize) };
+    release_fn(jni_env, closure_address)
+}
+
+extern "C" fn invoke_closure(jni_env: *mut JNIEnv, closure_address: jlong, method_name: jstring, args: jobjectArray) -> jobject {
+    jni_utils::catch_unwind_into_java_exception(jni_env, || {
+        let mut jvm = Jvm::try_from(jni_env).expect("Could not create a j4rs Jvm while invoking a proxy");
+        // The thread belongs to Java
+        jvm.detach_thread_on_drop(false);
+        match call_closure(jni_env, closure_address, method_name, args) {
+            Ok(java_object) => java_object,
+            Err(err) => {
+                if let Err(error_while_throwing) = jvm.throw_invocation_exception(&format!("{}", err)) {
+                    error(&format!("Could not throw the error of the proxy to Java: {}", error_while_throwing));
+                }
+                ptr::null_mut()
+            }
+        }
+    }).unwrap_or(ptr::null_mut())
+}
+
+fn call_closure(jni_env: *mut JNIEnv, closure_address: jlong, method_name: jstring, args: jobjectArray) -> errors::Result<jobject> {
+    let method_name = jni_utils::string_from_jstring(method_name, jni_env)?;
+    let args = jni_utils::instances_from_jobject_array(args, jni_env)?;
+    debug(&format!("Invoking the proxy method {} with {} arguments", method_name, args.len()));
+
+    let reentrant = INVOKING.with(|invoking| invoking.borrow().contains(&closure_address));
+    if reentrant {
+        return Err(errors::J4RsError::GeneralError(format!("The proxy method {} was called while the proxy was already being invoked by the same thread", method_name)));
+    }
+    let closure = unsafe { &*(closure_address as *const Mutex<ProxyFn>) };
+    // A previous invocation may have panicked. The panic was thrown to Java, so the closure can still be used.
+    let mut f = closure.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _invoking = Invoking::start(closure_address);
+    let instance = f(&method_name, args)?;
+    jni_utils::create_local_ref(instance.jinstance, jni_env)
+}
+
+// Marks a closure as being invoked by the current thread, until it is dropped.
+struct Invoking {
+    closure_address: jlong,
+}
+
+impl Invoking {
+    fn start(closure_address: jlong) -> Invoking {
+        INVOKING.with(|invoking| invoking.borrow_mut().push(closure_address));
+        Invoking { closure_address }
+    }
+}
+
+impl Drop for Invoking {
+    fn drop(&mut self) {
+        INVOKING.with(|invoking| invoking.borrow_mut().retain(|address| *address != self.closure_address));
+    }
+}
+
+extern "C" fn release_closure(jni_env: *mut JNIEnv, closure_address: jlong) {
+    let _ = jni_utils::catch_unwind_into_java_exception(jni_env, || {
+        debug("Releasing the closure of a proxy");
+        // The closure may own Instances that need the Jvm in order to be dropped
+        let mut jvm = Jvm::try_from(jni_env).expect("Could not create a j4rs Jvm while releasing a proxy");
+        jvm.detach_thread_on_drop(false);
+        unsafe { drop(Box::from_raw(closure_address as *mut Mutex<ProxyFn>)) };
+    });
+}
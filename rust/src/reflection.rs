@@ -0,0 +1,206 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use crate::{errors, InvocationArg, Jvm};
+
+const CLASS_CLASS_DESCRIPTION: &str = "org.astonbitecode.j4rs.api.dtos.ClassDescription";
+
+/// The description of a Java class, as retrieved by the Java reflection. It contains the constructors, methods and fields
+/// that the class declares, whatever their visibility, along with its superclass, interfaces, annotations and modifiers.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct ClassDescription {
+    pub(crate) name: String,
+    #[serde(rename = "interface")]
+    pub(crate) is_interface: bool,
+    superclass: Option<String>,
+    interfaces: Vec<String>,
+    annotations: Vec<String>,
+    modifiers: Vec<String>,
+    pub(crate) constructors: Vec<MethodDescription>,
+    pub(crate) methods: Vec<MethodDescription>,
+    pub(crate) fields: Vec<FieldDescription>,
+}
+
+impl ClassDescription {
+    /// The fully qualified name of the class.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the class is an interface.
+    pub fn is_interface(&self) -> bool {
+        self.is_interface
+    }
+
+    /// The fully qualified name of the superclass. Interfaces and `java.lang.Object` have no superclass.
+    pub fn superclass(&self) -> Option<&str> {
+        self.superclass.as_deref()
+    }
+
+    /// The fully qualified names of the interfaces that the class directly implements.
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// The fully qualified names of the classes of the runtime annotations, including the inherited ones.
+    pub fn annotations(&self) -> &[String] {
+        &self.annotations
+    }
+
+    /// The modifiers of the class (eg. `public`, `abstract`, `final`).
+    pub fn modifiers(&self) -> &[String] {
+        &self.modifiers
+    }
+
+    /// The constructors, ordered by the number and the types of their parameters.
+    pub fn constructors(&self) -> &[MethodDescription] {
+        &self.constructors
+    }
+
+    /// The methods that the class declares, ordered by name, then by the number and the types of their parameters.
+    pub fn methods(&self) -> &[MethodDescription] {
+        &self.methods
+    }
+
+    /// The fields that the class declares, ordered by name.
+    pub fn fields(&self) -> &[FieldDescription] {
+        &self.fields
+    }
+}
+
+/// The description of a constructor or a method.
+///
+/// The types are named like `Class.getTypeName` does (eg. `int[]` or `java.util.Map$Entry`).
+/// Constructors are named after their class, which is their return type as well.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct MethodDescription {
+    pub(crate) name: String,
+    #[serde(rename = "parameterTypes")]
+    pub(crate) parameter_types: Vec<String>,
+    #[serde(rename = "returnType")]
+    pub(crate) return_type: String,
+    #[serde(rename = "static")]
+    pub(crate) is_static: bool,
+    #[serde(rename = "varArgs")]
+    is_var_args: bool,
+    annotations: Vec<String>,
+    modifiers: Vec<String>,
+}
+
+impl MethodDescription {
+    /// The name of the method.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The types of the parameters.
+    pub fn parameter_types(&self) -> &[String] {
+        &self.parameter_types
+    }
+
+    /// The return type (`void` if nothing is returned).
+    pub fn return_type(&self) -> &str {
+        &self.return_type
+    }
+
+    /// Whether the method is static.
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Whether the last parameter is a variable arity parameter.
+    pub fn is_var_args(&self) -> bool {
+        self.is_var_args
+    }
+
+    /// The fully qualified names of the classes of the runtime annotations.
+    pub fn annotations(&self) -> &[String] {
+        &self.annotations
+    }
+
+    /// The modifiers of the method (eg. `public`, `static`, `synchronized`).
+    pub fn modifiers(&self) -> &[String] {
+        &self.modifiers
+    }
+
+    pub(crate) fn is_public(&self) -> bool {
+        self.modifiers.iter().any(|modifier| modifier == "public")
+    }
+}
+
+/// The description of a field.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct FieldDescription {
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) field_type: String,
+    #[serde(rename = "static")]
+    pub(crate) is_static: bool,
+    #[serde(rename = "final")]
+    is_final: bool,
+    annotations: Vec<String>,
+    modifiers: Vec<String>,
+}
+
+impl FieldDescription {
+    /// The name of the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type of the field, named like `Class.getTypeName` does.
+    pub fn field_type(&self) -> &str {
+        &self.field_type
+    }
+
+    /// Whether the field is static.
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Whether the field is final.
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    /// The fully qualified names of the classes of the runtime annotations.
+    pub fn annotations(&self) -> &[String] {
+        &self.annotations
+    }
+
+    /// The modifiers of the field (eg. `public`, `static`, `final`).
+    pub fn modifiers(&self) -> &[String] {
+        &self.modifiers
+    }
+
+    pub(crate) fn is_public(&self) -> bool {
+        self.modifiers.iter().any(|modifier| modifier == "public")
+    }
+}
+
+/// Describes the public classes of a jar. The jar should be in the classpath of the `jvm`.
+pub(crate) fn describe_jar(jvm: &Jvm, jar_path: &str) -> errors::Result<Vec<ClassDescription>> {
+    let descriptions = jvm.invoke_static(CLASS_CLASS_DESCRIPTION, "describeJar", &[InvocationArg::try_from(jar_path)?])?;
+    jvm.to_rust(descriptions)
+}
+
+/// Describes a Java class, or a primitive type.
+pub(crate) fn describe_class(jvm: &Jvm, class_name: &str) -> errors::Result<ClassDescription> {
+    let description = jvm.invoke_static(CLASS_CLASS_DESCRIPTION, "describe", &[InvocationArg::try_from(class_name)?])?;
+    jvm.to_rust(description)
+}
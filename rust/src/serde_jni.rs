@@ -0,0 +1,1020 @@
+// Copyright 2020 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A serde `Serializer` and `Deserializer` that convert Rust values to and from Java objects with JNI calls,
+//! without encoding them. They are used by the `Codec::Jni`.
+//!
+//! | Rust                         | Java                                              |
+//! |------------------------------|---------------------------------------------------|
+//! | `bool`                       | `Boolean`                                         |
+//! | `i8`, `i16`, `i32`, `i64`    | `Byte`, `Short`, `Integer`, `Long`                |
+//! | `u8`, `u16`, `u32`           | `Short`, `Integer`, `Long`                        |
+//! | `u64`, `i128`, `u128`        | `BigInteger`                                      |
+//! | `BigInteger`, `BigDecimal`   | `String`, or the expected big number              |
+//! | `f32`, `f64`                 | `Float`, `Double`                                 |
+//! | `char`, `String`             | `Character`, `String`                             |
+//! | bytes                        | `byte[]`                                          |
+//! | sequences and tuples         | `ArrayList`                                       |
+//! | maps                         | `HashMap`                                         |
+//! | structs                      | the expected class, through its setters or fields |
+//! | unit variants                | `String`, or the expected enum                    |
+//! | `None` and `()`              | `null`                                            |
+//!
+//! Structs are serialized to a `LinkedHashMap` if the expected class is not known (eg. `java.lang.Object`).
+//! Options are wrapped in `java.util.Optional`s if Java expects them, and Java `Optional`s are deserialized like nullable values.
+//! The other enum variants are serialized like serde_json does, as maps with the name of the variant as the single key.
+
+use std::fmt::Display;
+use std::mem;
+use std::ptr;
+
+use jni_sys::{jboolean, jint, jobject, jsize, jvalue, JNI_TRUE, JNIEnv};
+use serde::{de, ser, Deserialize, Serialize};
+use serde::de::{DeserializeSeed, IntoDeserializer, Visitor};
+use serde::de::value::SeqDeserializer;
+
+use crate::{cache, errors, Instance, JavaObject, jni_utils, Jvm, numbers};
+use crate::arrays::JavaPrimitive;
+use crate::errors::{J4RsError, opt_to_res};
+use crate::numbers::BigInteger;
+
+const CLASS_OBJECT_GRAPHS: &str = "org/astonbitecode/j4rs/api/value/ObjectGraphs";
+const CLASS_ENUM: &str = "java/lang/Enum";
+const CLASS_ARRAY_LIST: &str = "java/util/ArrayList";
+const CLASS_HASH_MAP: &str = "java/util/HashMap";
+const CLASS_LINKED_HASH_MAP: &str = "java/util/LinkedHashMap";
+const CLASS_MAP: &str = "java/util/Map";
+const CLASS_COLLECTION: &str = "java/util/Collection";
+const CLASS_OPTIONAL: &str = "java/util/Optional";
+const CLASS_OPTIONAL_NAME: &str = "java.util.Optional";
+const CLASS_OBJECT_ARRAY: &str = "[Ljava/lang/Object;";
+
+impl ser::Error for J4RsError {
+    fn custom<T: Display>(msg: T) -> Self {
+        J4RsError::ParseError(msg.to_string())
+    }
+}
+
+impl de::Error for J4RsError {
+    fn custom<T: Display>(msg: T) -> Self {
+        J4RsError::ParseError(msg.to_string())
+    }
+}
+
+/// Creates the Java object that represents the `value`. The object is a local reference.
+///
+/// The `class_name` is the class that is expected by Java. It is used to select the classes of the structs
+/// and to convert the values (eg. a Rust `i32` to a Java `long`).
+pub(crate) fn to_java<T: Serialize>(value: &T, class_name: &str, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let object = value.serialize(JavaSerializer { jni_env, class_name: Some(class_name) })?;
+    let class_name = string_ref(class_name, jni_env)?;
+    let converted = call_static_object(
+        jni_env,
+        CLASS_OBJECT_GRAPHS,
+        "convert",
+        "(Ljava/lang/Object;Ljava/lang/String;)Ljava/lang/Object;",
+        &[jvalue { l: object.raw() }, jvalue { l: class_name.raw() }],
+    )?;
+    Ok(converted.into_raw())
+}
+
+/// Deserializes the Java object of the `instance`.
+pub(crate) fn from_instance<T: for<'de> Deserialize<'de>>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<T> {
+    let object = unsafe {
+        // Call the getObjectMethod. This returns a localref
+        (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        )
+    };
+    let object = LocalRef::new(Jvm::do_return(jni_env, object)?, jni_env);
+    T::deserialize(JavaDeserializer::new(object))
+}
+
+/// Creates an object of the Java class of the `value` and sets its fields. The object is a local reference.
+pub(crate) fn object_to_java<O: JavaObject>(value: &O, jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let fields = JavaSerializer { jni_env, class_name: None }.structure(Some(O::JAVA_CLASS))?;
+    let mut writer = JavaObjectWriter { fields };
+    value.write_fields(&mut writer)?;
+    Ok(writer.fields.target.into_raw())
+}
+
+/// Reads a `JavaObject` from the fields of the Java object of the `instance`.
+pub(crate) fn object_from_instance<O: JavaObject>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<O> {
+    let object = unsafe {
+        // Call the getObjectMethod. This returns a localref
+        (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        )
+    };
+    let object = LocalRef::new(Jvm::do_return(jni_env, object)?, jni_env);
+    if object.is_null() {
+        return Err(J4RsError::RustError(format!("Cannot read a {} from a null Java object", O::JAVA_CLASS)));
+    }
+    let mut properties = JavaDeserializer::new(object).properties(O::JAVA_FIELDS)?;
+    let mut fields = Vec::with_capacity(O::JAVA_FIELDS.len());
+    while properties.index < properties.length {
+        let name = string_from(&properties.next_object()?)?;
+        fields.push((name, properties.next_object()?));
+    }
+    O::read_fields(&mut JavaObjectReader { class_name: O::JAVA_CLASS, fields })
+}
+
+/// Deserializes the Java object of the `instance`, if it is a `Map`, a `Collection` or an `Optional`. Otherwise, `None` is returned.
+///
+/// The collections are deserialized element by element, without being encoded.
+pub(crate) fn collection_from_instance<T: for<'de> Deserialize<'de>>(instance: &Instance, jni_env: *mut JNIEnv) -> errors::Result<Option<T>> {
+    let object = unsafe {
+        // Call the getObjectMethod. This returns a localref
+        (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        )
+    };
+    let object = LocalRef::new(Jvm::do_return(jni_env, object)?, jni_env);
+    if object.is_null() {
+        return Ok(None);
+    }
+    for class_name in [CLASS_MAP, CLASS_COLLECTION, CLASS_OPTIONAL] {
+        if object.is_instance_of(class_name)? {
+            return T::deserialize(JavaDeserializer::new(object)).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Sets the fields of the Java object that is created for a `JavaObject`.
+pub struct JavaObjectWriter {
+    fields: StructSerializer,
+}
+
+impl JavaObjectWriter {
+    /// Sets the Java field with the provided name, using its setter if there is one.
+    /// The value is converted to the class of the field.
+    pub fn set_field<T: ?Sized + Serialize>(&mut self, name: &'static str, value: &T) -> errors::Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.fields, name, value)
+    }
+}
+
+/// Reads the fields of the Java object of a `JavaObject`.
+pub struct JavaObjectReader {
+    class_name: &'static str,
+    // The values of the fields that are not read yet
+    fields: Vec<(String, LocalRef)>,
+}
+
+impl JavaObjectReader {
+    /// Reads the Java field with the provided name, using its getter if there is one.
+    /// Fields that do not exist or are not readable result in errors, even if `T` is an `Option`.
+    pub fn field<T: for<'de> Deserialize<'de>>(&mut self, name: &str) -> errors::Result<T> {
+        let value = match self.fields.iter().position(|(field_name, _)| field_name == name) {
+            Some(index) => self.fields.swap_remove(index).1,
+            None => return Err(J4RsError::ParseError(format!("Cannot read the field {} of {}: it does not exist", name, self.class_name))),
+        };
+        T::deserialize(JavaDeserializer::new(value))
+            .map_err(|error| J4RsError::ParseError(format!("Cannot read the field {} of {}: {}", name, self.class_name, error)))
+    }
+}
+
+// A local reference that is deleted when dropped
+struct LocalRef {
+    obj: jobject,
+    jni_env: *mut JNIEnv,
+}
+
+impl LocalRef {
+    fn new(obj: jobject, jni_env: *mut JNIEnv) -> LocalRef {
+        LocalRef { obj, jni_env }
+    }
+
+    fn null(jni_env: *mut JNIEnv) -> LocalRef {
+        LocalRef::new(ptr::null_mut(), jni_env)
+    }
+
+    fn raw(&self) -> jobject {
+        self.obj
+    }
+
+    fn is_null(&self) -> bool {
+        self.obj.is_null()
+    }
+
+    fn into_raw(self) -> jobject {
+        let obj = self.obj;
+        mem::forget(self);
+        obj
+    }
+
+    fn is_instance_of(&self, class_name: &'static str) -> errors::Result<bool> {
+        let class = cache::get_class(class_name)?;
+        unsafe {
+            Ok((opt_to_res(cache::get_is_instance_of())?)(self.jni_env, self.obj, class) == JNI_TRUE)
+        }
+    }
+}
+
+impl Drop for LocalRef {
+    fn drop(&mut self) {
+        if !self.obj.is_null() {
+            jni_utils::delete_java_local_ref(self.jni_env, self.obj);
+        }
+    }
+}
+
+fn string_ref(string: &str, jni_env: *mut JNIEnv) -> errors::Result<LocalRef> {
+    let jstring = jni_utils::jstring_from_str(string, jni_env)?;
+    Ok(LocalRef::new(Jvm::do_return(jni_env, jstring)?, jni_env))
+}
+
+fn new_object(jni_env: *mut JNIEnv, class_name: &'static str, signature: &'static str, args: &[jvalue]) -> errors::Result<LocalRef> {
+    let class = cache::get_class(class_name)?;
+    let constructor = cache::get_method_id(class_name, "<init>", signature)?;
+    let obj = unsafe {
+        (opt_to_res((**jni_env).NewObjectA)?)(jni_env, class, constructor, args.as_ptr())
+    };
+    Ok(LocalRef::new(Jvm::do_return(jni_env, obj)?, jni_env))
+}
+
+fn call_object(obj: &LocalRef, class_name: &'static str, method: &'static str, signature: &'static str, args: &[jvalue]) -> errors::Result<LocalRef> {
+    let method_id = cache::get_method_id(class_name, method, signature)?;
+    let result = unsafe {
+        (opt_to_res(cache::get_jni_call_object_method_a())?)(obj.jni_env, obj.raw(), method_id, args.as_ptr())
+    };
+    Ok(LocalRef::new(Jvm::do_return(obj.jni_env, result)?, obj.jni_env))
+}
+
+fn call_static_object(jni_env: *mut JNIEnv, class_name: &'static str, method: &'static str, signature: &'static str, args: &[jvalue]) -> errors::Result<LocalRef> {
+    let result = jni_utils::call_static_object_method(class_name, method, signature, args, jni_env)?;
+    Ok(LocalRef::new(result, jni_env))
+}
+
+fn call_static_void(jni_env: *mut JNIEnv, class_name: &'static str, method: &'static str, signature: &'static str, args: &[jvalue]) -> errors::Result<()> {
+    let class = cache::get_class(class_name)?;
+    let method_id = cache::get_static_method_id(class_name, method, signature)?;
+    unsafe {
+        (opt_to_res(cache::get_jni_call_static_void_method_a())?)(jni_env, class, method_id, args.as_ptr());
+    }
+    Jvm::do_return(jni_env, ())
+}
+
+fn string_from(obj: &LocalRef) -> errors::Result<String> {
+    jni_utils::string_from_jstring(obj.raw(), obj.jni_env)
+}
+
+fn map_put(map: &LocalRef, key: &LocalRef, value: &LocalRef) -> errors::Result<()> {
+    call_object(
+        map,
+        CLASS_MAP,
+        "put",
+        "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+        &[jvalue { l: key.raw() }, jvalue { l: value.raw() }],
+    ).map(|_| ())
+}
+
+// Wraps a value in a map, with the name of an enum variant as the single key
+fn variant_map(jni_env: *mut JNIEnv, variant: &str, value: &LocalRef) -> errors::Result<LocalRef> {
+    let map = new_object(jni_env, CLASS_LINKED_HASH_MAP, "()V", &[])?;
+    map_put(&map, &string_ref(variant, jni_env)?, value)?;
+    Ok(map)
+}
+
+struct JavaSerializer<'a> {
+    jni_env: *mut JNIEnv,
+    // The class that is expected by Java, if known
+    class_name: Option<&'a str>,
+}
+
+impl<'a> JavaSerializer<'a> {
+    fn boxed(&self, class_name: &'static str, signature: &'static str, value: jvalue) -> errors::Result<LocalRef> {
+        new_object(self.jni_env, class_name, signature, &[value])
+    }
+
+    fn big_integer(&self, value: BigInteger) -> errors::Result<LocalRef> {
+        Ok(LocalRef::new(numbers::new_big_integer(&value, self.jni_env)?, self.jni_env))
+    }
+
+    // The class of the elements, if an array of objects is expected (eg. `[Lorg.Dummy;`)
+    fn element_class_name(&self) -> Option<String> {
+        self.class_name
+            .filter(|name| name.starts_with("[L") && name.ends_with(';'))
+            .map(|name| name[2..name.len() - 1].to_string())
+    }
+
+    fn list(&self, len: Option<usize>) -> errors::Result<ListSerializer> {
+        let list = self.boxed(CLASS_ARRAY_LIST, "(I)V", jvalue { i: len.unwrap_or(0) as jint })?;
+        Ok(ListSerializer { jni_env: self.jni_env, list, element_class_name: self.element_class_name() })
+    }
+
+    fn structure(&self, class_name: Option<&str>) -> errors::Result<StructSerializer> {
+        let class_name = match class_name {
+            Some(class_name) => string_ref(class_name, self.jni_env)?,
+            None => LocalRef::null(self.jni_env),
+        };
+        let target = call_static_object(
+            self.jni_env,
+            CLASS_OBJECT_GRAPHS,
+            "newStruct",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[jvalue { l: class_name.raw() }],
+        )?;
+        let is_map = target.is_instance_of(CLASS_MAP)?;
+        Ok(StructSerializer { jni_env: self.jni_env, target, is_map })
+    }
+}
+
+impl<'a> ser::Serializer for JavaSerializer<'a> {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = VariantSerializer<ListSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = VariantSerializer<StructSerializer>;
+
+    fn serialize_bool(self, v: bool) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_BOOLEAN, "(Z)V", jvalue { z: v as jboolean })
+    }
+
+    fn serialize_i8(self, v: i8) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_BYTE, "(B)V", jvalue { b: v })
+    }
+
+    fn serialize_i16(self, v: i16) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_SHORT, "(S)V", jvalue { s: v })
+    }
+
+    fn serialize_i32(self, v: i32) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_INTEGER, "(I)V", jvalue { i: v })
+    }
+
+    fn serialize_i64(self, v: i64) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_LONG, "(J)V", jvalue { j: v })
+    }
+
+    // The unsigned integers are widened, as Java does not have unsigned types
+    fn serialize_u8(self, v: u8) -> errors::Result<LocalRef> {
+        self.serialize_i16(v as i16)
+    }
+
+    fn serialize_u16(self, v: u16) -> errors::Result<LocalRef> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_u32(self, v: u32) -> errors::Result<LocalRef> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> errors::Result<LocalRef> {
+        self.big_integer(BigInteger::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> errors::Result<LocalRef> {
+        self.big_integer(BigInteger::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> errors::Result<LocalRef> {
+        self.big_integer(BigInteger::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_FLOAT, "(F)V", jvalue { f: v })
+    }
+
+    fn serialize_f64(self, v: f64) -> errors::Result<LocalRef> {
+        self.boxed(cache::JNI_CLASS_DOUBLE, "(D)V", jvalue { d: v })
+    }
+
+    // The chars that do not fit in a Java char are serialized as Strings
+    fn serialize_char(self, v: char) -> errors::Result<LocalRef> {
+        if (v as u32) <= u16::MAX as u32 {
+            self.boxed(cache::JNI_CLASS_CHARACTER, "(C)V", jvalue { c: v as u16 })
+        } else {
+            self.serialize_str(&v.to_string())
+        }
+    }
+
+    fn serialize_str(self, v: &str) -> errors::Result<LocalRef> {
+        string_ref(v, self.jni_env)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> errors::Result<LocalRef> {
+        let array = unsafe { u8::new_java_array(v, self.jni_env)? };
+        Ok(LocalRef::new(array, self.jni_env))
+    }
+
+    fn serialize_none(self) -> errors::Result<LocalRef> {
+        Ok(LocalRef::null(self.jni_env))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> errors::Result<LocalRef> {
+        // The class of the value that an Optional contains is not known
+        let class_name = self.class_name.filter(|&class_name| class_name != CLASS_OPTIONAL_NAME);
+        value.serialize(JavaSerializer { jni_env: self.jni_env, class_name })
+    }
+
+    fn serialize_unit(self) -> errors::Result<LocalRef> {
+        Ok(LocalRef::null(self.jni_env))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> errors::Result<LocalRef> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> errors::Result<LocalRef> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> errors::Result<LocalRef> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> errors::Result<LocalRef> {
+        let jni_env = self.jni_env;
+        let value = value.serialize(JavaSerializer { jni_env, class_name: None })?;
+        variant_map(jni_env, variant, &value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> errors::Result<ListSerializer> {
+        self.list(len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> errors::Result<ListSerializer> {
+        self.list(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> errors::Result<ListSerializer> {
+        self.list(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize) -> errors::Result<VariantSerializer<ListSerializer>> {
+        let inner = JavaSerializer { jni_env: self.jni_env, class_name: None }.list(Some(len))?;
+        Ok(VariantSerializer { variant, inner })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> errors::Result<MapSerializer> {
+        let map = new_object(self.jni_env, CLASS_HASH_MAP, "()V", &[])?;
+        Ok(MapSerializer { jni_env: self.jni_env, map, key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> errors::Result<StructSerializer> {
+        self.structure(self.class_name)
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> errors::Result<VariantSerializer<StructSerializer>> {
+        let inner = self.structure(None)?;
+        Ok(VariantSerializer { variant, inner })
+    }
+}
+
+struct ListSerializer {
+    jni_env: *mut JNIEnv,
+    list: LocalRef,
+    element_class_name: Option<String>,
+}
+
+impl ser::SerializeSeq for ListSerializer {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> errors::Result<()> {
+        let element = value.serialize(JavaSerializer { jni_env: self.jni_env, class_name: self.element_class_name.as_deref() })?;
+        jni_utils::call_primitive_method!(self.list.raw(), CLASS_ARRAY_LIST, "add", "(Ljava/lang/Object;)Z", get_jni_call_boolean_method_a, [jvalue { l: element.raw() }], self.list.jni_env);
+        Ok(())
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        Ok(self.list)
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> errors::Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> errors::Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    jni_env: *mut JNIEnv,
+    map: LocalRef,
+    key: Option<LocalRef>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> errors::Result<()> {
+        self.key = Some(key.serialize(JavaSerializer { jni_env: self.jni_env, class_name: None })?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> errors::Result<()> {
+        let key = self.key.take().ok_or_else(|| J4RsError::RustError("A map value was serialized before its key".to_string()))?;
+        let value = value.serialize(JavaSerializer { jni_env: self.jni_env, class_name: None })?;
+        map_put(&self.map, &key, &value)
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        Ok(self.map)
+    }
+}
+
+struct StructSerializer {
+    jni_env: *mut JNIEnv,
+    target: LocalRef,
+    // Whether the struct is serialized to a Map, instead of a POJO
+    is_map: bool,
+}
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> errors::Result<()> {
+        let name = string_ref(key, self.jni_env)?;
+        let property_class_name = if self.is_map {
+            None
+        } else {
+            let class_name = call_static_object(
+                self.jni_env,
+                CLASS_OBJECT_GRAPHS,
+                "propertyClass",
+                "(Ljava/lang/Object;Ljava/lang/String;)Ljava/lang/String;",
+                &[jvalue { l: self.target.raw() }, jvalue { l: name.raw() }],
+            )?;
+            if class_name.is_null() { None } else { Some(string_from(&class_name)?) }
+        };
+        let value = value.serialize(JavaSerializer { jni_env: self.jni_env, class_name: property_class_name.as_deref() })?;
+        call_static_void(
+            self.jni_env,
+            CLASS_OBJECT_GRAPHS,
+            "setProperty",
+            "(Ljava/lang/Object;Ljava/lang/String;Ljava/lang/Object;)V",
+            &[jvalue { l: self.target.raw() }, jvalue { l: name.raw() }, jvalue { l: value.raw() }],
+        )
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        Ok(self.target)
+    }
+}
+
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<ListSerializer> {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> errors::Result<()> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        let jni_env = self.inner.jni_env;
+        let list = ser::SerializeSeq::end(self.inner)?;
+        variant_map(jni_env, self.variant, &list)
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<StructSerializer> {
+    type Ok = LocalRef;
+    type Error = J4RsError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> errors::Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> errors::Result<LocalRef> {
+        let jni_env = self.inner.jni_env;
+        let structure = ser::SerializeStruct::end(self.inner)?;
+        variant_map(jni_env, self.variant, &structure)
+    }
+}
+
+// The kinds of Java objects that the JavaDeserializer handles differently
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Null,
+    String,
+    Boolean,
+    Byte,
+    Short,
+    Integer,
+    Long,
+    Float,
+    Double,
+    Character,
+    BigInteger,
+    BigDecimal,
+    PrimitiveArray(&'static str),
+    ObjectArray,
+    Collection,
+    Map,
+    Enum,
+    Optional,
+    // Any other object, which is deserialized by its properties
+    Object,
+}
+
+const PRIMITIVE_ARRAY_CLASSES: [&str; 8] = ["[Z", "[B", "[C", "[S", "[I", "[J", "[F", "[D"];
+
+struct JavaDeserializer {
+    obj: LocalRef,
+    // Map keys that are not Strings may be deserialized as Strings, like in JSON
+    is_key: bool,
+}
+
+impl JavaDeserializer {
+    fn new(obj: LocalRef) -> JavaDeserializer {
+        JavaDeserializer { obj, is_key: false }
+    }
+
+    fn kind(&self) -> errors::Result<Kind> {
+        if self.obj.is_null() {
+            return Ok(Kind::Null);
+        }
+        let kinds = [
+            (cache::JNI_CLASS_STRING, Kind::String),
+            (cache::JNI_CLASS_INTEGER, Kind::Integer),
+            (cache::JNI_CLASS_LONG, Kind::Long),
+            (cache::JNI_CLASS_DOUBLE, Kind::Double),
+            (cache::JNI_CLASS_BOOLEAN, Kind::Boolean),
+            (CLASS_MAP, Kind::Map),
+            (CLASS_COLLECTION, Kind::Collection),
+            (CLASS_OBJECT_ARRAY, Kind::ObjectArray),
+            (cache::JNI_CLASS_FLOAT, Kind::Float),
+            (cache::JNI_CLASS_SHORT, Kind::Short),
+            (cache::JNI_CLASS_BYTE, Kind::Byte),
+            (cache::JNI_CLASS_CHARACTER, Kind::Character),
+            (CLASS_ENUM, Kind::Enum),
+            (cache::JNI_CLASS_BIG_INTEGER, Kind::BigInteger),
+            (cache::JNI_CLASS_BIG_DECIMAL, Kind::BigDecimal),
+            (CLASS_OPTIONAL, Kind::Optional),
+        ];
+        for (class_name, kind) in kinds {
+            if self.obj.is_instance_of(class_name)? {
+                return Ok(kind);
+            }
+        }
+        for class_name in PRIMITIVE_ARRAY_CLASSES {
+            if self.obj.is_instance_of(class_name)? {
+                return Ok(Kind::PrimitiveArray(class_name));
+            }
+        }
+        Ok(Kind::Object)
+    }
+
+    fn string(&self, kind: &Kind) -> errors::Result<String> {
+        match kind {
+            Kind::String => string_from(&self.obj),
+            Kind::Enum => string_from(&call_object(&self.obj, CLASS_ENUM, "name", "()Ljava/lang/String;", &[])?),
+            _ => string_from(&call_object(&self.obj, "java/lang/Object", "toString", "()Ljava/lang/String;", &[])?),
+        }
+    }
+
+    // The value of an Optional, if it is present
+    fn optional_value(&self) -> errors::Result<Option<JavaDeserializer>> {
+        let value = call_object(&self.obj, CLASS_OPTIONAL, "orElse", "(Ljava/lang/Object;)Ljava/lang/Object;", &[jvalue { l: ptr::null_mut() }])?;
+        Ok(if value.is_null() { None } else { Some(JavaDeserializer::new(value)) })
+    }
+
+    fn object_array(&self, kind: &Kind) -> errors::Result<ObjectArrayAccess> {
+        let array = match kind {
+            Kind::Collection => call_object(&self.obj, CLASS_COLLECTION, "toArray", "()[Ljava/lang/Object;", &[])?,
+            Kind::Map => call_static_object(
+                self.obj.jni_env,
+                CLASS_OBJECT_GRAPHS,
+                "entries",
+                "(Ljava/util/Map;)[Ljava/lang/Object;",
+                &[jvalue { l: self.obj.raw() }],
+            )?,
+            _ => LocalRef::new(unsafe { (opt_to_res(cache::get_jni_new_local_ref())?)(self.obj.jni_env, self.obj.raw()) }, self.obj.jni_env),
+        };
+        ObjectArrayAccess::new(array)
+    }
+
+    // The properties of a Java object, in pairs of names and values. If `names` is not empty, only these are retrieved.
+    fn properties(&self, names: &[&str]) -> errors::Result<ObjectArrayAccess> {
+        let jni_env = self.obj.jni_env;
+        let names_array = if names.is_empty() {
+            LocalRef::null(jni_env)
+        } else {
+            let array = unsafe {
+                (opt_to_res(cache::get_jni_new_object_array())?)(jni_env, names.len() as jsize, cache::get_class(cache::JNI_CLASS_STRING)?, ptr::null_mut())
+            };
+            let array = LocalRef::new(Jvm::do_return(jni_env, array)?, jni_env);
+            for (i, name) in names.iter().enumerate() {
+                let name = string_ref(name, jni_env)?;
+                unsafe {
+                    (opt_to_res(cache::get_jni_set_object_array_element())?)(jni_env, array.raw(), i as jsize, name.raw());
+                }
+            }
+            array
+        };
+        let pairs = call_static_object(
+            jni_env,
+            CLASS_OBJECT_GRAPHS,
+            "properties",
+            "(Ljava/lang/Object;[Ljava/lang/String;)[Ljava/lang/Object;",
+            &[jvalue { l: self.obj.raw() }, jvalue { l: names_array.raw() }],
+        )?;
+        ObjectArrayAccess::new(pairs)
+    }
+
+    fn primitive_array<'de, V: Visitor<'de>>(&self, class_name: &str, visitor: V) -> errors::Result<V::Value> {
+        macro_rules! visit_array_of {
+            ($rust_type:ty) => {{
+                let values = unsafe { <$rust_type>::from_java_array(self.obj.raw(), self.obj.jni_env)? };
+                visitor.visit_seq(SeqDeserializer::new(values.into_iter()))
+            }};
+        }
+
+        match class_name {
+            "[Z" => visit_array_of!(bool),
+            "[B" => visit_array_of!(u8),
+            "[C" => {
+                let values = unsafe { u16::from_java_array(self.obj.raw(), self.obj.jni_env)? };
+                let chars: Vec<char> = std::char::decode_utf16(values)
+                    .map(|c| c.map_err(de::Error::custom))
+                    .collect::<errors::Result<_>>()?;
+                visitor.visit_seq(SeqDeserializer::new(chars.into_iter()))
+            }
+            "[S" => visit_array_of!(i16),
+            "[I" => visit_array_of!(i32),
+            "[J" => visit_array_of!(i64),
+            "[F" => visit_array_of!(f32),
+            _ => visit_array_of!(f64),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for JavaDeserializer {
+    type Error = J4RsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        let kind = self.kind()?;
+        match kind {
+            Kind::Null => visitor.visit_unit(),
+            Kind::String | Kind::Enum => visitor.visit_string(self.string(&kind)?),
+            Kind::Boolean => visitor.visit_bool(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_BOOLEAN, "booleanValue", "()Z", get_jni_call_boolean_method_a, self.obj.jni_env) == JNI_TRUE),
+            Kind::Byte => visitor.visit_i8(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_BYTE, "byteValue", "()B", get_jni_call_byte_method_a, self.obj.jni_env)),
+            Kind::Short => visitor.visit_i16(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_SHORT, "shortValue", "()S", get_jni_call_short_method_a, self.obj.jni_env)),
+            Kind::Integer => visitor.visit_i32(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_INTEGER, "intValue", "()I", get_jni_call_int_method_a, self.obj.jni_env)),
+            Kind::Long => visitor.visit_i64(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_LONG, "longValue", "()J", get_jni_call_long_method_a, self.obj.jni_env)),
+            Kind::Float => visitor.visit_f32(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_FLOAT, "floatValue", "()F", get_jni_call_float_method_a, self.obj.jni_env)),
+            Kind::Double => visitor.visit_f64(jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_DOUBLE, "doubleValue", "()D", get_jni_call_double_method_a, self.obj.jni_env)),
+            Kind::Character => {
+                let c = jni_utils::call_primitive_method!(self.obj.raw(), cache::JNI_CLASS_CHARACTER, "charValue", "()C", get_jni_call_char_method_a, self.obj.jni_env);
+                match std::char::from_u32(c as u32) {
+                    Some(c) => visitor.visit_char(c),
+                    None => Err(de::Error::custom(format!("The Java char {:#x} is not a valid Rust char", c))),
+                }
+            }
+            Kind::BigInteger => {
+                let s = self.string(&kind)?;
+                if let Ok(v) = s.parse::<i64>() {
+                    visitor.visit_i64(v)
+                } else if let Ok(v) = s.parse::<u64>() {
+                    visitor.visit_u64(v)
+                } else if let Ok(v) = s.parse::<i128>() {
+                    visitor.visit_i128(v)
+                } else if let Ok(v) = s.parse::<u128>() {
+                    visitor.visit_u128(v)
+                } else {
+                    // Only the types that parse big numbers (eg. j4rs::BigInteger) accept the decimal string
+                    visitor.visit_string(s)
+                }
+            }
+            // The decimal string is exact, unlike a float
+            Kind::BigDecimal => visitor.visit_string(self.string(&kind)?),
+            Kind::PrimitiveArray(class_name) => self.primitive_array(class_name, visitor),
+            Kind::ObjectArray | Kind::Collection => visitor.visit_seq(self.object_array(&kind)?),
+            Kind::Map => visitor.visit_map(self.object_array(&kind)?),
+            Kind::Optional => match self.optional_value()? {
+                Some(value) => value.deserialize_any(visitor),
+                None => visitor.visit_unit(),
+            },
+            Kind::Object => visitor.visit_map(self.properties(&[])?),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        let kind = self.kind()?;
+        match kind {
+            Kind::Null | Kind::PrimitiveArray(_) | Kind::ObjectArray | Kind::Collection | Kind::Map | Kind::Optional | Kind::Object => {
+                self.deserialize_any(visitor)
+            }
+            _ if self.is_key || kind == Kind::String || kind == Kind::Enum => visitor.visit_string(self.string(&kind)?),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        if self.kind()? == Kind::PrimitiveArray("[B") {
+            visitor.visit_byte_buf(unsafe { u8::from_java_array(self.obj.raw(), self.obj.jni_env)? })
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        if self.obj.is_null() {
+            visitor.visit_none()
+        } else if self.obj.is_instance_of(CLASS_OPTIONAL)? {
+            match self.optional_value()? {
+                Some(value) => visitor.visit_some(value),
+                None => visitor.visit_none(),
+            }
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> errors::Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> errors::Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> errors::Result<V::Value> {
+        if self.kind()? == Kind::Object && !fields.is_empty() {
+            visitor.visit_map(self.properties(fields)?)
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> errors::Result<V::Value> {
+        let kind = self.kind()?;
+        match kind {
+            Kind::String | Kind::Enum => visitor.visit_enum(self.string(&kind)?.into_deserializer()),
+            Kind::Map => {
+                let mut entries = self.object_array(&kind)?;
+                if entries.length != 2 {
+                    return Err(de::Error::custom("A Map that represents an enum must contain exactly one entry"));
+                }
+                let variant = entries.next_object()?;
+                let value = entries.next_object()?;
+                visitor.visit_enum(VariantAccess { variant, value })
+            }
+            _ => Err(de::Error::custom(format!("Cannot deserialize an enum from a Java {:?}", kind))),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> errors::Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char seq tuple tuple_struct map identifier
+    }
+}
+
+// Accesses the elements of a Java Object[] as a sequence, or in pairs as a map
+struct ObjectArrayAccess {
+    array: LocalRef,
+    index: jsize,
+    length: jsize,
+}
+
+impl ObjectArrayAccess {
+    fn new(array: LocalRef) -> errors::Result<ObjectArrayAccess> {
+        let length = unsafe { (opt_to_res(cache::get_jni_get_array_length())?)(array.jni_env, array.raw()) };
+        Ok(ObjectArrayAccess { array, index: 0, length })
+    }
+
+    fn next_object(&mut self) -> errors::Result<LocalRef> {
+        let jni_env = self.array.jni_env;
+        let element = unsafe {
+            (opt_to_res(cache::get_jni_get_object_array_element())?)(jni_env, self.array.raw(), self.index)
+        };
+        self.index += 1;
+        Ok(LocalRef::new(Jvm::do_return(jni_env, element)?, jni_env))
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for ObjectArrayAccess {
+    type Error = J4RsError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> errors::Result<Option<T::Value>> {
+        if self.index >= self.length {
+            Ok(None)
+        } else {
+            seed.deserialize(JavaDeserializer::new(self.next_object()?)).map(Some)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.length - self.index) as usize)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for ObjectArrayAccess {
+    type Error = J4RsError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> errors::Result<Option<K::Value>> {
+        if self.index >= self.length {
+            Ok(None)
+        } else {
+            seed.deserialize(JavaDeserializer { obj: self.next_object()?, is_key: true }).map(Some)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> errors::Result<V::Value> {
+        seed.deserialize(JavaDeserializer::new(self.next_object()?))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(((self.length - self.index) / 2) as usize)
+    }
+}
+
+// An enum variant that is represented by a Map with a single entry
+struct VariantAccess {
+    variant: LocalRef,
+    value: LocalRef,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess {
+    type Error = J4RsError;
+    type Variant = JavaDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> errors::Result<(V::Value, JavaDeserializer)> {
+        let variant = seed.deserialize(JavaDeserializer { obj: self.variant, is_key: true })?;
+        Ok((variant, JavaDeserializer::new(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for JavaDeserializer {
+    type Error = J4RsError;
+
+    fn unit_variant(self) -> errors::Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> errors::Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> errors::Result<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> errors::Result<V::Value> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
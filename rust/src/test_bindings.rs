@@ -0,0 +1,278 @@
+// Generated by j4rs from the jar j4rs-0.12.1-SNAPSHOT-jar-with-dependencies.jar. Do not edit.
+
+/// Wraps an `Instance` of the Java class `org.astonbitecode.j4rs.tests.MyTest`.
+#[allow(dead_code)]
+pub struct MyTest {
+    instance: j4rs::Instance,
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+impl MyTest {
+    /// The name of the Java class
+    pub const CLASS_NAME: &'static str = "org.astonbitecode.j4rs.tests.MyTest";
+
+    /// Wraps an `Instance`. Its class is not checked.
+    pub fn from_instance(instance: j4rs::Instance) -> MyTest {
+        MyTest { instance }
+    }
+
+    /// The wrapped `Instance`
+    pub fn instance(&self) -> &j4rs::Instance {
+        &self.instance
+    }
+
+    /// Returns the wrapped `Instance`
+    pub fn into_instance(self) -> j4rs::Instance {
+        self.instance
+    }
+
+    /// Calls the constructor `org.astonbitecode.j4rs.tests.MyTest()`.
+    pub fn new(jvm: &j4rs::Jvm) -> j4rs::errors::Result<Self> {
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[])?;
+        Ok(Self::from_instance(instance))
+    }
+
+    /// Calls the constructor `org.astonbitecode.j4rs.tests.MyTest(java.lang.String)`.
+    pub fn new_string(jvm: &j4rs::Jvm, arg0: &str) -> j4rs::errors::Result<Self> {
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?])?;
+        Ok(Self::from_instance(instance))
+    }
+
+    /// Calls the constructor `org.astonbitecode.j4rs.tests.MyTest(java.lang.String[])`.
+    pub fn new_string_array(jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<Self> {
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[arg0])?;
+        Ok(Self::from_instance(instance))
+    }
+
+    /// Calls the constructor `org.astonbitecode.j4rs.tests.MyTest(org.astonbitecode.j4rs.tests.MyTest)`.
+    pub fn new_my_test(jvm: &j4rs::Jvm, arg0: &MyTest) -> j4rs::errors::Result<Self> {
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[j4rs::InvocationArg::from(jvm.clone_instance(arg0.instance())?)])?;
+        Ok(Self::from_instance(instance))
+    }
+
+    /// Calls `static void org.astonbitecode.j4rs.tests.MyTest.StaticMethod()`.
+    pub fn static_method(jvm: &j4rs::Jvm) -> j4rs::errors::Result<()> {
+        jvm.invoke_static(Self::CLASS_NAME, "StaticMethod", &[])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.MyTest.aMethod()`.
+    pub fn a_method(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "aMethod", &[])?;
+        Ok(())
+    }
+
+    /// Calls `java.lang.Integer org.astonbitecode.j4rs.tests.MyTest.addInts(java.lang.Integer[])`.
+    pub fn add_ints_integer_array(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<Option<i32>> {
+        let result = jvm.invoke(&self.instance, "addInts", &[arg0])?;
+        jvm.to_rust_deserialized(result)
+    }
+
+    /// Calls `java.lang.Integer org.astonbitecode.j4rs.tests.MyTest.addInts(int, int)`.
+    pub fn add_ints_int_int(&self, jvm: &j4rs::Jvm, arg0: i32, arg1: i32) -> j4rs::errors::Result<Option<i32>> {
+        let result = jvm.invoke(&self.instance, "addInts", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?.into_primitive()?, <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg1)?.into_primitive()?])?;
+        jvm.to_rust_deserialized(result)
+    }
+
+    /// Calls `static java.lang.Integer org.astonbitecode.j4rs.tests.MyTest.addNatively(java.lang.Integer, java.lang.Integer)`.
+    pub fn add_natively_integer_integer(jvm: &j4rs::Jvm, arg0: Option<i32>, arg1: Option<i32>) -> j4rs::errors::Result<Option<i32>> {
+        let result = jvm.invoke_static(Self::CLASS_NAME, "addNatively", &[match arg0 { Some(value) => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(value)?, None => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(j4rs::Null::Integer)? }, match arg1 { Some(value) => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(value)?, None => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(j4rs::Null::Integer)? }])?;
+        jvm.to_rust_deserialized(result)
+    }
+
+    /// Calls `static java.lang.String org.astonbitecode.j4rs.tests.MyTest.addNatively(java.lang.String, java.lang.String)`.
+    pub fn add_natively_string_string(jvm: &j4rs::Jvm, arg0: &str, arg1: &str) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke_static(Self::CLASS_NAME, "addNatively", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?, <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg1)?])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.lang.String org.astonbitecode.j4rs.tests.MyTest.appendToMyString(java.lang.String)`.
+    pub fn append_to_my_string(&self, jvm: &j4rs::Jvm, arg0: &str) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke(&self.instance, "appendToMyString", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.lang.Object org.astonbitecode.j4rs.tests.MyTest.echo(java.lang.Object)`.
+    pub fn echo(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<j4rs::Instance> {
+        jvm.invoke(&self.instance, "echo", &[arg0])
+    }
+
+    /// Calls `org.astonbitecode.j4rs.tests.DummyMapInterface org.astonbitecode.j4rs.tests.MyTest.getMap()`.
+    pub fn get_map(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<j4rs::Instance> {
+        jvm.invoke(&self.instance, "getMap", &[])
+    }
+
+    /// Calls `java.lang.String org.astonbitecode.j4rs.tests.MyTest.getMyString()`.
+    pub fn get_my_string(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke(&self.instance, "getMyString", &[])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.lang.String org.astonbitecode.j4rs.tests.MyTest.getMyWithArgs(java.lang.String)`.
+    pub fn get_my_with_args(&self, jvm: &j4rs::Jvm, arg0: &str) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke(&self.instance, "getMyWithArgs", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.lang.String org.astonbitecode.j4rs.tests.MyTest.getMyWithArgsList(java.lang.String[])`.
+    pub fn get_my_with_args_list(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke(&self.instance, "getMyWithArgsList", &[arg0])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.lang.Integer org.astonbitecode.j4rs.tests.MyTest.getNullInteger()`.
+    pub fn get_null_integer(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<Option<i32>> {
+        let result = jvm.invoke(&self.instance, "getNullInteger", &[])?;
+        jvm.to_rust_deserialized(result)
+    }
+
+    /// Calls `java.util.List org.astonbitecode.j4rs.tests.MyTest.getNumbersUntil(java.lang.Integer)`.
+    pub fn get_numbers_until(&self, jvm: &j4rs::Jvm, arg0: Option<i32>) -> j4rs::errors::Result<j4rs::Instance> {
+        jvm.invoke(&self.instance, "getNumbersUntil", &[match arg0 { Some(value) => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(value)?, None => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(j4rs::Null::Integer)? }])
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.MyTest.list(java.util.List)`.
+    pub fn list(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "list", &[arg0])?;
+        Ok(())
+    }
+
+    /// Calls `static int org.astonbitecode.j4rs.tests.MyTest.nativeAdd(int, int)`.
+    pub fn native_add_int_int(jvm: &j4rs::Jvm, arg0: i32, arg1: i32) -> j4rs::errors::Result<i32> {
+        let result = jvm.invoke_static(Self::CLASS_NAME, "nativeAdd", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?.into_primitive()?, <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg1)?.into_primitive()?])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `static java.lang.String org.astonbitecode.j4rs.tests.MyTest.nativeAdd(java.lang.String, java.lang.String)`.
+    pub fn native_add_string_string(jvm: &j4rs::Jvm, arg0: &str, arg1: &str) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke_static(Self::CLASS_NAME, "nativeAdd", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?, <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg1)?])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.MyTest.performCallback()`.
+    pub fn perform_callback(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "performCallback", &[])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.MyTest.throwFileNotFound(java.lang.String)`.
+    pub fn throw_file_not_found(&self, jvm: &j4rs::Jvm, arg0: &str) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "throwFileNotFound", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.MyTest.throwMyTestException(java.lang.Integer)`.
+    pub fn throw_my_test_exception(&self, jvm: &j4rs::Jvm, arg0: Option<i32>) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "throwMyTestException", &[match arg0 { Some(value) => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(value)?, None => <j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(j4rs::Null::Integer)? }])?;
+        Ok(())
+    }
+
+    /// Calls `static void org.astonbitecode.j4rs.tests.MyTest.useLongPrimitivesArray(long[])`.
+    pub fn use_long_primitives_array(jvm: &j4rs::Jvm, arg0: &[i64]) -> j4rs::errors::Result<()> {
+        jvm.invoke_static(Self::CLASS_NAME, "useLongPrimitivesArray", &[j4rs::InvocationArg::from_primitive_array(arg0)?])?;
+        Ok(())
+    }
+
+    /// Reads the field `static java.lang.String org.astonbitecode.j4rs.tests.MyTest.StaticString`.
+    pub fn static_string(jvm: &j4rs::Jvm) -> j4rs::errors::Result<String> {
+        let result = jvm.static_field(Self::CLASS_NAME, "StaticString")?;
+        jvm.to_rust(result)
+    }
+}
+
+/// Wraps an `Instance` of the Java class `org.astonbitecode.j4rs.tests.DummyBean`.
+#[allow(dead_code)]
+pub struct DummyBean {
+    instance: j4rs::Instance,
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+impl DummyBean {
+    /// The name of the Java class
+    pub const CLASS_NAME: &'static str = "org.astonbitecode.j4rs.tests.DummyBean";
+
+    /// Wraps an `Instance`. Its class is not checked.
+    pub fn from_instance(instance: j4rs::Instance) -> DummyBean {
+        DummyBean { instance }
+    }
+
+    /// The wrapped `Instance`
+    pub fn instance(&self) -> &j4rs::Instance {
+        &self.instance
+    }
+
+    /// Returns the wrapped `Instance`
+    pub fn into_instance(self) -> j4rs::Instance {
+        self.instance
+    }
+
+    /// Calls the constructor `org.astonbitecode.j4rs.tests.DummyBean()`.
+    pub fn new(jvm: &j4rs::Jvm) -> j4rs::errors::Result<Self> {
+        let instance = jvm.create_instance(Self::CLASS_NAME, &[])?;
+        Ok(Self::from_instance(instance))
+    }
+
+    /// Calls `org.astonbitecode.j4rs.tests.DummyBean org.astonbitecode.j4rs.tests.DummyBean.getChild()`.
+    pub fn get_child(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<DummyBean> {
+        let result = jvm.invoke(&self.instance, "getChild", &[])?;
+        Ok(DummyBean::from_instance(result))
+    }
+
+    /// Calls `java.lang.String org.astonbitecode.j4rs.tests.DummyBean.getFirstName()`.
+    pub fn get_first_name(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<String> {
+        let result = jvm.invoke(&self.instance, "getFirstName", &[])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `java.util.List org.astonbitecode.j4rs.tests.DummyBean.getTags()`.
+    pub fn get_tags(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<j4rs::Instance> {
+        jvm.invoke(&self.instance, "getTags", &[])
+    }
+
+    /// Calls `java.util.concurrent.TimeUnit org.astonbitecode.j4rs.tests.DummyBean.getUnit()`.
+    pub fn get_unit(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<j4rs::Instance> {
+        jvm.invoke(&self.instance, "getUnit", &[])
+    }
+
+    /// Calls `double[] org.astonbitecode.j4rs.tests.DummyBean.getWeights()`.
+    pub fn get_weights(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<Vec<f64>> {
+        let result = jvm.invoke(&self.instance, "getWeights", &[])?;
+        jvm.to_rust(result)
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.DummyBean.setChild(org.astonbitecode.j4rs.tests.DummyBean)`.
+    pub fn set_child(&self, jvm: &j4rs::Jvm, arg0: &DummyBean) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "setChild", &[j4rs::InvocationArg::from(jvm.clone_instance(arg0.instance())?)])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.DummyBean.setFirstName(java.lang.String)`.
+    pub fn set_first_name(&self, jvm: &j4rs::Jvm, arg0: &str) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "setFirstName", &[<j4rs::InvocationArg as std::convert::TryFrom<_>>::try_from(arg0)?])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.DummyBean.setTags(java.util.List)`.
+    pub fn set_tags(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "setTags", &[arg0])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.DummyBean.setUnit(java.util.concurrent.TimeUnit)`.
+    pub fn set_unit(&self, jvm: &j4rs::Jvm, arg0: j4rs::InvocationArg) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "setUnit", &[arg0])?;
+        Ok(())
+    }
+
+    /// Calls `void org.astonbitecode.j4rs.tests.DummyBean.setWeights(double[])`.
+    pub fn set_weights(&self, jvm: &j4rs::Jvm, arg0: &[f64]) -> j4rs::errors::Result<()> {
+        jvm.invoke(&self.instance, "setWeights", &[j4rs::InvocationArg::from_primitive_array(arg0)?])?;
+        Ok(())
+    }
+
+    /// Reads the field `long org.astonbitecode.j4rs.tests.DummyBean.count`.
+    pub fn count(&self, jvm: &j4rs::Jvm) -> j4rs::errors::Result<i64> {
+        let result = jvm.field(&self.instance, "count")?;
+        jvm.to_rust(result)
+    }
+}
@@ -137,7 +137,9 @@ fn find_j4rs_dynamic_libraries_dir_entries() -> errors::Result<Vec<fs::DirEntry>
             let entry = entry.as_ref().unwrap();
             let file_name = entry.file_name();
             let file_name = file_name.to_str().unwrap();
-            file_name.contains("j4rs") && (
+            // Other libraries, like the one of j4rs_derive, may be found in the deps directory as well
+            let lib_name = file_name.trim_start_matches("lib").split('.').next().unwrap_or_default();
+            (lib_name == "j4rs" || lib_name.starts_with("j4rs-")) && (
                 file_name.contains(".so") ||
                     file_name.contains(".dll") ||
                     file_name.contains(".dylib"))
@@ -167,6 +169,7 @@ pub(crate) fn get_class_name(inv_arg: &InvocationArg) -> &str {
     let class_name = match inv_arg {
         &InvocationArg::Java { instance: _, ref class_name, serialized: _ } => class_name,
         &InvocationArg::Rust { json: _, ref class_name, serialized: _ } => class_name,
+        &InvocationArg::RustEncoded { ref class_name, .. } => class_name,
         &InvocationArg::RustBasic { instance: _, ref class_name, serialized: _ } => class_name,
     };
     class_name.as_ref()